@@ -30,6 +30,7 @@ pub struct CliArguments {
 pub enum DiagnosticFormat {
     Human,
     Short,
+    Json,
 }
 
 impl Display for DiagnosticFormat {
@@ -79,7 +80,7 @@ pub struct CompileCommand {
     /// Path to input Typst file
     pub input: PathBuf,
 
-    /// Path to output PDF file or PNG file(s)
+    /// Path to output PDF or HTML file, or PNG/SVG file(s)
     pub output: Option<PathBuf>,
 
     /// Opens the output file after compilation using the default PDF viewer
@@ -90,6 +91,14 @@ pub struct CompileCommand {
     #[arg(long = "ppi")]
     pub ppi: Option<f32>,
 
+    /// Omits the bookmarks that are generated from headings in PDF output
+    #[arg(long = "no-bookmarks")]
+    pub no_bookmarks: bool,
+
+    /// Tags the content of PDF output with a structure tree for screen readers
+    #[arg(long = "tagged")]
+    pub tagged: bool,
+
     /// In which format to emit diagnostics
     #[clap(
         long,
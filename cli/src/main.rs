@@ -24,9 +24,12 @@ use siphasher::sip128::{Hasher128, SipHasher13};
 use termcolor::{ColorChoice, StandardStream, WriteColor};
 use time::macros::format_description;
 use time::Duration;
-use typst::diag::{FileError, FileResult, SourceError, StrResult};
+use typst::diag::{
+    FileError, FileResult, PackageError, Severity, SourceDiagnostic, StrResult,
+};
 use typst::doc::Document;
-use typst::eval::{Datetime, Library};
+use typst::eval::{Datetime, Library, PackageSpec, Tracer};
+use typst::export::PdfOptions;
 use typst::font::{Font, FontBook, FontInfo, FontVariant};
 use typst::geom::Color;
 use typst::syntax::{Source, SourceId};
@@ -114,6 +117,12 @@ struct CompileSettings {
     /// The PPI to use for PNG export.
     ppi: Option<f32>,
 
+    /// Whether to generate bookmarks in PDF export.
+    bookmarks: bool,
+
+    /// Whether to tag the content in PDF export.
+    tagged: bool,
+
     /// In which format to emit diagnostics
     diagnostic_format: DiagnosticFormat,
 }
@@ -129,6 +138,8 @@ impl CompileSettings {
         font_paths: Vec<PathBuf>,
         open: Option<Option<String>>,
         ppi: Option<f32>,
+        bookmarks: bool,
+        tagged: bool,
         diagnostic_format: DiagnosticFormat,
     ) -> Self {
         let output = match output {
@@ -144,6 +155,8 @@ impl CompileSettings {
             open,
             diagnostic_format,
             ppi,
+            bookmarks,
+            tagged,
         }
     }
 
@@ -153,12 +166,20 @@ impl CompileSettings {
     /// Panics if the command is not a compile or watch command.
     fn with_arguments(args: CliArguments) -> Self {
         let watch = matches!(args.command, Command::Watch(_));
-        let CompileCommand { input, output, open, ppi, diagnostic_format, .. } =
-            match args.command {
-                Command::Compile(command) => command,
-                Command::Watch(command) => command,
-                _ => unreachable!(),
-            };
+        let CompileCommand {
+            input,
+            output,
+            open,
+            ppi,
+            no_bookmarks,
+            tagged,
+            diagnostic_format,
+            ..
+        } = match args.command {
+            Command::Compile(command) => command,
+            Command::Watch(command) => command,
+            _ => unreachable!(),
+        };
 
         Self::new(
             input,
@@ -168,6 +189,8 @@ impl CompileSettings {
             args.font_paths,
             open,
             ppi,
+            !no_bookmarks,
+            tagged,
             diagnostic_format,
         )
     }
@@ -266,7 +289,7 @@ fn compile(mut command: CompileSettings) -> StrResult<()> {
 
         if recompile {
             let ok = compile_once(&mut world, &command)?;
-            comemo::evict(30);
+            typst::evict(30);
 
             // Ipen the file if requested, this must be done on the first
             // **successful** compilation
@@ -291,11 +314,17 @@ fn compile_once(world: &mut SystemWorld, command: &CompileSettings) -> StrResult
     world.reset();
     world.main = world.resolve(&command.input).map_err(|err| err.to_string())?;
 
-    match typst::compile(world) {
-        // Export the PDF / PNG.
+    let mut tracer = Tracer::default();
+    let result = typst::compile(world, &mut tracer);
+    let warnings = tracer.warnings();
+
+    match result {
+        // Export the PDF / PNG / SVG / HTML.
         Ok(document) => {
             export(&document, command)?;
             status(command, Status::Success).unwrap();
+            print_diagnostics(world, &[], warnings, command.diagnostic_format)
+                .map_err(|_| "failed to print diagnostics")?;
             tracing::info!("Compilation succeeded");
             Ok(true)
         }
@@ -304,7 +333,7 @@ fn compile_once(world: &mut SystemWorld, command: &CompileSettings) -> StrResult
         Err(errors) => {
             set_failed();
             status(command, Status::Error).unwrap();
-            print_diagnostics(world, *errors, command.diagnostic_format)
+            print_diagnostics(world, &errors, warnings, command.diagnostic_format)
                 .map_err(|_| "failed to print diagnostics")?;
             tracing::info!("Compilation failed");
             Ok(false)
@@ -341,8 +370,38 @@ fn export(document: &Document, command: &CompileSettings) -> StrResult<()> {
                 pixmap.save_png(path).map_err(|_| "failed to write PNG file")?;
             }
         }
+        Some(ext) if ext.eq_ignore_ascii_case("svg") => {
+            let string = command.output.to_str().unwrap_or_default();
+            let numbered = string.contains("{n}");
+            if !numbered && document.pages.len() > 1 {
+                Err("cannot export multiple SVGs without `{n}` in output path")?;
+            }
+
+            let width = 1 + document.pages.len().checked_ilog10().unwrap_or(0) as usize;
+            let mut storage;
+
+            for (i, frame) in document.pages.iter().enumerate() {
+                let svg = typst::export::svg(frame);
+                let path = if numbered {
+                    storage = string.replace("{n}", &format!("{:0width$}", i + 1));
+                    Path::new(&storage)
+                } else {
+                    command.output.as_path()
+                };
+                fs::write(path, svg).map_err(|_| "failed to write SVG file")?;
+            }
+        }
+        Some(ext) if ext.eq_ignore_ascii_case("html") => {
+            let html = typst::export::html(document);
+            fs::write(&command.output, html).map_err(|_| "failed to write HTML file")?;
+        }
         _ => {
-            let buffer = typst::export::pdf(document);
+            let options = PdfOptions {
+                bookmarks: command.bookmarks,
+                tagged: command.tagged,
+                ..Default::default()
+            };
+            let buffer = typst::export::pdf_with_options(document, options);
             fs::write(&command.output, buffer).map_err(|_| "failed to write PDF file")?;
         }
     }
@@ -424,12 +483,22 @@ impl Status {
 /// Print diagnostic messages to the terminal.
 fn print_diagnostics(
     world: &SystemWorld,
-    errors: Vec<SourceError>,
+    errors: &[SourceDiagnostic],
+    warnings: &[SourceDiagnostic],
     diagnostic_format: DiagnosticFormat,
 ) -> Result<(), codespan_reporting::files::Error> {
+    if errors.is_empty() && warnings.is_empty() {
+        return Ok(());
+    }
+
     let mut w = match diagnostic_format {
         DiagnosticFormat::Human => color_stream(),
         DiagnosticFormat::Short => StandardStream::stderr(ColorChoice::Never),
+        DiagnosticFormat::Json => {
+            let all: Vec<_> = errors.iter().chain(warnings).cloned().collect();
+            eprintln!("{}", typst::diag::serialize_errors(&all, world));
+            return Ok(());
+        }
     };
 
     let mut config = term::Config { tab_width: 2, ..Default::default() };
@@ -437,17 +506,25 @@ fn print_diagnostics(
         config.display_style = term::DisplayStyle::Short;
     }
 
-    for error in errors {
-        // The main diagnostic.
-        let range = error.range(world);
-        let diag = Diagnostic::error()
-            .with_message(error.message)
-            .with_labels(vec![Label::primary(error.span.source(), range)]);
+    for diagnostic in warnings.iter().chain(errors) {
+        // The main diagnostic. Diagnostics without a span concern the whole
+        // document and are shown without a label.
+        let mut diag = match diagnostic.severity {
+            Severity::Error => Diagnostic::error(),
+            Severity::Warning => Diagnostic::warning(),
+        }
+        .with_message(diagnostic.message.clone());
+        if !diagnostic.span.is_detached() {
+            diag = diag.with_labels(vec![Label::primary(
+                diagnostic.span.source(),
+                diagnostic.range(world),
+            )]);
+        }
 
         term::emit(&mut w, &config, world, &diag)?;
 
         // Stacktrace-like helper diagnostics.
-        for point in error.trace {
+        for point in &diagnostic.trace {
             let message = point.v.to_string();
             let help = Diagnostic::help().with_message(message).with_labels(vec![
                 Label::primary(
@@ -503,7 +580,7 @@ struct SystemWorld {
     hashes: RefCell<HashMap<PathBuf, FileResult<PathHash>>>,
     paths: RefCell<HashMap<PathHash, PathSlot>>,
     sources: FrozenVec<Box<Source>>,
-    current_date: Cell<Option<Datetime>>,
+    now: OnceCell<time::OffsetDateTime>,
     main: SourceId,
 }
 
@@ -534,7 +611,7 @@ impl SystemWorld {
             hashes: RefCell::default(),
             paths: RefCell::default(),
             sources: FrozenVec::new(),
-            current_date: Cell::new(None),
+            now: OnceCell::new(),
             main: SourceId::detached(),
         }
     }
@@ -590,21 +667,32 @@ impl World for SystemWorld {
             .clone()
     }
 
-    fn today(&self, offset: Option<i64>) -> Option<Datetime> {
-        if self.current_date.get().is_none() {
-            let datetime = match offset {
-                None => time::OffsetDateTime::now_local().ok()?,
-                Some(o) => time::OffsetDateTime::now_utc().add(Duration::hours(o)),
-            };
+    fn package(&self, spec: &PackageSpec) -> FileResult<PathBuf> {
+        // Look for the package in the local data and cache directories.
+        let subdir =
+            format!("typst/packages/{}/{}/{}", spec.namespace, spec.name, spec.version);
+        [dirs::data_dir(), dirs::cache_dir()]
+            .into_iter()
+            .flatten()
+            .map(|dir| dir.join(&subdir))
+            .find(|dir| dir.is_dir())
+            .ok_or_else(|| PackageError::NotFound(spec.clone()).into())
+    }
 
-            self.current_date.set(Some(Datetime::from_ymd(
-                datetime.year(),
-                datetime.month().try_into().ok()?,
-                datetime.day(),
-            )?))
-        }
+    fn today(&self, offset: Option<i64>) -> Option<Datetime> {
+        // Fix the current time once per compilation so that all calls agree,
+        // whatever offset they ask for.
+        let now = *self.now.get_or_init(time::OffsetDateTime::now_utc);
+        let datetime = match offset {
+            None => now.to_offset(time::UtcOffset::local_offset_at(now).ok()?),
+            Some(o) => now.add(Duration::hours(o)),
+        };
 
-        self.current_date.get()
+        Datetime::from_ymd(
+            datetime.year(),
+            datetime.month().try_into().ok()?,
+            datetime.day(),
+        )
     }
 }
 
@@ -667,7 +755,7 @@ impl SystemWorld {
         self.sources.as_mut().clear();
         self.hashes.borrow_mut().clear();
         self.paths.borrow_mut().clear();
-        self.current_date.set(None);
+        self.now.take();
     }
 }
 
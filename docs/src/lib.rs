@@ -19,8 +19,8 @@ use serde_yaml as yaml;
 use typst::doc::Frame;
 use typst::eval::{CastInfo, Func, FuncInfo, Library, Module, ParamInfo, Value};
 use typst::font::{Font, FontBook};
-use typst::geom::{Abs, Sides, Smart};
-use typst_library::layout::PageElem;
+use typst::geom::{Abs, Smart};
+use typst_library::layout::{Margin, PageElem};
 use unscanny::Scanner;
 
 static SRC: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src");
@@ -43,7 +43,7 @@ static LIBRARY: Lazy<Prehashed<Library>> = Lazy::new(|| {
     lib.styles
         .set(PageElem::set_width(Smart::Custom(Abs::pt(240.0).into())));
     lib.styles.set(PageElem::set_height(Smart::Auto));
-    lib.styles.set(PageElem::set_margin(Sides::splat(Some(Smart::Custom(
+    lib.styles.set(PageElem::set_margin(Margin::splat(Some(Smart::Custom(
         Abs::pt(15.0).into(),
     )))));
     typst::eval::set_lang_items(lib.items.clone());
@@ -931,7 +931,11 @@ const TYPE_ORDER: &[&str] = &[
     "relative length",
     "fraction",
     "color",
+    "gradient",
     "datetime",
+    "duration",
+    "bytes",
+    "random generator",
     "string",
     "regex",
     "label",
@@ -3,8 +3,9 @@ use std::str::FromStr;
 
 use time::{Month, PrimitiveDateTime};
 
-use typst::eval::{Datetime, Dynamic, Regex};
+use typst::eval::{Bytes, Datetime, Duration, Dynamic, Regex, Rng};
 
+use super::Encoding;
 use crate::prelude::*;
 
 /// Convert a value to an integer.
@@ -117,6 +118,7 @@ pub fn luma(
 /// #square(fill: rgb("#b1f2eb"))
 /// #square(fill: rgb(87, 127, 230))
 /// #square(fill: rgb(25%, 13%, 65%))
+/// #square(fill: rgb(cmyk(27%, 0%, 3%, 5%)))
 /// ```
 ///
 /// Display: RGB
@@ -138,6 +140,11 @@ pub fn rgb(
     /// ```
     #[external]
     hex: EcoString,
+    /// An existing color to convert to RGB(A).
+    ///
+    /// If a color is given, the individual components should not be given.
+    #[external]
+    color: Color,
     /// The red component.
     #[external]
     red: Component,
@@ -156,6 +163,8 @@ pub fn rgb(
             Ok(color) => color.into(),
             Err(msg) => bail!(string.span, msg),
         }
+    } else if let Some(color) = args.find::<Color>()? {
+        color.to_rgba().into()
     } else {
         let Component(r) = args.expect("red component")?;
         let Component(g) = args.expect("green component")?;
@@ -325,6 +334,9 @@ cast_from_value!(
 
 /// Returns the current date.
 ///
+/// Without an offset, this is the local date as determined by the
+/// environment the document is compiled in.
+///
 /// ## Example
 /// ```example
 /// Today's date is
@@ -336,8 +348,9 @@ cast_from_value!(
 /// Returns: datetime
 #[func]
 pub fn datetime_today(
-    /// An offset to apply to the current UTC date. If set to `{auto}`, the
-    /// offset will be the local offset.
+    /// An offset in hours to apply to the current UTC date. For example,
+    /// `{offset: 2}` yields the current date in UTC+2. If set to `{auto}`,
+    /// the local offset is used.
     #[named]
     #[default]
     offset: Smart<i64>,
@@ -350,6 +363,80 @@ pub fn datetime_today(
     Value::Dyn(Dynamic::new(current_date))
 }
 
+/// Create a new duration.
+///
+/// Durations also result from subtracting two
+/// [datetimes]($type/datetime) of the same kind and can be added to or
+/// subtracted from datetimes.
+///
+/// ## Example { #example }
+/// ```example
+/// #let deadline = datetime(year: 2023, month: 6, day: 30)
+/// #let start = datetime(year: 2023, month: 6, day: 1)
+/// #(deadline - start).days() days to go. \
+/// #(start + duration(weeks: 1)).display()
+/// ```
+///
+/// Display: Duration
+/// Category: construct
+/// Returns: duration
+#[func]
+pub fn duration(
+    /// The number of seconds.
+    #[named]
+    #[default]
+    seconds: i64,
+    /// The number of minutes.
+    #[named]
+    #[default]
+    minutes: i64,
+    /// The number of hours.
+    #[named]
+    #[default]
+    hours: i64,
+    /// The number of days.
+    #[named]
+    #[default]
+    days: i64,
+    /// The number of weeks.
+    #[named]
+    #[default]
+    weeks: i64,
+) -> Value {
+    match Duration::from_parts(weeks, days, hours, minutes, seconds) {
+        Some(duration) => Value::dynamic(duration),
+        None => bail!(args.span, "duration is too large"),
+    }
+}
+
+/// Create a seeded pseudo-random number generator.
+///
+/// Typst documents compile to the same output every time, so there is no
+/// source of true randomness. Instead, a generator produces a fixed sequence
+/// of numbers for each seed. Store it in a variable and call its methods to
+/// draw numbers, which advances the generator.
+///
+/// ## Example { #example }
+/// ```example
+/// #let rng = random(42)
+/// #for i in range(8) {
+///   let size = 5pt + rng.next() * 10pt
+///   box(circle(radius: size / 2, fill: blue))
+/// }
+/// ```
+///
+/// Display: Random
+/// Category: construct
+/// Returns: random generator
+#[func]
+pub fn random(
+    /// The seed of the generator. Generators with the same seed produce the
+    /// same numbers.
+    seed: i64,
+) -> Value {
+    Value::dynamic(Rng::new(seed))
+}
+
 /// Create a CMYK color.
 ///
 /// This is useful if you want to target a specific printer. The conversion
@@ -380,6 +467,161 @@ pub fn cmyk(
     Value::Color(CmykColor::new(cyan.0, magenta.0, yellow.0, key.0).into())
 }
 
+/// Create an RGB color from hue, saturation, and lightness.
+///
+/// The resulting color is converted to and stored in the sRGB color space.
+///
+/// ## Example { #example }
+/// ```example
+/// #square(fill: hsl(210deg, 50%, 60%))
+/// #square(fill: hsl(30deg, 100%, 50%))
+/// ```
+///
+/// Display: HSL
+/// Category: construct
+/// Returns: color
+#[func]
+pub fn hsl(
+    /// The hue angle.
+    hue: Angle,
+    /// The saturation component.
+    saturation: Spanned<Ratio>,
+    /// The lightness component.
+    lightness: Spanned<Ratio>,
+) -> Value {
+    for component in [&saturation, &lightness] {
+        if !(0.0..=1.0).contains(&component.v.get()) {
+            bail!(component.span, "ratio must be between 0% and 100%");
+        }
+    }
+
+    Value::Color(RgbaColor::from_hsl(hue, saturation.v.get(), lightness.v.get()).into())
+}
+
+/// Create a linear gradient.
+///
+/// This is the same as [`gradient.linear`]($func/gradient.linear), which is
+/// currently the only kind of gradient.
+///
+/// Display: Gradient
+/// Category: construct
+/// Returns: gradient
+#[func]
+#[scope(
+    scope.define("linear", gradient_linear);
+    scope
+)]
+pub fn gradient(
+    /// The color stops of the gradient.
+    #[variadic]
+    stops: Vec<Spanned<GradientStop>>,
+    /// The direction of the gradient.
+    #[named]
+    #[default]
+    angle: Angle,
+) -> Value {
+    linear_gradient(stops, angle, args.span)?
+}
+
+/// Create a linear gradient.
+///
+/// A gradient can be used anywhere a color fill is accepted, for example for
+/// shapes and text. Each color stop is either a pair of an offset along the
+/// gradient's axis and a color, or just a color. Stops without an offset are
+/// spread out evenly between their neighbours. A gradient with just one stop
+/// is the same as that stop's color.
+///
+/// ## Example { #example }
+/// ```example
+/// #rect(fill: gradient.linear((0%, red), (100%, blue)))
+/// #rect(fill: gradient.linear(red, yellow, green, angle: 45deg))
+/// #text(fill: gradient.linear(navy, aqua))[Gradient text]
+/// ```
+///
+/// Display: Linear Gradient
+/// Category: construct
+/// Returns: gradient
+#[func]
+pub fn gradient_linear(
+    /// The color stops of the gradient.
+    #[variadic]
+    stops: Vec<Spanned<GradientStop>>,
+    /// The direction of the gradient. At `{0deg}`, the gradient runs from left
+    /// to right. Positive angles rotate it clockwise.
+    #[named]
+    #[default]
+    angle: Angle,
+) -> Value {
+    linear_gradient(stops, angle, args.span)?
+}
+
+/// Build a linear gradient from its stops, filling in missing offsets.
+fn linear_gradient(
+    stops: Vec<Spanned<GradientStop>>,
+    angle: Angle,
+    span: Span,
+) -> SourceResult<Value> {
+    let mut last = Ratio::zero();
+    for Spanned { v: GradientStop(offset, _), span } in &stops {
+        if let Some(offset) = *offset {
+            if !(0.0..=1.0).contains(&offset.get()) {
+                bail!(*span, "offset must be between 0% and 100%");
+            } else if offset < last {
+                bail!(*span, "offsets must be in ascending order");
+            }
+            last = offset;
+        }
+    }
+
+    let mut offsets: Vec<_> = stops.iter().map(|stop| stop.v.0).collect();
+    if let Some(first) = offsets.first_mut() {
+        first.get_or_insert(Ratio::zero());
+    }
+    if let Some(last) = offsets.last_mut() {
+        last.get_or_insert(Ratio::one());
+    }
+
+    // Spread stops without an offset evenly between their neighbours.
+    let mut start = 0;
+    for i in 1..offsets.len() {
+        if let Some(end) = offsets[i] {
+            let from = offsets[start].unwrap_or_default();
+            let count = (i - start) as f64;
+            for (k, offset) in offsets[start + 1..i].iter_mut().enumerate() {
+                *offset = Some(from + (end - from) * ((k + 1) as f64 / count));
+            }
+            start = i;
+        }
+    }
+
+    let stops: Vec<_> = offsets
+        .into_iter()
+        .zip(stops)
+        .map(|(offset, stop)| (offset.unwrap_or_default(), stop.v.1))
+        .collect();
+
+    Ok(match stops.as_slice() {
+        [] => bail!(span, "gradient must have at least one stop"),
+        [(_, color)] => Value::Color(*color),
+        _ => Value::dynamic(LinearGradient { stops, angle }),
+    })
+}
+
+/// A color stop of a gradient with an optional offset.
+struct GradientStop(Option<Ratio>, Color);
+
+cast_from_value! {
+    GradientStop,
+    color: Color => Self(None, color),
+    array: Array => {
+        let mut iter = array.into_iter();
+        match (iter.next(), iter.next(), iter.next()) {
+            (Some(offset), Some(color), None) => Self(Some(offset.cast()?), color.cast()?),
+            _ => Err("array must contain exactly an offset and a color")?,
+        }
+    },
+}
+
 /// A component that must be a ratio.
 struct RatioComponent(u8);
 
@@ -459,13 +701,15 @@ cast_from_value! {
 /// - Integers are formatted in base 10.
 /// - Floats are formatted in base 10 and never in exponential notation.
 /// - From labels the name is extracted.
+/// - Bytes are decoded or encoded with the given `encoding`.
 ///
 /// ## Example { #example }
 /// ```example
 /// #str(10) \
 /// #str(2.7) \
 /// #str(1e8) \
-/// #str(<intro>)
+/// #str(<intro>) \
+/// #str(bytes("Hi"), encoding: "hex")
 /// ```
 ///
 /// Display: String
@@ -474,20 +718,95 @@ cast_from_value! {
 #[func]
 pub fn str(
     /// The value that should be converted to a string.
-    value: ToStr,
+    value: Spanned<ToStr>,
+    /// How to turn bytes into text. Only applies to bytes.
+    #[named]
+    #[default]
+    encoding: Encoding,
 ) -> Value {
-    Value::Str(value.0)
+    let Spanned { v: value, span } = value;
+    match value {
+        ToStr::Str(string) => Value::Str(string),
+        ToStr::Bytes(bytes) => Value::Str(encoding.encode(&bytes).at(span)?.into()),
+    }
 }
 
 /// A value that can be cast to a string.
-struct ToStr(Str);
+enum ToStr {
+    /// A value that is already converted.
+    Str(Str),
+    /// Bytes that still need to be encoded.
+    Bytes(Bytes),
+}
 
 cast_from_value! {
     ToStr,
-    v: i64 => Self(format_str!("{}", v)),
-    v: f64 => Self(format_str!("{}", v)),
-    v: Label => Self(v.0.into()),
-    v: Str => Self(v),
+    v: i64 => Self::Str(format_str!("{}", v)),
+    v: f64 => Self::Str(format_str!("{}", v)),
+    v: Label => Self::Str(v.0.into()),
+    v: Str => Self::Str(v),
+    v: Bytes => Self::Bytes(v),
+}
+
+/// Convert a value to bytes.
+///
+/// - Strings are decoded with the given `encoding`, which defaults to UTF-8.
+/// - Arrays must consist of integers between `{0}` and `{255}`.
+///
+/// Bytes are never implicitly converted from or to strings. Use this function
+/// and the [`str`]($func/str) function to convert between both explicitly.
+///
+/// ## Example { #example }
+/// ```example
+/// #let data = bytes("Hello")
+/// #data.len() \
+/// #str(data, encoding: "base64") \
+/// #str(bytes((72, 105)))
+/// ```
+///
+/// Display: Bytes
+/// Category: construct
+/// Returns: bytes
+#[func]
+pub fn bytes(
+    /// The value that should be converted to bytes.
+    value: Spanned<ToBytes>,
+    /// How the text is encoded. Only applies to strings.
+    #[named]
+    #[default]
+    encoding: Encoding,
+) -> Value {
+    let Spanned { v: value, span } = value;
+    Value::dynamic(match value {
+        ToBytes::Str(text) => encoding.decode(&text).at(span)?,
+        ToBytes::Bytes(bytes) => bytes,
+    })
+}
+
+/// A value that can be cast to bytes.
+enum ToBytes {
+    /// Text that still needs to be decoded.
+    Str(Str),
+    /// A value that is already converted.
+    Bytes(Bytes),
+}
+
+cast_from_value! {
+    ToBytes,
+    v: Str => Self::Str(v),
+    v: Bytes => Self::Bytes(v),
+    v: Array => Self::Bytes(
+        v.into_iter()
+            .map(|value| {
+                value
+                    .cast::<i64>()
+                    .ok()
+                    .and_then(|byte| u8::try_from(byte).ok())
+                    .ok_or("array must only contain integers between 0 and 255")
+            })
+            .collect::<Result<Vec<u8>, _>>()?
+            .into()
+    ),
 }
 
 /// Create a label from a string.
@@ -1,10 +1,14 @@
 use typst::diag::{format_xml_like_error, FileError};
+use typst::eval::Bytes;
 
 use crate::prelude::*;
 
-/// Read plain text from a file.
+/// Read plain text or raw bytes from a file.
 ///
-/// The file will be read and returned as a string.
+/// By default, the file will be read as UTF-8 and returned as a string. If
+/// the file is not valid UTF-8, an error naming the file is raised. With
+/// `{encoding: none}`, the file's raw [bytes]($type/bytes) are returned
+/// instead.
 ///
 /// ## Example { #example }
 /// ```example
@@ -16,19 +20,62 @@ use crate::prelude::*;
 ///
 /// Display: Plain text
 /// Category: data-loading
-/// Returns: string
+/// Returns: string or bytes
 #[func]
 pub fn read(
     /// Path to a file.
     path: Spanned<EcoString>,
+    /// The encoding to read the file with.
+    ///
+    /// With `"base64"` or `"hex"`, the file's bytes are returned as a string
+    /// of base64 or hexadecimal digits, which is useful for data URLs. If set
+    /// to `{none}`, the raw bytes are returned.
+    #[named]
+    #[default(Some(Encoding::Utf8))]
+    encoding: Option<Encoding>,
 ) -> Value {
     let Spanned { v: path, span } = path;
-    let path = vm.locate(&path).at(span)?;
-    let data = vm.world().file(&path).at(span)?;
-    let text = std::str::from_utf8(&data)
-        .map_err(|_| "file is not valid utf-8")
-        .at(span)?;
-    Value::Str(text.into())
+    let resolved = vm.locate(&path).at(span)?;
+    let data = Bytes::from(vm.world().file(&resolved).at(span)?);
+    match encoding {
+        None => Value::dynamic(data),
+        Some(Encoding::Utf8) if data.to_utf8().is_err() => {
+            bail!(span, "file is not valid utf-8 (at {})", path)
+        }
+        Some(encoding) => Value::Str(encoding.encode(&data).at(span)?.into()),
+    }
+}
+
+/// How bytes are represented as text.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Encoding {
+    /// The Unicode UTF-8 encoding.
+    #[default]
+    Utf8,
+    /// Base64 with the standard alphabet and padding.
+    Base64,
+    /// Two lowercase hexadecimal digits per byte.
+    Hex,
+}
+
+impl Encoding {
+    /// Turn bytes into text in this encoding.
+    pub fn encode(self, bytes: &Bytes) -> StrResult<EcoString> {
+        Ok(match self {
+            Self::Utf8 => bytes.to_utf8()?.into(),
+            Self::Base64 => bytes.to_base64(),
+            Self::Hex => bytes.to_hex(),
+        })
+    }
+
+    /// Turn text in this encoding into bytes.
+    pub fn decode(self, text: &str) -> StrResult<Bytes> {
+        match self {
+            Self::Utf8 => Ok(text.as_bytes().into()),
+            Self::Base64 => Bytes::from_base64(text),
+            Self::Hex => Bytes::from_hex(text),
+        }
+    }
 }
 
 /// Read structured data from a CSV file.
@@ -36,7 +83,7 @@ pub fn read(
 /// The CSV file will be read and parsed into a 2-dimensional array of strings:
 /// Each row in the CSV file will be represented as an array of strings, and all
 /// rows will be collected into a single array. Header rows will not be
-/// stripped.
+/// stripped unless `header` is set to `{true}`.
 ///
 /// ## Example { #example }
 /// ```example
@@ -61,6 +108,19 @@ pub fn csv(
     #[named]
     #[default]
     delimiter: Delimiter,
+    /// Whether the first row contains the names of the columns. If `{true}`,
+    /// each following row is represented as a dictionary mapping from these
+    /// names to the row's fields instead of as an array.
+    ///
+    /// ```example
+    /// #let animals = csv("zoo.csv", header: true)
+    /// #for animal in animals [
+    ///   - #animal.Name is a #animal.Species
+    /// ]
+    /// ```
+    #[named]
+    #[default]
+    header: bool,
 ) -> Value {
     let Spanned { v: path, span } = path;
     let path = vm.locate(&path).at(span)?;
@@ -72,14 +132,23 @@ pub fn csv(
 
     let mut reader = builder.from_reader(data.as_slice());
     let mut array = Array::new();
+    let mut names: Option<Vec<Str>> = None;
 
     for (line, result) in reader.records().enumerate() {
         // Original solution use line from error, but that is incorrect with has_headers set to false
         // See issue: https://github.com/BurntSushi/rust-csv/issues/184
         let line = line + 1; // Counting lines from 1
         let row = result.map_err(|err| format_csv_error(err, line)).at(span)?;
-        let sub = row.into_iter().map(|field| Value::Str(field.into())).collect();
-        array.push(Value::Array(sub))
+        if header && line == 1 {
+            names = Some(row.into_iter().map(Str::from).collect());
+            continue;
+        }
+
+        let fields = row.into_iter().map(|field| Value::Str(field.into()));
+        array.push(match &names {
+            Some(names) => Value::Dict(names.iter().cloned().zip(fields).collect()),
+            None => Value::Array(fields.collect()),
+        });
     }
 
     Value::Array(array)
@@ -179,28 +248,7 @@ pub fn json(
     let data = vm.world().file(&path).at(span)?;
     let value: serde_json::Value =
         serde_json::from_slice(&data).map_err(format_json_error).at(span)?;
-    convert_json(value)
-}
-
-/// Convert a JSON value to a Typst value.
-fn convert_json(value: serde_json::Value) -> Value {
-    match value {
-        serde_json::Value::Null => Value::None,
-        serde_json::Value::Bool(v) => Value::Bool(v),
-        serde_json::Value::Number(v) => match v.as_i64() {
-            Some(int) => Value::Int(int),
-            None => Value::Float(v.as_f64().unwrap_or(f64::NAN)),
-        },
-        serde_json::Value::String(v) => Value::Str(v.into()),
-        serde_json::Value::Array(v) => {
-            Value::Array(v.into_iter().map(convert_json).collect())
-        }
-        serde_json::Value::Object(v) => Value::Dict(
-            v.into_iter()
-                .map(|(key, value)| (key.into(), convert_json(value)))
-                .collect(),
-        ),
-    }
+    Value::from_json(value)
 }
 
 /// Format the user-facing JSON error message.
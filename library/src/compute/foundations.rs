@@ -1,3 +1,8 @@
+use std::fmt::Write;
+
+use sha2::{Digest, Sha256};
+use typst::eval::Bytes;
+
 use crate::prelude::*;
 
 /// Determine a value's type.
@@ -53,6 +58,61 @@ pub fn repr(
     value.repr().into()
 }
 
+/// Compute a hash of a value.
+///
+/// Returns the hash as a string of 32 hexadecimal digits. Equal values have
+/// equal hashes, so this is useful for deriving identifiers or cache keys
+/// from data.
+///
+/// **Note:** The hash is only guaranteed to be stable for the same Typst
+/// version on the same platform. It may change between versions. Content
+/// also hashes the position in the source code it was written at. For a hash
+/// that is stable everywhere, use [`sha256`]($func/sha256).
+///
+/// ## Example { #example }
+/// ```example
+/// #let id = hash((title: "Intro", page: 1))
+/// #id.len() hex digits
+/// ```
+///
+/// Display: Hash
+/// Category: foundations
+/// Returns: string
+#[func]
+pub fn hash(
+    /// The value to hash.
+    value: Value,
+) -> Value {
+    Value::Str(format_str!("{:032x}", typst::util::hash128(&value)))
+}
+
+/// Compute the SHA-256 digest of bytes.
+///
+/// Returns the digest as a string of 64 lowercase hexadecimal digits. Unlike
+/// [`hash`]($func/hash), the digest is the same on all platforms and in all
+/// versions.
+///
+/// ## Example { #example }
+/// ```example
+/// #sha256(bytes("Typst"))
+/// ```
+///
+/// Display: SHA-256
+/// Category: foundations
+/// Returns: string
+#[func]
+pub fn sha256(
+    /// The bytes to compute the digest of. To hash a string, first convert it
+    /// with the [`bytes`]($func/bytes) function.
+    data: Bytes,
+) -> Value {
+    let mut hex = EcoString::new();
+    for byte in Sha256::digest(data.as_slice()) {
+        write!(hex, "{byte:02x}").unwrap();
+    }
+    Value::Str(hex.into())
+}
+
 /// Fail with an error.
 ///
 /// ## Example { #example }
@@ -15,6 +15,8 @@ use crate::prelude::*;
 pub(super) fn define(global: &mut Scope) {
     global.define("type", type_);
     global.define("repr", repr);
+    global.define("hash", hash);
+    global.define("sha256", sha256);
     global.define("panic", panic);
     global.define("assert", assert);
     global.define("eval", eval);
@@ -23,9 +25,14 @@ pub(super) fn define(global: &mut Scope) {
     global.define("luma", luma);
     global.define("rgb", rgb);
     global.define("cmyk", cmyk);
+    global.define("hsl", hsl);
+    global.define("gradient", gradient);
     global.define("datetime", datetime);
+    global.define("duration", duration);
+    global.define("random", random);
     global.define("symbol", symbol);
     global.define("str", str);
+    global.define("bytes", bytes);
     global.define("label", label);
     global.define("regex", regex);
     global.define("range", range);
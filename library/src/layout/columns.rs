@@ -4,10 +4,10 @@ use crate::text::TextElem;
 /// Separate a region into multiple equally sized columns.
 ///
 /// The `column` function allows to separate the interior of any container into
-/// multiple columns. It will not equalize the height of the columns, instead,
-/// the columns will take up the height of their container or the remaining
-/// height on the page. The columns function can break across pages if
-/// necessary.
+/// multiple columns. By default, it will not equalize the height of the
+/// columns, instead, the columns will take up the height of their container or
+/// the remaining height on the page. The columns function can break across
+/// pages if necessary.
 ///
 /// ## Example { #example }
 /// ```example
@@ -44,6 +44,20 @@ pub struct ColumnsElem {
     #[default(Ratio::new(0.04).into())]
     pub gutter: Rel<Length>,
 
+    /// Whether to balance the columns of the last region such that they end
+    /// at roughly equal heights instead of filling them one after another.
+    ///
+    /// ```example
+    /// #columns(2, balance: true)[
+    ///   #lorem(25)
+    /// ]
+    ///
+    /// This text follows below the
+    /// balanced columns.
+    /// ```
+    #[default(false)]
+    pub balance: bool,
+
     /// The content that should be layouted into the columns.
     #[required]
     pub body: Content,
@@ -87,7 +101,12 @@ impl Layout for ColumnsElem {
         };
 
         // Layout the children.
-        let mut frames = body.layout(vt, styles, pod)?.into_iter();
+        let mut frames = if self.balance(styles) && columns > 1 {
+            balance(&body, vt, styles, pod, columns)?
+        } else {
+            body.layout(vt, styles, pod)?
+        }
+        .into_iter();
         let mut finished = vec![];
 
         let dir = TextElem::dir_in(styles);
@@ -127,6 +146,73 @@ impl Layout for ColumnsElem {
     }
 }
 
+/// Layout the body into the column regions such that the columns of the last
+/// region end at roughly equal heights.
+///
+/// This performs a binary search for the smallest column height with which the
+/// body still fits into the same number of regions. Since the height is
+/// bounded by the tallest column of the unbalanced layout, this terminates
+/// even if some unbreakable content is taller than a whole column.
+fn balance(
+    body: &Content,
+    vt: &mut Vt,
+    styles: StyleChain,
+    pod: Regions,
+    columns: usize,
+) -> SourceResult<Fragment> {
+    let unbalanced = body.measure(vt, styles, pod)?;
+    let count = (unbalanced.len() + columns - 1) / columns * columns;
+    let mut heights: Vec<_> = pod.iter().take(count).map(|size| size.y).collect();
+    if count == 0 || heights.len() < count {
+        return body.layout(vt, styles, pod);
+    }
+
+    // Only the columns of the last region are balanced.
+    let start = count - columns;
+    let mut lo = Abs::zero();
+    let mut hi = unbalanced
+        .iter()
+        .skip(start)
+        .map(Frame::height)
+        .fold(Abs::zero(), Abs::max);
+
+    let regions = shorten(&pod, &mut heights, start, hi);
+    if body.measure(vt, styles, regions)?.len() > count {
+        return body.layout(vt, styles, pod);
+    }
+
+    while hi - lo > Abs::pt(0.5) {
+        let mid = (lo + hi) / 2.0;
+        let regions = shorten(&pod, &mut heights, start, mid);
+        if body.measure(vt, styles, regions)?.len() <= count {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    body.layout(vt, styles, shorten(&pod, &mut heights, start, hi))
+}
+
+/// Create regions from the given column pod in which all columns from `start`
+/// onwards have the given height.
+fn shorten<'a>(
+    pod: &Regions,
+    heights: &'a mut [Abs],
+    start: usize,
+    height: Abs,
+) -> Regions<'a> {
+    heights[start..].fill(height);
+    Regions {
+        size: Size::new(pod.size.x, heights[0]),
+        full: pod.full,
+        backlog: &heights[1..],
+        last: Some(height),
+        expand: pod.expand,
+        root: pod.root,
+    }
+}
+
 /// A forced column break.
 ///
 /// The function will behave like a [page break]($func/pagebreak) when used in a
@@ -318,6 +318,30 @@ pub struct BlockElem {
     #[default(false)]
     pub clip: bool,
 
+    /// The distance between the lines of a baseline grid to which the lines
+    /// of paragraphs are snapped.
+    ///
+    /// When set, each line of a paragraph is moved down until its baseline
+    /// lies on a multiple of the grid distance, measured from the top of the
+    /// block's content. This gives text a consistent vertical rhythm, even
+    /// across headings and differently sized text. With a set rule, this
+    /// applies to the page's content. Lines of a paragraph that are kept
+    /// together to prevent [widows and orphans]($func/par.orphans) are
+    /// snapped as a whole.
+    ///
+    /// Other blocks like figures and block equations are never snapped
+    /// themselves. Instead, the first line after them is moved to the next
+    /// grid line.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #set block(baseline-grid: 12pt)
+    /// #set par(leading: 4pt)
+    /// = Heading
+    /// #lorem(12)
+    /// ```
+    pub baseline_grid: Option<Length>,
+
     /// The contents of the block.
     #[positional]
     pub body: Option<Content>,
@@ -325,7 +349,16 @@ pub struct BlockElem {
     /// Whether this block must stick to the following one.
     ///
     /// Use this to prevent page breaks between e.g. a heading and its body.
-    #[internal]
+    /// Headings are sticky by default. If the block doesn't fit into the next
+    /// page together with the start of its successor, it is left where it is
+    /// instead.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #v(40pt)
+    /// #block(sticky: true)[*Keep me*]
+    /// #block(breakable: false, lorem(10))
+    /// ```
     #[default(false)]
     pub sticky: bool,
 }
@@ -340,6 +373,10 @@ impl Layout for BlockElem {
     ) -> SourceResult<Fragment> {
         // Apply inset.
         let mut body = self.body(styles).unwrap_or_default();
+        if let Some(grid) = self.baseline_grid(styles) {
+            body = body.styled(Self::set_baseline_grid(Some(grid)));
+        }
+
         let inset = self.inset(styles);
         if inset.iter().any(|v| !v.is_zero()) {
             body = body.clone().padded(inset.map(|side| side.map(Length::from)));
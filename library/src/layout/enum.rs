@@ -262,7 +262,7 @@ impl Layout for EnumElem {
             &cells,
             regions,
             styles,
-        );
+        )?;
 
         Ok(layouter.layout()?.fragment)
     }
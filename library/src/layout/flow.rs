@@ -100,6 +100,8 @@ struct FlowLayouter<'a> {
     has_footnotes: bool,
     /// Footnote configuration.
     footnote_config: FootnoteConfig,
+    /// The distance between the lines of the baseline grid, if any.
+    baseline_grid: Option<Abs>,
     /// Finished frames for previous regions.
     finished: Vec<Frame>,
 }
@@ -122,6 +124,9 @@ enum FlowItem {
     Frame(Frame, Axes<Align>, bool),
     /// An absolutely placed frame.
     Placed(Frame),
+    /// A floating frame, whether it floats to the top or bottom, and the
+    /// clearance between it and the other content.
+    Floating(Frame, Align, Abs),
     /// A footnote frame (can also be the separator).
     Footnote(Frame),
 }
@@ -149,6 +154,9 @@ impl<'a> FlowLayouter<'a> {
                 clearance: FootnoteEntry::clearance_in(styles),
                 gap: FootnoteEntry::gap_in(styles),
             },
+            baseline_grid: BlockElem::baseline_grid_in(styles)
+                .map(|grid| grid.resolve(styles))
+                .filter(|grid| *grid > Abs::zero()),
             finished: vec![],
         }
     }
@@ -182,36 +190,26 @@ impl<'a> FlowLayouter<'a> {
         styles: StyleChain,
     ) -> SourceResult<()> {
         let aligns = AlignElem::alignment_in(styles).resolve(styles);
-        let leading = ParElem::leading_in(styles);
         let consecutive = self.last_was_par;
-        let lines = par
-            .layout(vt, styles, consecutive, self.regions.base(), self.regions.expand.x)?
-            .into_frames();
-
-        let mut sticky = self.items.len();
-        for (i, item) in self.items.iter().enumerate().rev() {
-            match *item {
-                FlowItem::Absolute(_, _) => {}
-                FlowItem::Frame(.., true) => sticky = i,
-                _ => break,
-            }
-        }
+        let (lines, leadings) = par.layout(
+            vt,
+            styles,
+            consecutive,
+            self.regions.base(),
+            self.regions.expand.x,
+        )?;
+        let lines = lines.into_frames();
 
         if let Some(first) = lines.first() {
-            if !self.regions.size.y.fits(first.height()) && !self.regions.in_last() {
-                let carry: Vec<_> = self.items.drain(sticky..).collect();
-                self.finish_region()?;
-                for item in carry {
-                    self.layout_item(vt, item)?;
-                }
-            }
+            self.keep_with_next(vt, first.height())?;
         }
 
         for (i, frame) in lines.into_iter().enumerate() {
             if i > 0 {
-                self.layout_item(vt, FlowItem::Absolute(leading, true))?;
+                self.layout_item(vt, FlowItem::Absolute(leadings[i - 1], true))?;
             }
 
+            self.snap_to_grid(&frame)?;
             self.layout_item(vt, FlowItem::Frame(frame, aligns, false))?;
         }
 
@@ -231,6 +229,7 @@ impl<'a> FlowLayouter<'a> {
         let sticky = BlockElem::sticky_in(styles);
         let pod = Regions::one(self.regions.base(), Axes::splat(false));
         let frame = content.layout(vt, styles, pod)?.into_frame();
+        self.keep_with_next(vt, frame.height())?;
         self.layout_item(vt, FlowItem::Frame(frame, aligns, sticky))?;
         self.last_was_par = false;
         Ok(())
@@ -251,6 +250,10 @@ impl<'a> FlowLayouter<'a> {
         // Placed elements that are out of flow produce placed items which
         // aren't aligned later.
         if let Some(placed) = block.to::<PlaceElem>() {
+            if placed.float(styles) {
+                return self.layout_float(vt, block, placed, styles);
+            }
+
             if placed.out_of_flow(styles) {
                 let frame = block.layout(vt, styles, self.regions)?.into_frame();
                 self.layout_item(vt, FlowItem::Placed(frame))?;
@@ -271,13 +274,18 @@ impl<'a> FlowLayouter<'a> {
         let is_columns = block.is::<ColumnsElem>();
 
         // Layout the block itself.
-        let sticky = BlockElem::sticky_in(styles);
+        let sticky = match block.to::<BlockElem>() {
+            Some(elem) => elem.sticky(styles),
+            None => BlockElem::sticky_in(styles),
+        };
         let fragment = block.layout(vt, styles, self.regions)?;
         self.regions.root = self.root && is_columns;
 
         for (i, frame) in fragment.into_iter().enumerate() {
             if i > 0 {
                 self.finish_region()?;
+            } else {
+                self.keep_with_next(vt, frame.height())?;
             }
 
             self.layout_item(vt, FlowItem::Frame(frame, aligns, sticky))?;
@@ -289,6 +297,89 @@ impl<'a> FlowLayouter<'a> {
         Ok(())
     }
 
+    /// Layout a floating placed element at the top or bottom of the region.
+    fn layout_float(
+        &mut self,
+        vt: &mut Vt,
+        block: &Content,
+        placed: &PlaceElem,
+        styles: StyleChain,
+    ) -> SourceResult<()> {
+        let align = match placed.alignment(styles).y.map(|align| align.resolve(styles)) {
+            Some(Align::Top) => Align::Top,
+            Some(Align::Bottom) => Align::Bottom,
+            _ => bail!(block.span(), "floating placement must be `top` or `bottom`"),
+        };
+
+        let clearance = placed.clearance(styles);
+        let frame = block.layout(vt, styles, self.regions)?.into_frame();
+        self.layout_item(vt, FlowItem::Floating(frame, align, clearance))
+    }
+
+    /// Break the region before content of the given height if it doesn't fit,
+    /// moving the sticky items that precede it on to the next region.
+    ///
+    /// The sticky items stay where they are if they are the only content of
+    /// the region or if they wouldn't fit into the next region together with
+    /// the content, since moving them would then just leave an empty region
+    /// behind.
+    fn keep_with_next(&mut self, vt: &mut Vt, needed: Abs) -> SourceResult<()> {
+        if self.regions.size.y.fits(needed) || self.regions.in_last() {
+            return Ok(());
+        }
+
+        let mut sticky = self.items.len();
+        for (i, item) in self.items.iter().enumerate().rev() {
+            match *item {
+                FlowItem::Absolute(_, _) => {}
+                FlowItem::Frame(.., true) => sticky = i,
+                _ => break,
+            }
+        }
+
+        let height = items_height(&self.items[sticky..]);
+
+        let stranded = self.items[..sticky]
+            .iter()
+            .any(|item| matches!(item, FlowItem::Frame(..)));
+        let next = self
+            .regions
+            .backlog
+            .first()
+            .copied()
+            .or(self.regions.last)
+            .unwrap_or(self.regions.full);
+        if !stranded || !next.fits(height + needed) {
+            sticky = self.items.len();
+        }
+
+        let carry: Vec<_> = self.items.drain(sticky..).collect();
+        self.finish_region()?;
+        for item in carry {
+            self.layout_item(vt, item)?;
+        }
+
+        Ok(())
+    }
+
+    /// Move a line down so that its baseline lies on the baseline grid,
+    /// breaking the region first if the line wouldn't fit anymore.
+    fn snap_to_grid(&mut self, line: &Frame) -> SourceResult<()> {
+        let Some(grid) = self.baseline_grid else { return Ok(()) };
+        let mut delta = grid_delta(items_height(&self.items) + line.baseline(), grid);
+        if !self.regions.size.y.fits(delta + line.height()) && !self.regions.in_last() {
+            self.finish_region()?;
+            delta = grid_delta(line.baseline(), grid);
+        }
+
+        if delta > Abs::zero() {
+            self.regions.size.y -= delta;
+            self.items.push(FlowItem::Absolute(delta, false));
+        }
+
+        Ok(())
+    }
+
     /// Layout a finished frame.
     #[tracing::instrument(name = "FlowLayouter::layout_item", skip_all)]
     fn layout_item(&mut self, vt: &mut Vt, item: FlowItem) -> SourceResult<()> {
@@ -313,6 +404,14 @@ impl<'a> FlowLayouter<'a> {
                     return self.handle_footnotes(vt, item, size.y);
                 }
             }
+            FlowItem::Floating(ref frame, _, clearance) => {
+                let height = frame.height() + clearance;
+                if !self.regions.size.y.fits(height) && !self.regions.in_last() {
+                    self.finish_region()?;
+                }
+
+                self.regions.size.y -= height;
+            }
             FlowItem::Placed(_) => {}
             FlowItem::Footnote(_) => {}
         }
@@ -337,6 +436,8 @@ impl<'a> FlowLayouter<'a> {
         let mut used = Size::zero();
         let mut footnote_height = Abs::zero();
         let mut first_footnote = true;
+        let mut float_top_height = Abs::zero();
+        let mut float_bottom_height = Abs::zero();
         for item in &self.items {
             match item {
                 FlowItem::Absolute(v, _) => used.y += *v,
@@ -346,6 +447,15 @@ impl<'a> FlowLayouter<'a> {
                     used.y += size.y;
                     used.x.set_max(size.x);
                 }
+                FlowItem::Floating(frame, align, clearance) => {
+                    let height = frame.height() + *clearance;
+                    match align {
+                        Align::Bottom => float_bottom_height += height,
+                        _ => float_top_height += height,
+                    }
+                    used.y += height;
+                    used.x.set_max(frame.width());
+                }
                 FlowItem::Placed(_) => {}
                 FlowItem::Footnote(frame) => {
                     let size = frame.size();
@@ -369,9 +479,11 @@ impl<'a> FlowLayouter<'a> {
         }
 
         let mut output = Frame::new(size);
-        let mut offset = Abs::zero();
+        let mut offset = float_top_height;
         let mut ruler = Align::Top;
         let mut footnote_offset = size.y - footnote_height;
+        let mut float_top_offset = Abs::zero();
+        let mut float_bottom_offset = footnote_offset - float_bottom_height;
 
         // Place all frames.
         for item in self.items.drain(..) {
@@ -396,6 +508,22 @@ impl<'a> FlowLayouter<'a> {
                     footnote_offset += frame.height() + self.footnote_config.gap;
                     output.push_frame(pos, frame);
                 }
+                FlowItem::Floating(frame, align, clearance) => {
+                    let y = match align {
+                        Align::Bottom => {
+                            float_bottom_offset += clearance;
+                            let y = float_bottom_offset;
+                            float_bottom_offset += frame.height();
+                            y
+                        }
+                        _ => {
+                            let y = float_top_offset;
+                            float_top_offset += frame.height() + clearance;
+                            y
+                        }
+                    };
+                    output.push_frame(Point::with_y(y), frame);
+                }
                 FlowItem::Placed(frame) => {
                     output.push_frame(Point::zero(), frame);
                 }
@@ -540,3 +668,29 @@ fn find_footnotes(notes: &mut Vec<FootnoteElem>, frame: &Frame) {
         }
     }
 }
+
+/// The combined height of spacing and frames.
+fn items_height(items: &[FlowItem]) -> Abs {
+    items
+        .iter()
+        .map(|item| match item {
+            FlowItem::Absolute(v, _) => *v,
+            FlowItem::Frame(frame, ..) => frame.height(),
+            FlowItem::Floating(frame, Align::Top, clearance) => {
+                frame.height() + *clearance
+            }
+            _ => Abs::zero(),
+        })
+        .sum()
+}
+
+/// How far a baseline at the given offset must be moved down to lie on the
+/// baseline grid.
+fn grid_delta(baseline: Abs, grid: Abs) -> Abs {
+    let lines = baseline / grid;
+    if (lines - lines.round()).abs() < 1e-6 {
+        Abs::zero()
+    } else {
+        grid * lines.ceil() - baseline
+    }
+}
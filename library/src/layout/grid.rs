@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use crate::prelude::*;
 use crate::text::TextElem;
 
@@ -29,11 +31,24 @@ use super::Sizing;
 ///   with a fraction of `{1fr}`, they will each take up half of the remaining
 ///   space.
 ///
+/// While sizing an `{auto}` track, its contents are measured against all space
+/// that is still available to it. Fractional tracks or spacing within such
+/// contents therefore don't depend on the track's final size. Instead, they
+/// claim all of the available space and the `{auto}` track grows to fit them.
+///
 /// To specify a single track, the array can be omitted in favor of a single
 /// value. To specify multiple `{auto}` tracks, enter the number of tracks
 /// instead of an array. For example, `columns:` `{3}` is equivalent to
 /// `columns:` `{(auto, auto, auto)}`.
 ///
+/// A cell can span multiple columns or rows by wrapping it in
+/// [`grid.cell`]($func/grid.cell). The following cells are then placed into
+/// the next slots that aren't covered by it.
+///
+/// The first rows can be marked as a header with
+/// [`grid.header`]($func/grid.header). When the grid breaks across pages, the
+/// header is repeated at the top of each page.
+///
 /// ## Example { #example }
 /// ```example
 /// #set text(10pt, style: "italic")
@@ -62,6 +77,11 @@ use super::Sizing;
 /// Display: Grid
 /// Category: layout
 #[element(Layout)]
+#[scope(
+    scope.define("cell", GridCell::func());
+    scope.define("header", GridHeader::func());
+    scope
+)]
 pub struct GridElem {
     /// Defines the column sizes.
     ///
@@ -101,6 +121,177 @@ pub struct GridElem {
     pub children: Vec<Content>,
 }
 
+/// A cell in a grid or table that spans multiple columns or rows.
+///
+/// The cell is placed into the next free slot, just like other cells. Cells
+/// of a table keep the table's inset, alignment and fill.
+///
+/// ## Example { #example }
+/// ```example
+/// #table(
+///   columns: 3,
+///   table.cell(colspan: 2)[*Merged header*],
+///   table.cell(rowspan: 2)[Tall],
+///   [A], [B],
+///   [C], [D],
+/// )
+/// ```
+///
+/// Display: Grid Cell
+/// Category: layout
+#[element]
+pub struct GridCell {
+    /// The cell's body.
+    #[required]
+    pub body: Content,
+
+    /// The number of columns the cell spans.
+    #[default(NonZeroUsize::ONE)]
+    pub colspan: NonZeroUsize,
+
+    /// The number of rows the cell spans.
+    #[default(NonZeroUsize::ONE)]
+    pub rowspan: NonZeroUsize,
+}
+
+/// The header of a grid or table.
+///
+/// The header must be the grid's first child. Its cells make up the grid's
+/// first rows and the following cells start in the row below them.
+///
+/// ## Example { #example }
+/// ```example
+/// #set page(height: 100pt)
+/// #table(
+///   columns: 2,
+///   table.header[*Name*][*Score*],
+///   ..range(8).map(n => ([Player #n], [#(n * 10)])).flatten(),
+/// )
+/// ```
+///
+/// Display: Grid Header
+/// Category: layout
+#[element]
+pub struct GridHeader {
+    /// Whether to repeat the header at the top of each region the grid breaks
+    /// into.
+    #[default(true)]
+    pub repeat: bool,
+
+    /// The header's cells.
+    #[variadic]
+    pub children: Vec<Content>,
+}
+
+/// Inline the cells of a grid's header into the grid's other cells.
+///
+/// Returns all cells and the header, if there is one.
+pub fn inline_header(
+    children: &[Content],
+) -> SourceResult<(Vec<Content>, Option<GridHeader>)> {
+    let mut cells = vec![];
+    let mut header = None;
+
+    for (i, child) in children.iter().enumerate() {
+        match child.to::<GridHeader>() {
+            Some(_) if i > 0 => {
+                bail!(child.span(), "header must be the first cell of the grid")
+            }
+            Some(elem) => {
+                cells.extend(elem.children());
+                header = Some(elem.clone());
+            }
+            None => cells.push(child.clone()),
+        }
+    }
+
+    Ok((cells, header))
+}
+
+/// The position of a cell in a grid, in terms of content tracks.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CellPlacement {
+    /// The column of the cell's top-left slot.
+    pub x: usize,
+    /// The row of the cell's top-left slot.
+    pub y: usize,
+    /// The number of columns and rows the cell spans.
+    pub span: Axes<usize>,
+}
+
+/// Determine the position of each cell in a grid with the given number of
+/// columns.
+///
+/// The cells are placed in row-major order, each into the first slot that
+/// isn't already covered by a previous cell spanning multiple tracks. The
+/// cells following the first `header` cells start in a new row.
+pub fn place_cells(
+    cells: &[Content],
+    columns: usize,
+    header: usize,
+    styles: StyleChain,
+) -> SourceResult<Vec<CellPlacement>> {
+    let mut occupied: Vec<bool> = vec![];
+    let mut placements = Vec::with_capacity(cells.len());
+    let mut cursor = 0;
+
+    for (i, child) in cells.iter().enumerate() {
+        if i > 0 && i == header {
+            cursor = cursor.max(header_rows(&placements) * columns);
+        }
+
+        let span = match child.to::<GridCell>() {
+            Some(cell) => {
+                Axes::new(cell.colspan(styles).get(), cell.rowspan(styles).get())
+            }
+            None => Axes::splat(1),
+        };
+
+        if span.x > columns {
+            bail!(child.span(), "cell spans more columns than the grid has");
+        }
+
+        while occupied.get(cursor).copied().unwrap_or(false) {
+            cursor += 1;
+        }
+
+        let x = cursor % columns;
+        let y = cursor / columns;
+        if x + span.x > columns {
+            bail!(child.span(), "cell spans past the last column");
+        }
+
+        let end = (y + span.y) * columns;
+        if occupied.len() < end {
+            occupied.resize(end, false);
+        }
+
+        for row in y..y + span.y {
+            for col in x..x + span.x {
+                let slot = &mut occupied[row * columns + col];
+                if *slot {
+                    bail!(child.span(), "cell overlaps with another cell");
+                }
+                *slot = true;
+            }
+        }
+
+        placements.push(CellPlacement { x, y, span });
+        cursor += span.x;
+    }
+
+    Ok(placements)
+}
+
+/// The number of rows covered by the placed cells.
+pub fn header_rows(placements: &[CellPlacement]) -> usize {
+    placements
+        .iter()
+        .map(|placement| placement.y + placement.span.y)
+        .max()
+        .unwrap_or(0)
+}
+
 impl Layout for GridElem {
     #[tracing::instrument(name = "GridElem::layout", skip_all)]
     fn layout(
@@ -118,7 +309,7 @@ impl Layout for GridElem {
             &cells,
             regions,
             styles,
-        );
+        )?;
 
         // Measure the columns and layout the grid row-by-row.
         Ok(layouter.layout()?.fragment)
@@ -144,12 +335,17 @@ cast_to_value! {
 pub struct GridLayouter<'a, 'v> {
     /// The core context.
     vt: &'a mut Vt<'v>,
-    /// The grid cells.
-    cells: &'a [Content],
-    /// Whether this is an RTL grid.
-    is_rtl: bool,
-    /// Whether this grid has gutters.
-    has_gutter: bool,
+    /// The grid cells, positioned in terms of all tracks including gutter
+    /// tracks.
+    cells: Vec<Cell>,
+    /// For each slot of the grid in row-major order, the index of the cell
+    /// that covers it.
+    slots: Vec<Option<usize>>,
+    /// The number of header tracks to repeat in each region.
+    repeat: Option<usize>,
+    /// The header rows to repeat at the top of each region, once the header
+    /// is complete.
+    header: Vec<(Frame, usize)>,
     /// The column tracks including gutter tracks.
     cols: Vec<Sizing>,
     /// The row tracks including gutter tracks.
@@ -181,6 +377,37 @@ pub struct GridLayout {
     pub cols: Vec<Abs>,
     /// The heights of the resulting rows segments, by region.
     pub rows: Vec<Vec<RowPiece>>,
+    /// The tracks covered by cells that span multiple tracks.
+    pub spans: Vec<CellSpan>,
+}
+
+/// The tracks covered by a cell that spans multiple tracks.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CellSpan {
+    /// The covered columns, including gutter columns.
+    pub cols: Range<usize>,
+    /// The covered rows, including gutter rows.
+    pub rows: Range<usize>,
+}
+
+impl CellSpan {
+    /// Whether the span covers the slot in column `x` and row `y`.
+    pub fn covers(&self, x: usize, y: usize) -> bool {
+        self.cols.contains(&x) && self.rows.contains(&y)
+    }
+}
+
+/// A grid cell, positioned in terms of all tracks including gutter tracks.
+#[derive(Debug, Clone)]
+struct Cell {
+    /// The cell's content.
+    body: Content,
+    /// The column of the cell's leftmost track.
+    x: usize,
+    /// The row of the cell's topmost track.
+    y: usize,
+    /// The number of tracks the cell spans.
+    span: Axes<usize>,
 }
 
 /// Details about a resulting row piece.
@@ -204,15 +431,16 @@ enum Row {
 impl<'a, 'v> GridLayouter<'a, 'v> {
     /// Create a new grid layouter.
     ///
-    /// This prepares grid layout by unifying content and gutter tracks.
+    /// This prepares grid layout by placing the cells and unifying content and
+    /// gutter tracks.
     pub fn new(
         vt: &'a mut Vt<'v>,
         tracks: Axes<&[Sizing]>,
         gutter: Axes<&[Sizing]>,
-        cells: &'a [Content],
+        cells: &[Content],
         regions: Regions<'a>,
         styles: StyleChain<'a>,
-    ) -> Self {
+    ) -> SourceResult<Self> {
         let mut cols = vec![];
         let mut rows = vec![];
 
@@ -221,12 +449,10 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
 
         // Number of content rows: At least as many as given, but also at least
         // as many as needed to place each item.
-        let r = {
-            let len = cells.len();
-            let given = tracks.y.len();
-            let needed = len / c + (len % c).clamp(0, 1);
-            given.max(needed)
-        };
+        let (cells, header) = inline_header(cells)?;
+        let count = header.as_ref().map_or(0, |header| header.children().len());
+        let placements = place_cells(&cells, c, count, styles)?;
+        let r = tracks.y.len().max(header_rows(&placements));
 
         let has_gutter = gutter.any(|tracks| !tracks.is_empty());
         let auto = Sizing::Auto;
@@ -263,6 +489,44 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             cols.reverse();
         }
 
+        // With gutter, content tracks have even indices and spans include the
+        // gutter in between.
+        let factor = if has_gutter { 2 } else { 1 };
+
+        // The tracks to repeat in each region, including the gutter below.
+        let repeat = header
+            .filter(|header| header.repeat(styles))
+            .map(|_| (factor * header_rows(&placements[..count])).min(rows.len()));
+
+        // Position the cells in terms of all tracks.
+        let mut slots = vec![None; cols.len() * rows.len()];
+        let cells: Vec<_> = cells
+            .iter()
+            .zip(placements)
+            .enumerate()
+            .map(|(i, (child, placement))| {
+                let span = placement.span.map(|n| factor * n - (factor - 1));
+                let mut x = factor * placement.x;
+                if is_rtl {
+                    x = cols.len() - x - span.x;
+                }
+                let y = factor * placement.y;
+
+                for row in y..y + span.y {
+                    for col in x..x + span.x {
+                        slots[row * cols.len() + col] = Some(i);
+                    }
+                }
+
+                let body = match child.to::<GridCell>() {
+                    Some(cell) => cell.body(),
+                    None => child.clone(),
+                };
+
+                Cell { body, x, y, span }
+            })
+            .collect();
+
         let rcols = vec![Abs::zero(); cols.len()];
         let lrows = vec![];
 
@@ -271,11 +535,12 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         let mut regions = regions;
         regions.expand = Axes::new(true, false);
 
-        Self {
+        Ok(Self {
             vt,
             cells,
-            is_rtl,
-            has_gutter,
+            slots,
+            repeat,
+            header: vec![],
             cols,
             rows,
             regions,
@@ -286,7 +551,7 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             lrows,
             initial: regions.size,
             finished: vec![],
-        }
+        })
     }
 
     /// Determines the columns sizes and then layouts the grid row-by-row.
@@ -296,7 +561,7 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         for y in 0..self.rows.len() {
             // Skip to next region if current one is full, but only for content
             // rows, not for gutter rows.
-            if y % 2 == 0 && self.regions.is_full() {
+            if y % 2 == 0 && self.is_full() {
                 self.finish_region()?;
             }
 
@@ -305,14 +570,39 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
                 Sizing::Rel(v) => self.layout_relative_row(v, y)?,
                 Sizing::Fr(v) => self.lrows.push(Row::Fr(v, y)),
             }
+
+            // Remember the finished header for repetition, unless it is the
+            // whole grid.
+            if self.repeat == Some(y + 1) && y + 1 < self.rows.len() {
+                self.header = self
+                    .lrows
+                    .iter()
+                    .filter_map(|row| match row {
+                        Row::Frame(frame, y) => Some((frame.clone(), *y)),
+                        Row::Fr(..) => None,
+                    })
+                    .collect();
+            }
         }
 
         self.finish_region()?;
+        self.layout_spanning_cells()?;
+
+        let spans = self
+            .cells
+            .iter()
+            .filter(|cell| cell.span.x > 1 || cell.span.y > 1)
+            .map(|cell| CellSpan {
+                cols: cell.x..cell.x + cell.span.x,
+                rows: cell.y..cell.y + cell.span.y,
+            })
+            .collect();
 
         Ok(GridLayout {
             fragment: Fragment::frames(self.finished),
             cols: self.rcols,
             rows: self.rrows,
+            spans,
         })
     }
 
@@ -376,21 +666,25 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
 
             let mut resolved = Abs::zero();
             for y in 0..self.rows.len() {
-                if let Some(cell) = self.cell(x, y) {
-                    // For relative rows, we can already resolve the correct
-                    // base and for auto and fr we could only guess anyway.
-                    let height = match self.rows[y] {
-                        Sizing::Rel(v) => {
-                            v.resolve(self.styles).relative_to(self.regions.base().y)
-                        }
-                        _ => self.regions.base().y,
-                    };
-
-                    let size = Size::new(available, height);
-                    let pod = Regions::one(size, Axes::splat(false));
-                    let frame = cell.measure(self.vt, self.styles, pod)?.into_frame();
-                    resolved.set_max(frame.width());
+                // Cells spanning multiple columns are handled below.
+                let Some(cell) = self.cell(x, y) else { continue };
+                if cell.span.x > 1 {
+                    continue;
                 }
+
+                // For relative rows, we can already resolve the correct
+                // base and for auto and fr we could only guess anyway.
+                let height = match self.rows[y] {
+                    Sizing::Rel(v) => {
+                        v.resolve(self.styles).relative_to(self.regions.base().y)
+                    }
+                    _ => self.regions.base().y,
+                };
+
+                let size = Size::new(available, height);
+                let pod = Regions::one(size, Axes::splat(false));
+                let frame = cell.body.measure(self.vt, self.styles, pod)?.into_frame();
+                resolved.set_max(frame.width());
             }
 
             self.rcols[x] = resolved;
@@ -398,6 +692,33 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             count += 1;
         }
 
+        // If a cell spanning multiple columns is wider than its columns, its
+        // last auto column grows by the difference. Fractional columns will
+        // take up the remaining space anyway.
+        let spanning: Vec<_> =
+            self.cells.iter().filter(|cell| cell.span.x > 1).cloned().collect();
+        for cell in spanning {
+            let cols = cell.x..cell.x + cell.span.x;
+            if self.cols[cols.clone()].iter().any(|col| col.is_fractional()) {
+                continue;
+            }
+
+            let Some(last) = cols.clone().rev().find(|&x| self.cols[x] == Sizing::Auto)
+            else {
+                continue;
+            };
+
+            let size = Size::new(available, self.regions.base().y);
+            let pod = Regions::one(size, Axes::splat(false));
+            let frame = cell.body.measure(self.vt, self.styles, pod)?.into_frame();
+            let width: Abs = self.rcols[cols].iter().sum();
+            if frame.width() > width {
+                let excess = frame.width() - width;
+                self.rcols[last] += excess;
+                auto += excess;
+            }
+        }
+
         Ok((auto, count))
     }
 
@@ -472,16 +793,18 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             return Ok(());
         }
 
-        // Expand all but the last region.
+        // Expand all but the last region. The following regions start with
+        // the repeated header.
         // Skip the first region if the space is eaten up by an fr row.
         let len = resolved.len();
-        for (region, target) in self
-            .regions
-            .iter()
+        let header = self.header_height();
+        let heights = std::iter::once(self.regions.size.y)
+            .chain(self.regions.iter().skip(1).map(|region| region.y - header));
+        for (height, target) in heights
             .zip(&mut resolved[..len - 1])
             .skip(self.lrows.iter().any(|row| matches!(row, Row::Fr(..))) as usize)
         {
-            target.set_max(region.y);
+            target.set_max(height);
         }
 
         // Layout into multiple regions.
@@ -506,12 +829,24 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
     ) -> SourceResult<Option<Vec<Abs>>> {
         let mut resolved: Vec<Abs> = vec![];
 
-        for (x, &rcol) in self.rcols.iter().enumerate() {
+        // The repeated header takes up space in all following regions.
+        let header = self.header_height();
+        let backlog: Vec<_> =
+            self.regions.backlog.iter().map(|&height| height - header).collect();
+
+        for x in 0..self.rcols.len() {
             if let Some(cell) = self.cell(x, y) {
+                // Cells spanning multiple rows are handled below.
+                if cell.span.y > 1 {
+                    continue;
+                }
+
                 let mut pod = self.regions;
-                pod.size.x = rcol;
+                pod.size.x = self.cell_width(&cell);
+                pod.backlog = &backlog;
+                pod.last = self.regions.last.map(|height| height - header);
 
-                let frames = cell.measure(self.vt, self.styles, pod)?.into_frames();
+                let frames = cell.body.measure(self.vt, self.styles, pod)?.into_frames();
 
                 // Skip the first region if one cell in it is empty. Then,
                 // remeasure.
@@ -535,6 +870,62 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
             }
         }
 
+        // A cell spanning multiple rows that ends in this row makes it tall
+        // enough for the rest of the cell to fit into the rows it spans, after
+        // filling its pieces in earlier regions. If the rest doesn't fit into
+        // this region, the row breaks.
+        let ending: Vec<_> = self
+            .cells
+            .iter()
+            .filter(|cell| cell.span.y > 1 && cell.y + cell.span.y == y + 1)
+            .cloned()
+            .collect();
+        for cell in ending {
+            let above: Abs = self
+                .lrows
+                .iter()
+                .filter_map(|row| match row {
+                    Row::Frame(frame, y) if *y >= cell.y => Some(frame.height()),
+                    _ => None,
+                })
+                .sum();
+
+            let mut heights: Vec<Abs> = self
+                .rrows
+                .iter()
+                .filter(|rrows| rrows.iter().any(|row| row.y >= cell.y))
+                .map(|rrows| {
+                    rrows.iter().filter(|row| row.y >= cell.y).map(|row| row.height).sum()
+                })
+                .collect();
+            let skip = heights.len();
+            heights.push(above + self.regions.size.y);
+            heights.extend(&backlog);
+
+            let size = Size::new(self.cell_width(&cell), heights[0]);
+            let mut pod = Regions::one(size, Axes::new(true, false));
+            pod.backlog = &heights[1..];
+            pod.last = self.regions.last.map(|height| height - header);
+
+            // If the cell already fits into its earlier pieces, this row
+            // needs no extra space for it.
+            let frames = cell.body.measure(self.vt, self.styles, pod)?.into_frames();
+            let mut sizes = frames.iter().skip(skip).map(|frame| frame.height());
+            if let Some(size) = sizes.next() {
+                let needed = size - above;
+                match resolved.first_mut() {
+                    Some(first) => first.set_max(needed),
+                    None => resolved.push(needed.max(Abs::zero())),
+                }
+            }
+
+            for (target, size) in resolved.iter_mut().skip(1).zip(&mut sizes) {
+                target.set_max(size);
+            }
+
+            resolved.extend(sizes);
+        }
+
         Ok(Some(resolved))
     }
 
@@ -546,7 +937,7 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
 
         // Skip to fitting region.
         let height = frame.height();
-        while !self.regions.size.y.fits(height) && !self.regions.in_last() {
+        while !self.regions.size.y.fits(height) && !self.in_last() {
             self.finish_region()?;
 
             // Don't skip multiple regions for gutter and don't push a row.
@@ -565,18 +956,18 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         let mut output = Frame::new(Size::new(self.width, height));
         let mut pos = Point::zero();
 
-        for (x, &rcol) in self.rcols.iter().enumerate() {
-            if let Some(cell) = self.cell(x, y) {
-                let size = Size::new(rcol, height);
+        for x in 0..self.rcols.len() {
+            if let Some(cell) = self.cell(x, y).filter(|cell| cell.span.y == 1) {
+                let size = Size::new(self.cell_width(&cell), height);
                 let mut pod = Regions::one(size, Axes::splat(true));
                 if self.rows[y] == Sizing::Auto {
                     pod.full = self.regions.full;
                 }
-                let frame = cell.layout(self.vt, self.styles, pod)?.into_frame();
+                let frame = cell.body.layout(self.vt, self.styles, pod)?.into_frame();
                 output.push_frame(pos, frame);
             }
 
-            pos.x += rcol;
+            pos.x += self.rcols[x];
         }
 
         Ok(output)
@@ -598,18 +989,18 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
 
         // Layout the row.
         let mut pos = Point::zero();
-        for (x, &rcol) in self.rcols.iter().enumerate() {
-            if let Some(cell) = self.cell(x, y) {
-                pod.size.x = rcol;
+        for x in 0..self.rcols.len() {
+            if let Some(cell) = self.cell(x, y).filter(|cell| cell.span.y == 1) {
+                pod.size.x = self.cell_width(&cell);
 
                 // Push the layouted frames into the individual output frames.
-                let fragment = cell.layout(self.vt, self.styles, pod)?;
+                let fragment = cell.body.layout(self.vt, self.styles, pod)?;
                 for (output, frame) in outputs.iter_mut().zip(fragment) {
                     output.push_frame(pos, frame);
                 }
             }
 
-            pos.x += rcol;
+            pos.x += self.rcols[x];
         }
 
         Ok(Fragment::frames(outputs))
@@ -667,33 +1058,104 @@ impl<'a, 'v> GridLayouter<'a, 'v> {
         self.regions.next();
         self.initial = self.regions.size;
 
+        // Repeat the header at the top of the next region.
+        for (frame, y) in self.header.clone() {
+            self.push_row(frame, y);
+        }
+
         Ok(())
     }
 
-    /// Get the content of the cell in column `x` and row `y`.
+    /// Layout cells spanning multiple rows into the rows they cover.
     ///
-    /// Returns `None` if it's a gutter cell.
+    /// A cell whose rows break across regions is split into one piece per
+    /// region. Cells in the repeated header are laid out again in each region.
+    fn layout_spanning_cells(&mut self) -> SourceResult<()> {
+        let spanning: Vec<_> =
+            self.cells.iter().filter(|cell| cell.span.y > 1).cloned().collect();
+        for cell in spanning {
+            let rows = cell.y..cell.y + cell.span.y;
+            let in_header = !self.header.is_empty()
+                && self.repeat.map_or(false, |repeat| rows.end <= repeat);
+
+            // Find the piece of the cell in each region as its index,
+            // vertical offset, and height.
+            let mut pieces = vec![];
+            for (i, rrows) in self.rrows.iter().enumerate() {
+                let Some(start) = rrows.iter().position(|row| rows.contains(&row.y))
+                else {
+                    continue;
+                };
+
+                let dy = rrows[..start].iter().map(|row| row.height).sum();
+                let height = rrows[start..]
+                    .iter()
+                    .take_while(|row| rows.contains(&row.y))
+                    .map(|row| row.height)
+                    .sum();
+                pieces.push((i, dy, height));
+            }
+
+            let dx = self.rcols[..cell.x].iter().sum();
+            let width = self.cell_width(&cell);
+            let groups: Vec<_> = if in_header {
+                pieces.chunks(1).collect()
+            } else {
+                vec![pieces.as_slice()]
+            };
+
+            for group in groups {
+                let Some(&(_, _, first)) = group.first() else { continue };
+                let heights: Vec<_> = group.iter().map(|&(_, _, h)| h).collect();
+                let mut pod = Regions::one(Size::new(width, first), Axes::splat(true));
+                pod.backlog = &heights[1..];
+                let fragment = cell.body.layout(self.vt, self.styles, pod)?;
+                for (&(i, dy, _), frame) in group.iter().zip(fragment) {
+                    self.finished[i].push_frame(Point::new(dx, dy), frame);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The total height of the header rows repeated in each region.
+    fn header_height(&self) -> Abs {
+        self.header.iter().map(|(frame, _)| frame.height()).sum()
+    }
+
+    /// Whether the current region is full and a region break is called for.
+    fn is_full(&self) -> bool {
+        Abs::zero().fits(self.regions.size.y) && !self.in_last()
+    }
+
+    /// Whether the current region is the last usable region.
+    ///
+    /// Regions after the first one start with the repeated header, so the
+    /// last region is only reached once its space is reduced by the header.
+    fn in_last(&self) -> bool {
+        self.regions.backlog.is_empty()
+            && self.regions.last.map_or(true, |height| {
+                self.regions.size.y == height - self.header_height()
+            })
+    }
+
+    /// Get the cell whose top-left track is in column `x` and row `y`.
+    ///
+    /// Returns `None` if it's a gutter cell, an empty slot, or covered by a
+    /// cell starting in another track.
     #[track_caller]
-    fn cell(&self, mut x: usize, y: usize) -> Option<&'a Content> {
+    fn cell(&self, x: usize, y: usize) -> Option<Cell> {
         assert!(x < self.cols.len());
         assert!(y < self.rows.len());
 
-        // Columns are reorder, but the cell slice is not.
-        if self.is_rtl {
-            x = self.cols.len() - 1 - x;
-        }
+        let index = self.slots[y * self.cols.len() + x]?;
+        let cell = &self.cells[index];
+        (cell.x == x && cell.y == y).then(|| cell.clone())
+    }
 
-        if self.has_gutter {
-            // Even columns and rows are children, odd ones are gutter.
-            if x % 2 == 0 && y % 2 == 0 {
-                let c = 1 + self.cols.len() / 2;
-                self.cells.get((y / 2) * c + x / 2)
-            } else {
-                None
-            }
-        } else {
-            let c = self.cols.len();
-            self.cells.get(y * c + x)
-        }
+    /// The total width of the columns spanned by a cell.
+    fn cell_width(&self, cell: &Cell) -> Abs {
+        self.rcols[cell.x..cell.x + cell.span.x].iter().sum()
     }
 }
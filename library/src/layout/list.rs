@@ -157,7 +157,7 @@ impl Layout for ListElem {
             &cells,
             regions,
             styles,
-        );
+        )?;
 
         Ok(layouter.layout()?.fragment)
     }
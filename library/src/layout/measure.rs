@@ -37,6 +37,16 @@ use crate::prelude::*;
 /// The measure function returns a dictionary with the entries `width` and
 /// `height`, both of type [`length`]($type/length).
 ///
+/// Content that adapts to its container, like a paragraph that wraps, can be
+/// measured in a region of fixed size by passing a `width` or `height`.
+///
+/// ```example
+/// #style(styles => {
+///   let size = measure(lorem(10), styles, width: 50pt)
+///   [The paragraph is #size.height high.]
+/// })
+/// ```
+///
 /// Display: Measure
 /// Category: layout
 /// Returns: dictionary
@@ -46,9 +56,21 @@ pub fn measure(
     content: Content,
     /// The styles with which to layout the content.
     styles: Styles,
+    /// The width of the region in which the content is measured. If `{auto}`,
+    /// the width is unlimited.
+    #[named]
+    #[default]
+    width: Smart<Length>,
+    /// The height of the region in which the content is measured. If
+    /// `{auto}`, the height is unlimited.
+    #[named]
+    #[default]
+    height: Smart<Length>,
 ) -> Value {
-    let pod = Regions::one(Axes::splat(Abs::inf()), Axes::splat(false));
     let styles = StyleChain::new(&styles);
+    let size =
+        Axes::new(width, height).map(|v| v.map_or(Abs::inf(), |v| v.resolve(styles)));
+    let pod = Regions::one(size, Axes::splat(false));
     let frame = content.measure(&mut vm.vt, styles, pod)?.into_frame();
     let Size { x, y } = frame.size();
     dict! { "width" => x, "height" => y }.into()
@@ -164,7 +164,11 @@ pub trait Layout {
 }
 
 impl Layout for Content {
-    #[tracing::instrument(name = "Content::layout", skip_all)]
+    #[tracing::instrument(
+        name = "Content::layout",
+        skip_all,
+        fields(element = self.func().name())
+    )]
     fn layout(
         &self,
         vt: &mut Vt,
@@ -453,17 +457,33 @@ struct DocBuilder<'a> {
     pages: StyleVecBuilder<'a, Content>,
     /// Whether to keep a following page even if it is empty.
     keep_next: bool,
+    /// Whether the next page should be cleared to an even or odd number.
+    clear_next: Option<Parity>,
 }
 
 impl<'a> DocBuilder<'a> {
     fn accept(&mut self, content: &Content, styles: StyleChain<'a>) -> bool {
         if let Some(pagebreak) = content.to::<PagebreakElem>() {
-            self.keep_next = !pagebreak.weak(styles);
+            // A weak pagebreak on an empty page is skipped along with its
+            // parity.
+            let weak = pagebreak.weak(styles);
+            if !weak || !self.keep_next {
+                self.clear_next = pagebreak.to(styles);
+            }
+            self.keep_next = !weak;
             return true;
         }
 
-        if content.is::<PageElem>() {
-            self.pages.push(content.clone(), styles);
+        if let Some(page) = content.to::<PageElem>() {
+            let elem = if let Some(clear_to) = self.clear_next.take() {
+                let mut page = page.clone();
+                page.push_clear_to(Some(clear_to));
+                page.pack()
+            } else {
+                content.clone()
+            };
+
+            self.pages.push(elem, styles);
             self.keep_next = false;
             return true;
         }
@@ -474,7 +494,11 @@ impl<'a> DocBuilder<'a> {
 
 impl Default for DocBuilder<'_> {
     fn default() -> Self {
-        Self { pages: StyleVecBuilder::new(), keep_next: true }
+        Self {
+            pages: StyleVecBuilder::new(),
+            keep_next: true,
+            clear_next: None,
+        }
     }
 }
 
@@ -517,8 +541,16 @@ impl<'a> FlowBuilder<'a> {
                 self.0.push(spacing.pack(), styles);
             }
 
+            let par_spacing = if content.is::<ParElem>() {
+                ParElem::spacing_in(styles).as_custom()
+            } else {
+                None
+            };
+
             let (above, below) = if let Some(block) = content.to::<BlockElem>() {
                 (block.above(styles), block.below(styles))
+            } else if let Some(spacing) = par_spacing {
+                (VElem::block_spacing(spacing), VElem::block_spacing(spacing))
             } else {
                 (BlockElem::above_in(styles), BlockElem::below_in(styles))
             };
@@ -6,6 +6,11 @@ use crate::prelude::*;
 /// for each side individually, or for all sides at once by specifying a
 /// positional argument.
 ///
+/// Percentages in the padding are relative to the size of the padded result,
+/// so `{pad(x: 10%)}` leaves 80% of the width for the content. They can be
+/// combined with fixed lengths, as in `{10% + 4pt}`. Negative padding is
+/// allowed and makes the content bleed out of its container on that side.
+///
 /// ## Example { #example }
 /// ```example
 /// #set align(center)
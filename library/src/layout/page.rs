@@ -4,6 +4,7 @@ use std::str::FromStr;
 use super::{AlignElem, ColumnsElem};
 use crate::meta::{Counter, CounterKey, Numbering};
 use crate::prelude::*;
+use crate::text::TextElem;
 
 /// Layouts its child onto one or multiple pages.
 ///
@@ -97,6 +98,10 @@ pub struct PageElem {
     ///   - `right`: The right margin.
     ///   - `bottom`: The bottom margin.
     ///   - `left`: The left margin.
+    ///   - `inside`: The margin at the inner side of the page (where the
+    ///     [binding]($func/page.binding) is).
+    ///   - `outside`: The margin at the outer side of the page (opposite to the
+    ///     [binding]($func/page.binding)).
     ///   - `x`: The horizontal margins.
     ///   - `y`: The vertical margins.
     ///   - `rest`: The margins on all sides except those for which the
@@ -115,8 +120,33 @@ pub struct PageElem {
     ///   fill: aqua,
     /// )
     /// ```
+    ///
+    /// The `left` and `right` margins are mutually exclusive with the `inside`
+    /// and `outside` margins. With the latter, the horizontal margins are
+    /// mirrored on every other page, as is common for printed books.
+    ///
+    /// ```example
+    /// #set page(
+    ///   height: 60pt,
+    ///   margin: (inside: 32pt, outside: 8pt, y: 8pt),
+    /// )
+    ///
+    /// #lorem(16)
+    /// ```
     #[fold]
-    pub margin: Sides<Option<Smart<Rel<Length>>>>,
+    pub margin: Margin,
+
+    /// On which side the pages will be bound.
+    ///
+    /// - `{auto}`: Equivalent to `left` if the [text direction]($func/text.dir)
+    ///   is left-to-right and `right` if it is right-to-left.
+    /// - `left`: Bound on the left side.
+    /// - `right`: Bound on the right side.
+    ///
+    /// The binding determines which side of a page the `inside` and `outside`
+    /// margins are applied to. With a left binding, the first page's inside is
+    /// on its left, as it is a right-hand page.
+    pub binding: Smart<Binding>,
 
     /// How many columns the page has.
     ///
@@ -268,6 +298,12 @@ pub struct PageElem {
     /// will be created after the body has been typeset.
     #[required]
     pub body: Content,
+
+    /// Whether the page should be aligned to an even or odd page by
+    /// inserting a blank page before it if necessary. This is set by a
+    /// preceding [page break]($func/pagebreak) with a `to` argument.
+    #[internal]
+    pub clear_to: Option<Parity>,
 }
 
 impl PageElem {
@@ -302,12 +338,22 @@ impl PageElem {
 
         // Determine the margins.
         let default = Rel::from(0.1190 * min);
-        let margin = self
-            .margin(styles)
-            .map(|side| side.unwrap_or(default))
+        let margin = self.margin(styles);
+        let two_sided = margin.two_sided.unwrap_or(false);
+        let margin = margin
+            .sides
+            .map(|side| side.and_then(Smart::as_custom).unwrap_or(default))
             .resolve(styles)
             .relative_to(size);
 
+        // Determine the binding.
+        let binding =
+            self.binding(styles)
+                .unwrap_or_else(|| match TextElem::dir_in(styles) {
+                    Dir::LTR => Binding::Left,
+                    _ => Binding::Right,
+                });
+
         // Realize columns.
         let mut child = self.body();
         let columns = self.columns(styles);
@@ -320,14 +366,27 @@ impl PageElem {
         regions.root = true;
 
         // Layout the child.
-        let mut fragment = child.layout(vt, styles, regions)?;
+        let mut frames = child.layout(vt, styles, regions)?.into_frames();
+
+        // Align the child to the requested parity with a blank page.
+        if self
+            .clear_to(styles)
+            .map_or(false, |parity| !parity.matches(number.get()))
+        {
+            let size = area.map(Abs::is_finite).select(area, Size::zero());
+            frames.insert(0, Frame::new(size));
+        }
+
+        let mut fragment = Fragment::frames(frames);
 
         let fill = self.fill(styles);
         let foreground = self.foreground(styles);
         let background = self.background(styles);
         let header = self.header(styles);
         let header_ascent = self.header_ascent(styles);
-        let footer = self.footer(styles).or_else(|| {
+        let footer = self.footer(styles);
+        let custom_footer = footer.is_some();
+        let footer = footer.or_else(|| {
             self.numbering(styles).map(|numbering| {
                 let both = match &numbering {
                     Numbering::Pattern(pattern) => pattern.pieces() >= 2,
@@ -352,6 +411,15 @@ impl PageElem {
             // The padded width of the page's content without margins.
             let pw = frame.width();
 
+            // Mirror the horizontal margins on pages whose inside is on the
+            // right. The content's width stays the same as the sum of the
+            // horizontal margins doesn't change.
+            let margin = if two_sided && binding.swap(number) {
+                Sides { left: margin.right, right: margin.left, ..margin }
+            } else {
+                margin
+            };
+
             // Realize margins.
             frame.set_size(frame.size() + margin.sum_by_axis());
             frame.translate(Point::new(margin.left, margin.top));
@@ -388,12 +456,29 @@ impl PageElem {
                     align = Align::CENTER_HORIZON.into();
                 };
 
+                let body = content.clone().styled(AlignElem::set_alignment(align));
+
+                // Headers and footers that are taller than their margin are
+                // clipped instead of running into the page's body. Measuring
+                // takes an extra layout pass, so the footer that only shows
+                // the page number is not checked.
+                let clipped = ptr::eq(marginal, &header)
+                    || (custom_footer && ptr::eq(marginal, &footer));
+                let overflows = clipped && {
+                    let pod = Regions::one(area.with_y(Abs::inf()), Axes::splat(false));
+                    body.measure(vt, styles, pod)?.into_frame().height() > area.y
+                };
+
                 let pod = Regions::one(area, Axes::splat(true));
-                let sub = content
-                    .clone()
-                    .styled(AlignElem::set_alignment(align))
-                    .layout(vt, styles, pod)?
-                    .into_frame();
+                let mut sub = body.layout(vt, styles, pod)?.into_frame();
+                if overflows {
+                    vt.tracer.warn(warning!(
+                        content.span(),
+                        "page {} does not fit into its margin and is clipped",
+                        name,
+                    ));
+                    sub.clip();
+                }
 
                 if ptr::eq(marginal, &header) || ptr::eq(marginal, &background) {
                     frame.prepend_frame(pos, sub);
@@ -413,6 +498,135 @@ impl PageElem {
     }
 }
 
+/// Specification of the page's margins.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Margin {
+    /// The margins for each side.
+    pub sides: Sides<Option<Smart<Rel<Length>>>>,
+    /// Whether the `left` and `right` margins are actually `inside` and
+    /// `outside` margins that are mirrored depending on the binding.
+    pub two_sided: Option<bool>,
+}
+
+impl Margin {
+    /// Create an instance with four equal components.
+    pub fn splat(value: Option<Smart<Rel<Length>>>) -> Self {
+        Self { sides: Sides::splat(value), two_sided: None }
+    }
+}
+
+impl Fold for Margin {
+    type Output = Self;
+
+    fn fold(self, outer: Self::Output) -> Self::Output {
+        Self {
+            sides: self.sides.zip(outer.sides).map(|(inner, outer)| inner.or(outer)),
+            two_sided: self.two_sided.or(outer.two_sided),
+        }
+    }
+}
+
+cast_from_value! {
+    Margin,
+    v: Smart<Rel<Length>> => Self::splat(Some(v)),
+    mut dict: Dict => {
+        let mut take = |key| dict.take(key).ok().map(Value::cast).transpose();
+
+        let rest = take("rest")?;
+        let x = take("x")?.or(rest);
+        let y = take("y")?.or(rest);
+        let top = take("top")?.or(y);
+        let bottom = take("bottom")?.or(y);
+        let inside = take("inside")?;
+        let outside = take("outside")?;
+        let left = take("left")?;
+        let right = take("right")?;
+
+        let two_sided = inside.is_some() || outside.is_some();
+        if two_sided && (left.is_some() || right.is_some()) {
+            Err("`inside` and `outside` are mutually exclusive with `left` and `right`")?;
+        }
+
+        dict.finish(&[
+            "left", "top", "right", "bottom", "inside", "outside", "x", "y", "rest",
+        ])?;
+
+        // Only a margin that touches the horizontal sides decides whether
+        // the page is two-sided.
+        let horizontal = two_sided || left.is_some() || right.is_some();
+
+        Self {
+            sides: Sides {
+                left: inside.or(left).or(x),
+                top,
+                right: outside.or(right).or(x),
+                bottom,
+            },
+            two_sided: horizontal.then_some(two_sided),
+        }
+    },
+}
+
+cast_to_value! {
+    v: Margin => {
+        if !v.two_sided.unwrap_or(false) {
+            return v.sides.into();
+        }
+
+        let mut dict = Dict::new();
+        let mut handle = |key: &str, component: Option<Smart<Rel<Length>>>| {
+            if let Some(value) = component {
+                dict.insert(key.into(), value.into());
+            }
+        };
+
+        handle("top", v.sides.top);
+        handle("bottom", v.sides.bottom);
+        handle("inside", v.sides.left);
+        handle("outside", v.sides.right);
+
+        Value::Dict(dict)
+    }
+}
+
+/// On which side the pages will be bound.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Binding {
+    /// Bound on the left, as customary in LTR languages.
+    Left,
+    /// Bound on the right, as customary in RTL languages.
+    Right,
+}
+
+impl Binding {
+    /// Whether to swap left and right margin for the page with this number.
+    fn swap(self, number: NonZeroUsize) -> bool {
+        match self {
+            // With a left binding, odd pages are right-hand pages whose inside
+            // is on the left, so the margins must be swapped on even pages.
+            Self::Left => number.get() % 2 == 0,
+            // With a right binding, it is exactly the other way around.
+            Self::Right => number.get() % 2 == 1,
+        }
+    }
+}
+
+cast_from_value! {
+    Binding,
+    v: GenAlign => match v {
+        GenAlign::Specific(Align::Left) => Self::Left,
+        GenAlign::Specific(Align::Right) => Self::Right,
+        _ => Err("must be `left` or `right`")?,
+    },
+}
+
+cast_to_value! {
+    v: Binding => Value::from(match v {
+        Binding::Left => GenAlign::Specific(Align::Left),
+        Binding::Right => GenAlign::Specific(Align::Right),
+    })
+}
+
 /// A manual page break.
 ///
 /// Must not be used inside any containers.
@@ -435,6 +649,37 @@ pub struct PagebreakElem {
     /// empty.
     #[default(false)]
     pub weak: bool,
+
+    /// If given, ensures that the next page will be an even/odd page, with an
+    /// empty page in between if necessary.
+    ///
+    /// ```example
+    /// #set page(height: 30pt)
+    ///
+    /// First.
+    /// #pagebreak(to: "odd")
+    /// Third.
+    /// ```
+    pub to: Option<Parity>,
+}
+
+/// Whether something should be even or odd.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Parity {
+    /// Next page will be an even page.
+    Even,
+    /// Next page will be an odd page.
+    Odd,
+}
+
+impl Parity {
+    /// Whether the given physical page number matches this parity.
+    fn matches(self, number: usize) -> bool {
+        match self {
+            Self::Even => number % 2 == 0,
+            Self::Odd => number % 2 == 1,
+        }
+    }
 }
 
 /// A header, footer, foreground or background definition.
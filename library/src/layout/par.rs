@@ -8,8 +8,8 @@ use crate::layout::AlignElem;
 use crate::math::EquationElem;
 use crate::prelude::*;
 use crate::text::{
-    is_gb_style, shape, LinebreakElem, Quoter, Quotes, ShapedText, SmartQuoteElem,
-    SpaceElem, TextElem,
+    highlight, is_gb_style, shape, LinebreakElem, Quoter, Quotes, ShapedText,
+    SmartQuoteElem, SpaceElem, TextElem,
 };
 
 /// Arrange text, spacing and inline-level elements into a paragraph.
@@ -43,10 +43,35 @@ use crate::text::{
 #[element(Construct)]
 pub struct ParElem {
     /// The spacing between lines.
+    ///
+    /// Relative lengths like `{1em}` are resolved against the font size of
+    /// the tallest text in each line, so lines with larger text get more
+    /// leading.
+    ///
+    /// ```example
+    /// #set par(leading: 0.4em)
+    /// Small and #text(1.5em)[large \
+    /// text] on one line.
+    /// ```
     #[resolve]
     #[default(Em::new(0.65).into())]
     pub leading: Length,
 
+    /// The spacing between paragraphs.
+    ///
+    /// When set to `{auto}`, the [block spacing]($func/block.spacing) is used
+    /// instead. Explicit [`above`]($func/block.above) and
+    /// [`below`]($func/block.below) spacing of neighbouring blocks takes
+    /// precedence over this.
+    ///
+    /// ```example
+    /// #set par(spacing: 0.65em)
+    /// First paragraph.
+    ///
+    /// Second paragraph.
+    /// ```
+    pub spacing: Smart<Spacing>,
+
     /// Whether to justify text in its line.
     ///
     /// Hyphenation will be enabled for justified paragraphs if the [text
@@ -88,7 +113,9 @@ pub struct ParElem {
     /// The indent the first line of a paragraph should have.
     ///
     /// Only the first line of a consecutive paragraph will be indented (not
-    /// the first one in a block or on the page).
+    /// the first one in a block or on the page). In particular, paragraphs
+    /// directly following a heading or another block-level element are never
+    /// indented, as is typographic custom.
     ///
     /// By typographic convention, paragraph breaks are indicated either by some
     /// space between paragraphs or by indented first lines. Consider reducing
@@ -101,6 +128,39 @@ pub struct ParElem {
     #[resolve]
     pub hanging_indent: Length,
 
+    /// The minimum number of lines of a paragraph that must stay at the
+    /// bottom of a page or column when the paragraph is broken across it.
+    ///
+    /// If fewer lines would fit, these lines move on to the next page or
+    /// column together. Paragraphs with fewer lines than this are broken as
+    /// usual. Set this to `{1}` to disable orphan control.
+    ///
+    /// ```example
+    /// #set page(height: 80pt)
+    /// #set par(orphans: 3)
+    /// #v(40pt)
+    /// #lorem(20)
+    /// ```
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub orphans: NonZeroUsize,
+
+    /// The minimum number of lines of a paragraph that must start a page or
+    /// column when the paragraph is broken across it.
+    ///
+    /// If fewer lines would be left over, more lines move on to the next page
+    /// or column. As with [`orphans`]($func/par.orphans), paragraphs with
+    /// fewer lines than this are broken as usual. Set this to `{1}` to
+    /// disable widow control.
+    ///
+    /// ```example
+    /// #set page(height: 80pt)
+    /// #set par(widows: 3)
+    /// #v(20pt)
+    /// #lorem(20)
+    /// ```
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub widows: NonZeroUsize,
+
     /// The contents of the paragraph.
     #[external]
     #[required]
@@ -129,6 +189,8 @@ impl Construct for ParElem {
 
 impl ParElem {
     /// Layout the paragraph into a collection of lines.
+    ///
+    /// Also returns the leading between each pair of adjacent frames.
     #[tracing::instrument(name = "ParElement::layout", skip_all)]
     pub fn layout(
         &self,
@@ -137,7 +199,7 @@ impl ParElem {
         consecutive: bool,
         region: Size,
         expand: bool,
-    ) -> SourceResult<Fragment> {
+    ) -> SourceResult<(Fragment, Vec<Abs>)> {
         #[comemo::memoize]
         #[allow(clippy::too_many_arguments)]
         fn cached(
@@ -150,7 +212,7 @@ impl ParElem {
             consecutive: bool,
             region: Size,
             expand: bool,
-        ) -> SourceResult<Fragment> {
+        ) -> SourceResult<(Fragment, Vec<Abs>)> {
             let mut locator = Locator::chained(locator);
             let mut vt = Vt { world, tracer, locator: &mut locator, introspector };
             let children = par.children();
@@ -162,6 +224,7 @@ impl ParElem {
             // representation on which we can do line breaking without layouting
             // each and every line from scratch.
             let p = prepare(&mut vt, &children, &text, segments, spans, styles, region)?;
+            warn_tofus(&mut vt, &p);
 
             // Break the paragraph into lines.
             let lines = linebreak(&vt, &p, region.x - p.hang);
@@ -170,7 +233,7 @@ impl ParElem {
             finalize(&mut vt, &p, &lines, region, expand)
         }
 
-        let fragment = cached(
+        let (fragment, leadings) = cached(
             self,
             vt.world,
             TrackedMut::reborrow_mut(&mut vt.tracer),
@@ -183,7 +246,7 @@ impl ParElem {
         )?;
 
         vt.locator.visit_frames(&fragment);
-        Ok(fragment)
+        Ok((fragment, leadings))
     }
 }
 
@@ -498,6 +561,16 @@ impl<'a> Line<'a> {
         count
     }
 
+    /// The leading after this line, resolved for its tallest text run.
+    fn leading(&self, p: &Preparation) -> Abs {
+        self.items()
+            .filter_map(Item::text)
+            .max_by_key(|shaped| shaped.size)
+            .map_or(ParElem::leading_in(p.styles), |shaped| {
+                ParElem::leading_in(shaped.styles)
+            })
+    }
+
     /// How much can the line stretch
     fn stretchability(&self) -> Abs {
         self.items().filter_map(Item::text).map(|s| s.stretchability()).sum()
@@ -792,6 +865,40 @@ fn shared_get<T: PartialEq>(
         .then_some(value)
 }
 
+/// Warn about characters that none of the fonts have a glyph for.
+///
+/// Each character is only reported once per document to not drown out other
+/// warnings when it is used many times.
+fn warn_tofus(vt: &mut Vt, p: &Preparation) {
+    for shaped in p.items.iter().filter_map(Item::text) {
+        for glyph in &shaped.glyphs {
+            if glyph.glyph_id != 0 || glyph.c.is_whitespace() {
+                continue;
+            }
+
+            let mut tried = TextElem::font_in(shaped.styles)
+                .into_iter()
+                .map(|family| eco_format!("{:?}", family.as_str()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            if TextElem::fallback_in(shaped.styles) {
+                tried.push_str(" and the fallback fonts");
+            }
+
+            vt.tracer.warn_once(
+                typst::util::hash128(&("tofu", glyph.c)),
+                warning!(
+                    glyph.span.0,
+                    "no font has a glyph for {:?} (U+{:04X}), tried {}",
+                    glyph.c,
+                    glyph.c as u32,
+                    tried,
+                ),
+            );
+        }
+    }
+}
+
 /// Find suitable linebreaks.
 fn linebreak<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<Line<'a>> {
     let linebreaks = ParElem::linebreaks_in(p.styles).unwrap_or_else(|| {
@@ -1241,14 +1348,15 @@ fn line<'a>(
     }
 }
 
-/// Combine layouted lines into one frame per region.
+/// Combine layouted lines into one frame per region and determine the leading
+/// between them.
 fn finalize(
     vt: &mut Vt,
     p: &Preparation,
     lines: &[Line],
     region: Size,
     expand: bool,
-) -> SourceResult<Fragment> {
+) -> SourceResult<(Fragment, Vec<Abs>)> {
     // Determine the paragraph's width: Full width of the region if we
     // should expand or there's fractional spacing, fit-to-width otherwise.
     let width = if !region.x.is_finite()
@@ -1265,23 +1373,40 @@ fn finalize(
         .map(|line| commit(vt, p, line, width, region.y))
         .collect::<SourceResult<_>>()?;
 
-    // Prevent orphans.
-    let leading = ParElem::leading_in(p.styles);
-    if frames.len() >= 2 && !frames[1].is_empty() {
-        let second = frames.remove(1);
-        let first = &mut frames[0];
-        merge(first, second, leading);
+    // Lines with different font sizes are separated by the larger leading.
+    let mut leadings: Vec<Abs> = lines
+        .windows(2)
+        .map(|pair| pair[0].leading(p).max(pair[1].leading(p)))
+        .collect();
+
+    // Prevent orphans by merging the first lines into one frame.
+    let orphans = ParElem::orphans_in(p.styles).get();
+    if frames.len() >= orphans {
+        for _ in 1..orphans {
+            if frames.len() < 2 || frames[1].is_empty() {
+                break;
+            }
+            let second = frames.remove(1);
+            let first = &mut frames[0];
+            merge(first, second, leadings.remove(0));
+        }
     }
 
-    // Prevent widows.
-    let len = frames.len();
-    if len >= 2 && !frames[len - 2].is_empty() {
-        let second = frames.pop().unwrap();
-        let first = frames.last_mut().unwrap();
-        merge(first, second, leading);
+    // Prevent widows by merging the last lines into one frame.
+    let widows = ParElem::widows_in(p.styles).get();
+    if frames.len() >= widows {
+        for _ in 1..widows {
+            let len = frames.len();
+            if len < 2 || frames[len - 2].is_empty() {
+                break;
+            }
+            let second = frames.pop().unwrap();
+            let first = frames.last_mut().unwrap();
+            merge(first, second, leadings.pop().unwrap());
+        }
     }
 
-    Ok(Fragment::frames(frames))
+    Ok((Fragment::frames(frames), leadings))
 }
 
 /// Merge two line frames
@@ -1371,6 +1496,7 @@ fn commit(
 
     // Build the frames and determine the height and baseline.
     let mut frames = vec![];
+    let mut runs = vec![];
     for item in reordered {
         let mut push = |offset: &mut Abs, frame: Frame| {
             let width = frame.width();
@@ -1383,6 +1509,7 @@ fn commit(
         match item {
             Item::Absolute(v) => {
                 offset += *v;
+                runs.push(None);
             }
             Item::Fractional(v, elem) => {
                 let amount = v.share(fr, remaining);
@@ -1395,12 +1522,19 @@ fn commit(
                 } else {
                     offset += amount;
                 }
+                runs.push(None);
             }
             Item::Text(shaped) => {
                 let frame = shaped.build(vt, justification_ratio, extra_justification);
+                let start = offset;
                 push(&mut offset, frame);
+                runs.push(Some((start, offset, shaped)));
             }
-            Item::Frame(frame) | Item::Meta(frame) => {
+            Item::Frame(frame) => {
+                push(&mut offset, frame.clone());
+                runs.push(None);
+            }
+            Item::Meta(frame) => {
                 push(&mut offset, frame.clone());
             }
         }
@@ -1416,12 +1550,19 @@ fn commit(
     output.set_baseline(top);
 
     // Construct the line's frame.
+    let dx = p.align.position(remaining);
     for (offset, frame) in frames {
-        let x = offset + p.align.position(remaining);
         let y = top - frame.baseline();
-        output.push_frame(Point::new(x, y), frame);
+        output.push_frame(Point::new(offset + dx, y), frame);
     }
 
+    // Draw highlights behind the line's text.
+    let runs: Vec<_> = runs
+        .into_iter()
+        .map(|run| run.map(|(start, end, shaped)| (start + dx, end + dx, shaped)))
+        .collect();
+    highlight(&mut output, &runs);
+
     Ok(output)
 }
 
@@ -4,8 +4,11 @@ use crate::prelude::*;
 ///
 /// Placed content will not affect the position of other content. Place is
 /// always relative to its parent container and will be in the foreground of all
-/// other content in the container. Page margins will be respected.
+/// other content in the container. Page margins will be respected. Multiple
+/// placed elements are layered in the order in which they appear.
 ///
+/// Placed content can also _float_: Then, it's moved to the top or bottom of
+/// the current region and the other content in the flow makes room for it.
 ///
 /// ## Example { #example }
 /// ```example
@@ -48,6 +51,28 @@ pub struct PlaceElem {
     /// The vertical displacement of the placed content.
     pub dy: Rel<Length>,
 
+    /// Whether the placed content should float to the top or bottom of the
+    /// current region instead of overlapping the other content. The other
+    /// content of the flow then flows around it.
+    ///
+    /// A floating element's vertical alignment must be `{top}` or
+    /// `{bottom}`. This only has an effect in flows, such as the body of a
+    /// page or a block. Elsewhere, floating content is laid out like a block.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #place(bottom, float: true, rect(width: 100%)[Figure])
+    /// #lorem(12)
+    /// ```
+    #[default(false)]
+    pub float: bool,
+
+    /// The amount of space between floating content and the other content of
+    /// the flow.
+    #[resolve]
+    #[default(Em::new(1.5).into())]
+    pub clearance: Length,
+
     /// The content to place.
     #[required]
     pub body: Content,
@@ -61,6 +86,18 @@ impl Layout for PlaceElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
+        // Floating content spans the full width and only takes up the
+        // height it needs. The flow moves it to its final position.
+        if self.float(styles) {
+            let pod = Regions::one(regions.base(), Axes::new(true, false));
+            let alignment = self.alignment(styles);
+            let child = self
+                .body()
+                .moved(Axes::new(self.dx(styles), self.dy(styles)))
+                .aligned(Axes::new(alignment.x, None));
+            return child.layout(vt, styles, pod);
+        }
+
         let out_of_flow = self.out_of_flow(styles);
 
         // The pod is the base area of the region because for absolute
@@ -68,6 +68,10 @@ impl Behave for HElem {
 /// the remaining space on the page is distributed among all fractional spacings
 /// according to their relative fractions.
 ///
+/// If the available height is unbounded, for example in a page with
+/// `{height: auto}` or in a block without a fixed height, there is no
+/// remaining space to distribute and fractional spacing collapses to zero.
+///
 /// ## Example { #example }
 /// ```example
 /// #grid(
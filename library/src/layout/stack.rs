@@ -32,6 +32,26 @@ pub struct StackElem {
     /// Spacing to insert between items where no explicit spacing was provided.
     pub spacing: Option<Spacing>,
 
+    /// Whether to align the items of a horizontal stack on their baselines.
+    ///
+    /// By default, items are aligned vertically according to their
+    /// [alignment]($func/align), which is at the top unless specified
+    /// otherwise. With this enabled, text and boxes line up as they would in
+    /// a paragraph instead and the vertical alignment of the items is ignored.
+    /// This has no effect on vertical stacks.
+    ///
+    /// ```example
+    /// #stack(
+    ///   dir: ltr,
+    ///   baseline: true,
+    ///   text(20pt)[Big],
+    ///   [small],
+    ///   box(rect(height: 1em)),
+    /// )
+    /// ```
+    #[default(false)]
+    pub baseline: bool,
+
     /// The children to stack along the axis.
     #[variadic]
     pub children: Vec<StackChild>,
@@ -45,7 +65,8 @@ impl Layout for StackElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
-        let mut layouter = StackLayouter::new(self.dir(styles), regions, styles);
+        let mut layouter =
+            StackLayouter::new(self.dir(styles), self.baseline(styles), regions, styles);
 
         // Spacing to insert before the next block.
         let spacing = self.spacing(styles);
@@ -109,6 +130,8 @@ struct StackLayouter<'a> {
     dir: Dir,
     /// The axis of the stacking direction.
     axis: Axis,
+    /// Whether items are aligned on their baselines along the cross axis.
+    baseline: bool,
     /// The regions to layout children into.
     regions: Regions<'a>,
     /// The inherited styles.
@@ -119,6 +142,12 @@ struct StackLayouter<'a> {
     initial: Size,
     /// The generic size used by the frames for the current region.
     used: Gen<Abs>,
+    /// The maximal distance from the top to the baseline of the frames in the
+    /// current region.
+    ascent: Abs,
+    /// The maximal distance from the baseline to the bottom of the frames in
+    /// the current region.
+    descent: Abs,
     /// The sum of fractions in the current region.
     fr: Fr,
     /// Already layouted items whose exact positions are not yet known due to
@@ -140,7 +169,12 @@ enum StackItem {
 
 impl<'a> StackLayouter<'a> {
     /// Create a new stack layouter.
-    fn new(dir: Dir, mut regions: Regions<'a>, styles: StyleChain<'a>) -> Self {
+    fn new(
+        dir: Dir,
+        baseline: bool,
+        mut regions: Regions<'a>,
+        styles: StyleChain<'a>,
+    ) -> Self {
         let axis = dir.axis();
         let expand = regions.expand;
 
@@ -150,11 +184,14 @@ impl<'a> StackLayouter<'a> {
         Self {
             dir,
             axis,
+            baseline: baseline && axis == Axis::X,
             regions,
             styles,
             expand,
             initial: regions.size,
             used: Gen::zero(),
+            ascent: Abs::zero(),
+            descent: Abs::zero(),
             fr: Fr::zero(),
             items: vec![],
             finished: vec![],
@@ -223,6 +260,12 @@ impl<'a> StackLayouter<'a> {
 
             self.used.main += gen.main;
             self.used.cross.set_max(gen.cross);
+            if self.baseline {
+                let baseline = first_baseline(&frame);
+                self.ascent.set_max(baseline);
+                self.descent.set_max(size.y - baseline);
+                self.used.cross.set_max(self.ascent + self.descent);
+            }
 
             self.items.push(StackItem::Frame(frame, aligns));
 
@@ -279,9 +322,13 @@ impl<'a> StackLayouter<'a> {
 
                     // Align along the cross axis.
                     let other = self.axis.other();
-                    let cross = aligns
-                        .get(other)
-                        .position(size.get(other) - frame.size().get(other));
+                    let cross = if self.baseline {
+                        self.ascent - first_baseline(&frame)
+                    } else {
+                        aligns
+                            .get(other)
+                            .position(size.get(other) - frame.size().get(other))
+                    };
 
                     let pos = Gen::new(cross, main).to_point(self.axis);
                     cursor += child;
@@ -290,10 +337,16 @@ impl<'a> StackLayouter<'a> {
             }
         }
 
+        if self.baseline {
+            output.set_baseline(self.ascent);
+        }
+
         // Advance to the next region.
         self.regions.next();
         self.initial = self.regions.size;
         self.used = Gen::zero();
+        self.ascent = Abs::zero();
+        self.descent = Abs::zero();
         self.fr = Fr::zero();
         self.finished.push(output);
     }
@@ -305,6 +358,26 @@ impl<'a> StackLayouter<'a> {
     }
 }
 
+/// The baseline of a frame, or else the baseline of the first text in it.
+fn first_baseline(frame: &Frame) -> Abs {
+    if frame.has_baseline() {
+        return frame.baseline();
+    }
+
+    text_baseline(frame).unwrap_or(frame.height())
+}
+
+/// The baseline of the first text in a frame, if any.
+fn text_baseline(frame: &Frame) -> Option<Abs> {
+    frame.items().find_map(|(pos, item)| match item {
+        FrameItem::Text(_) => Some(pos.y),
+        FrameItem::Group(group) if group.transform.is_identity() => {
+            text_baseline(&group.frame).map(|y| pos.y + y)
+        }
+        _ => None,
+    })
+}
+
 /// A container with a main and cross component.
 #[derive(Default, Copy, Clone, Eq, PartialEq, Hash)]
 struct Gen<T> {
@@ -1,4 +1,7 @@
-use crate::layout::{AlignElem, GridLayouter, TrackSizings};
+use crate::layout::{
+    inline_header, place_cells, AlignElem, CellPlacement, GridCell, GridHeader,
+    GridLayouter, TrackSizings,
+};
 use crate::meta::{Figurable, LocalName};
 use crate::prelude::*;
 
@@ -13,6 +16,11 @@ use crate::prelude::*;
 /// To give a table a caption and make it [referenceable]($func/ref), put it
 /// into a [figure]($func/figure).
 ///
+/// Cells can span multiple columns or rows by wrapping them in
+/// [`table.cell`]($func/grid.cell). Lines are not drawn through such cells.
+/// The first rows can be marked as a [`table.header`]($func/grid.header) that
+/// is repeated on each page the table breaks across.
+///
 /// ## Example { #example }
 /// ```example
 /// #table(
@@ -36,6 +44,11 @@ use crate::prelude::*;
 /// Display: Table
 /// Category: layout
 #[element(Layout, LocalName, Figurable)]
+#[scope(
+    scope.define("cell", GridCell::func());
+    scope.define("header", GridHeader::func());
+    scope
+)]
 pub struct TableElem {
     /// Defines the column sizes. See the [grid documentation]($func/grid) for
     /// more information on track sizing.
@@ -138,23 +151,39 @@ impl Layout for TableElem {
         let tracks = Axes::new(self.columns(styles).0, self.rows(styles).0);
         let gutter = Axes::new(self.column_gutter(styles).0, self.row_gutter(styles).0);
         let cols = tracks.x.len().max(1);
-        let cells: Vec<_> = self
-            .children()
+        let (children, header) = inline_header(&self.children())?;
+        let count = header.as_ref().map_or(0, |header| header.children().len());
+        let placements = place_cells(&children, cols, count, styles)?;
+        let mut cells: Vec<_> = children
             .into_iter()
-            .enumerate()
-            .map(|(i, child)| {
-                let mut child = child.padded(Sides::splat(inset));
-
-                let x = i % cols;
-                let y = i / cols;
+            .zip(placements)
+            .map(|(child, placement)| {
+                // Cells spanning multiple tracks are padded and aligned as a
+                // whole.
+                let cell = child.to::<GridCell>().cloned();
+                let body = cell.as_ref().map_or(child, GridCell::body);
+                let mut body = body.padded(Sides::splat(inset));
+
+                let CellPlacement { x, y, .. } = placement;
                 if let Smart::Custom(alignment) = align.resolve(vt, x, y)? {
-                    child = child.styled(AlignElem::set_alignment(alignment));
+                    body = body.styled(AlignElem::set_alignment(alignment));
                 }
 
-                Ok(child)
+                Ok(match cell {
+                    Some(cell) => cell.with_body(body).pack(),
+                    None => body,
+                })
             })
             .collect::<SourceResult<_>>()?;
 
+        // Put the padded header cells back into the header.
+        if let Some(header) = header {
+            let body = cells.split_off(count);
+            cells = std::iter::once(header.with_children(cells).pack())
+                .chain(body)
+                .collect();
+        }
+
         let fill = self.fill(styles);
         let stroke = self.stroke(styles).map(PartialStroke::unwrap_or_default);
 
@@ -166,7 +195,7 @@ impl Layout for TableElem {
             &cells,
             regions,
             styles,
-        );
+        )?;
 
         // Measure the columns and layout the grid row-by-row.
         let mut layout = layouter.layout()?;
@@ -178,33 +207,69 @@ impl Layout for TableElem {
                 let thickness = stroke.thickness;
                 let half = thickness / 2.0;
 
-                // Render horizontal lines.
-                for offset in points(rows.iter().map(|piece| piece.height)) {
-                    let target = Point::with_x(frame.width() + thickness);
-                    let hline = Geometry::Line(target).stroked(stroke.clone());
-                    frame.prepend(
-                        Point::new(-half, offset),
-                        FrameItem::Shape(hline, self.span()),
-                    );
+                // Render horizontal lines, leaving out cells that span
+                // across them.
+                let heights = rows.iter().map(|piece| piece.height);
+                for (i, offset) in points(heights).enumerate() {
+                    let spanned = |x| {
+                        let (Some(above), Some(below)) =
+                            (i.checked_sub(1).and_then(|k| rows.get(k)), rows.get(i))
+                        else {
+                            return false;
+                        };
+                        layout.spans.iter().any(|span| {
+                            span.covers(x, above.y) && span.covers(x, below.y)
+                        })
+                    };
+
+                    for (start, length) in segments(&layout.cols, frame.width(), spanned)
+                    {
+                        let target = Point::with_x(length + thickness);
+                        let hline = Geometry::Line(target).stroked(stroke.clone());
+                        frame.prepend(
+                            Point::new(start - half, offset),
+                            FrameItem::Shape(hline, self.span()),
+                        );
+                    }
                 }
 
-                // Render vertical lines.
-                for offset in points(layout.cols.iter().copied()) {
-                    let target = Point::with_y(frame.height() + thickness);
-                    let vline = Geometry::Line(target).stroked(stroke.clone());
-                    frame.prepend(
-                        Point::new(offset, -half),
-                        FrameItem::Shape(vline, self.span()),
-                    );
+                // Render vertical lines, leaving out cells that span across
+                // them.
+                let heights: Vec<_> = rows.iter().map(|piece| piece.height).collect();
+                for (i, offset) in points(layout.cols.iter().copied()).enumerate() {
+                    let spanned = |k: usize| {
+                        let y = rows[k].y;
+                        i > 0
+                            && i < layout.cols.len()
+                            && layout
+                                .spans
+                                .iter()
+                                .any(|span| span.covers(i - 1, y) && span.covers(i, y))
+                    };
+
+                    for (start, length) in segments(&heights, frame.height(), spanned) {
+                        let target = Point::with_y(length + thickness);
+                        let vline = Geometry::Line(target).stroked(stroke.clone());
+                        frame.prepend(
+                            Point::new(offset, start - half),
+                            FrameItem::Shape(vline, self.span()),
+                        );
+                    }
                 }
             }
 
-            // Render cell backgrounds.
+            // Render cell backgrounds. Cells spanning multiple tracks are
+            // filled as a whole.
             let mut dx = Abs::zero();
             for (x, &col) in layout.cols.iter().enumerate() {
                 let mut dy = Abs::zero();
                 for row in rows {
-                    if let Some(fill) = fill.resolve(vt, x, row.y)? {
+                    let (fx, fy) = layout
+                        .spans
+                        .iter()
+                        .find(|span| span.covers(x, row.y))
+                        .map_or((x, row.y), |span| (span.cols.start, span.rows.start));
+                    if let Some(fill) = fill.resolve(vt, fx, fy)? {
                         let pos = Point::new(dx, dy);
                         let size = Size::new(col, row.height);
                         let rect = Geometry::Rect(size).filled(fill);
@@ -232,6 +297,38 @@ fn points(extents: impl IntoIterator<Item = Abs>) -> impl Iterator<Item = Abs> {
         })
 }
 
+/// Split consecutive extents into runs, leaving out the extents for which
+/// `skip` returns true, and return the offset and length of each run.
+///
+/// A run that reaches the last extent is stretched to the `total` length.
+fn segments(
+    extents: &[Abs],
+    total: Abs,
+    mut skip: impl FnMut(usize) -> bool,
+) -> Vec<(Abs, Abs)> {
+    let mut runs = vec![];
+    let mut run: Option<(Abs, Abs)> = None;
+    let mut offset = Abs::zero();
+
+    for (i, &extent) in extents.iter().enumerate() {
+        if skip(i) {
+            runs.extend(run.take());
+        } else {
+            let (_, length) = run.get_or_insert((offset, Abs::zero()));
+            *length += extent;
+        }
+        offset += extent;
+    }
+
+    if let Some((start, _)) = run {
+        runs.push((start, total - start));
+    } else if extents.is_empty() {
+        runs.push((Abs::zero(), total));
+    }
+
+    runs
+}
+
 /// A value that can be configured per cell.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub enum Celled<T> {
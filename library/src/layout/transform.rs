@@ -56,7 +56,7 @@ impl Layout for MoveElem {
 /// Rotate content without affecting layout.
 ///
 /// Rotate an element by a given angle. The layout will act as if the element
-/// was not rotated.
+/// was not rotated unless `reflow` is enabled.
 ///
 /// ## Example { #example }
 /// ```example
@@ -101,6 +101,19 @@ pub struct RotateElem {
     #[default(Align::CENTER_HORIZON)]
     pub origin: Axes<Option<GenAlign>>,
 
+    /// Whether the rotation impacts the layout.
+    ///
+    /// If set to `{false}`, the rotated content will retain the bounding box
+    /// of the original content. If set to `{true}`, the bounding box will
+    /// take the rotation of the content into account and adjust the layout
+    /// accordingly.
+    ///
+    /// ```example
+    /// Hello #rotate(90deg, reflow: true)[World]!
+    /// ```
+    #[default(false)]
+    pub reflow: bool,
+
     /// The content to rotate.
     #[required]
     pub body: Content,
@@ -121,7 +134,7 @@ impl Layout for RotateElem {
         let ts = Transform::translate(x, y)
             .pre_concat(Transform::rotate(self.angle(styles)))
             .pre_concat(Transform::translate(-x, -y));
-        frame.transform(ts);
+        transform_frame(&mut frame, ts, self.reflow(styles));
         Ok(Fragment::frame(frame))
     }
 }
@@ -129,7 +142,7 @@ impl Layout for RotateElem {
 /// Scale content without affecting layout.
 ///
 /// The `scale` function allows you to scale and mirror content without
-/// affecting the layout.
+/// affecting the layout unless `reflow` is enabled.
 ///
 ///
 /// ## Example { #example }
@@ -170,6 +183,19 @@ pub struct ScaleElem {
     #[default(Align::CENTER_HORIZON)]
     pub origin: Axes<Option<GenAlign>>,
 
+    /// Whether the scaling impacts the layout.
+    ///
+    /// If set to `{false}`, the scaled content will retain the bounding box
+    /// of the original content. If set to `{true}`, the bounding box will
+    /// take the scaling of the content into account and adjust the layout
+    /// accordingly.
+    ///
+    /// ```example
+    /// Hello #scale(x: 20%, y: 40%, reflow: true)[World]!
+    /// ```
+    #[default(false)]
+    pub reflow: bool,
+
     /// The content to scale.
     #[required]
     pub body: Content,
@@ -190,7 +216,36 @@ impl Layout for ScaleElem {
         let transform = Transform::translate(x, y)
             .pre_concat(Transform::scale(self.x(styles), self.y(styles)))
             .pre_concat(Transform::translate(-x, -y));
-        frame.transform(transform);
+        transform_frame(&mut frame, transform, self.reflow(styles));
         Ok(Fragment::frame(frame))
     }
 }
+
+/// Apply a transform to a frame's contents.
+///
+/// With `reflow`, the frame is resized to the bounding box of its transformed
+/// contents, which are moved to start at the frame's origin. The baseline is
+/// then placed at the bottom of the new frame.
+fn transform_frame(frame: &mut Frame, ts: Transform, reflow: bool) {
+    if !reflow {
+        frame.transform(ts);
+        return;
+    }
+
+    let size = frame.size();
+    let corners =
+        [Point::zero(), Point::with_x(size.x), Point::with_y(size.y), size.to_point()]
+            .map(|corner| corner.transform(ts));
+
+    let mut min = corners[0];
+    let mut max = corners[0];
+    for corner in &corners[1..] {
+        min = Point::new(min.x.min(corner.x), min.y.min(corner.y));
+        max = Point::new(max.x.max(corner.x), max.y.max(corner.y));
+    }
+
+    let bbox = Size::new(max.x - min.x, max.y - min.y);
+    frame.transform(Transform::translate(-min.x, -min.y).pre_concat(ts));
+    frame.set_size(bbox);
+    frame.set_baseline(bbox.y);
+}
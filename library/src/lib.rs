@@ -16,7 +16,7 @@ pub mod visualize;
 use typst::diag::At;
 use typst::eval::{LangItems, Library, Module, Scope};
 use typst::geom::{Align, Color, Dir, GenAlign, Smart};
-use typst::model::{Element, Styles};
+use typst::model::{Element, StyleChain, Styles};
 
 use self::layout::LayoutRoot;
 
@@ -160,6 +160,10 @@ fn items() -> LangItems {
         math_root: |index, radicand| {
             math::RootElem::new(radicand).with_index(index).pack()
         },
+        mathml: |content| {
+            let equation = content.to::<math::EquationElem>()?;
+            Some(math::mathml(equation, StyleChain::default()))
+        },
         library_method: |vm, dynamic, method, args, span| {
             if let Some(counter) = dynamic.downcast::<meta::Counter>().cloned() {
                 counter.call_method(vm, method, args, span)
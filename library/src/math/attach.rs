@@ -365,7 +365,7 @@ fn is_atomic_text_frame(frame: &Frame) -> bool {
 
 /// Unicode codepoints that should have sub- and superscripts attached as limits.
 #[rustfmt::skip]
-const LIMITS: &[char] = &[
+pub(super) const LIMITS: &[char] = &[
     /* ∏ */ '\u{220F}', /* ∐ */ '\u{2210}', /* ∑ */ '\u{2211}',
     /* ⋀ */ '\u{22C0}', /* ⋁ */ '\u{22C1}',
     /* ⋂ */ '\u{22C2}', /* ⋃ */ '\u{22C3}',
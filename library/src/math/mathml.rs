@@ -0,0 +1,484 @@
+use std::fmt::Write;
+
+use super::*;
+
+/// Export an equation into MathML.
+///
+/// This works on the equation's content instead of its layout, so that the
+/// structure of fractions, scripts, roots, and so on is preserved. Elements
+/// without a MathML counterpart degrade to `<mtext>` with their plain text.
+pub fn mathml(equation: &EquationElem, styles: StyleChain) -> String {
+    let display = if equation.block(styles) { "block" } else { "inline" };
+    let mut out = format!("<math display=\"{display}\">");
+    row(&mut out, &equation.body(), styles);
+    out.push_str("</math>");
+    out
+}
+
+/// Write content as a single MathML element, wrapping it in an `<mrow>`.
+fn row(out: &mut String, content: &Content, styles: StyleChain) {
+    out.push_str("<mrow>");
+    write_content(out, content, styles);
+    out.push_str("</mrow>");
+}
+
+/// Write optional content as a single MathML element or `<none/>`.
+fn row_or_none(out: &mut String, content: Option<&Content>, styles: StyleChain) {
+    match content {
+        Some(content) => row(out, content, styles),
+        None => out.push_str("<none/>"),
+    }
+}
+
+/// Write the MathML for a piece of content.
+fn write_content(out: &mut String, content: &Content, styles: StyleChain) {
+    if let Some(children) = content.to_sequence() {
+        for child in children {
+            write_content(out, child, styles);
+        }
+    } else if let Some((body, map)) = content.to_styled() {
+        write_content(out, body, styles.chain(map));
+    } else if let Some(elem) = content.to::<EquationElem>() {
+        write_content(out, &elem.body(), styles);
+    } else if let Some(elem) = content.to::<TextElem>() {
+        write_text(out, &elem.text());
+    } else if content.is::<SpaceElem>() || content.is::<AlignPointElem>() {
+        // Spacing between math tokens is decided by the MathML renderer, and
+        // alignment points have no counterpart outside of tables.
+    } else if content.is::<LinebreakElem>() {
+        out.push_str("<mspace linebreak=\"newline\"/>");
+    } else if let Some(elem) = content.to::<HElem>() {
+        write_space(out, elem.amount());
+    } else if let Some(elem) = content.to::<FracElem>() {
+        out.push_str("<mfrac>");
+        row(out, &elem.num(), styles);
+        row(out, &elem.denom(), styles);
+        out.push_str("</mfrac>");
+    } else if let Some(elem) = content.to::<BinomElem>() {
+        out.push_str("<mrow><mo>(</mo><mfrac linethickness=\"0\">");
+        row(out, &elem.upper(), styles);
+        row(out, &elem.lower(), styles);
+        out.push_str("</mfrac><mo>)</mo></mrow>");
+    } else if let Some(elem) = content.to::<AttachElem>() {
+        write_attach(out, elem, styles);
+    } else if let Some(elem) = content.to::<RootElem>() {
+        match elem.index(styles) {
+            Some(index) => {
+                out.push_str("<mroot>");
+                row(out, &elem.radicand(), styles);
+                row(out, &index, styles);
+                out.push_str("</mroot>");
+            }
+            None => {
+                out.push_str("<msqrt>");
+                write_content(out, &elem.radicand(), styles);
+                out.push_str("</msqrt>");
+            }
+        }
+    } else if let Some(elem) = content.to::<OpElem>() {
+        write!(out, "<mi>{}</mi>", escape(&elem.text().plain_text())).unwrap();
+    } else if let Some(elem) = content.to::<LrElem>() {
+        row(out, &elem.body(), styles);
+    } else if let Some(elem) = content.to::<VecElem>() {
+        let rows: Vec<_> = elem.children().into_iter().map(|cell| vec![cell]).collect();
+        let delim = elem.delim(styles);
+        write_table(
+            out,
+            &rows,
+            delim.map(Delimiter::open),
+            delim.map(Delimiter::close),
+            styles,
+        );
+    } else if let Some(elem) = content.to::<MatElem>() {
+        let delim = elem.delim(styles);
+        write_table(
+            out,
+            &elem.rows(),
+            delim.map(Delimiter::open),
+            delim.map(Delimiter::close),
+            styles,
+        );
+    } else if let Some(elem) = content.to::<CasesElem>() {
+        let rows: Vec<_> = elem.children().into_iter().map(|cell| vec![cell]).collect();
+        write_table(out, &rows, Some(elem.delim(styles).open()), None, styles);
+    } else if let Some(elem) = content.to::<AccentElem>() {
+        out.push_str("<mover accent=\"true\">");
+        row(out, &elem.base(), styles);
+        write!(out, "<mo>{}</mo>", escape(&elem.accent().0.to_string())).unwrap();
+        out.push_str("</mover>");
+    } else if let Some(elem) = content.to::<UnderlineElem>() {
+        write_under(out, &elem.body(), '_', None, styles);
+    } else if let Some(elem) = content.to::<OverlineElem>() {
+        write_over(out, &elem.body(), '‾', None, styles);
+    } else if let Some(elem) = content.to::<UnderbraceElem>() {
+        write_under(out, &elem.body(), '⏟', elem.annotation(styles), styles);
+    } else if let Some(elem) = content.to::<OverbraceElem>() {
+        write_over(out, &elem.body(), '⏞', elem.annotation(styles), styles);
+    } else if let Some(elem) = content.to::<UnderbracketElem>() {
+        write_under(out, &elem.body(), '⎵', elem.annotation(styles), styles);
+    } else if let Some(elem) = content.to::<OverbracketElem>() {
+        write_over(out, &elem.body(), '⎴', elem.annotation(styles), styles);
+    } else if let Some(elem) = content.to::<CancelElem>() {
+        out.push_str("<menclose notation=\"updiagonalstrike\">");
+        row(out, &elem.body(), styles);
+        out.push_str("</menclose>");
+    } else if let Some(elem) = content.to::<MathStyleElem>() {
+        write_style(out, elem, styles);
+    } else if let Some(elem) = content.to::<ScriptsElem>() {
+        write_content(out, &elem.body(), styles);
+    } else if let Some(elem) = content.to::<LimitsElem>() {
+        write_content(out, &elem.body(), styles);
+    } else {
+        let text = content.plain_text();
+        if !text.is_empty() {
+            write!(out, "<mtext>{}</mtext>", escape(&text)).unwrap();
+        }
+    }
+}
+
+/// Write text as MathML tokens.
+fn write_text(out: &mut String, text: &str) {
+    let mut chars = text.chars();
+    if text.is_empty() {
+        return;
+    }
+
+    if text.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        write!(out, "<mn>{}</mn>", escape(text)).unwrap();
+    } else if let (Some(c), None) = (chars.next(), chars.next()) {
+        let tag = match unicode_math_class::class(c) {
+            Some(
+                MathClass::Binary
+                | MathClass::Relation
+                | MathClass::Opening
+                | MathClass::Closing
+                | MathClass::Fence
+                | MathClass::Punctuation
+                | MathClass::Large
+                | MathClass::Unary
+                | MathClass::Vary,
+            ) => "mo",
+            _ => "mi",
+        };
+        write!(out, "<{tag}>{}</{tag}>", escape(text)).unwrap();
+    } else {
+        write!(out, "<mtext>{}</mtext>", escape(text)).unwrap();
+    }
+}
+
+/// Write horizontal spacing.
+fn write_space(out: &mut String, amount: Spacing) {
+    let Spacing::Rel(rel) = amount else { return };
+    if !rel.rel.is_zero() {
+        return;
+    }
+
+    let length = rel.abs;
+    if length.abs.is_zero() {
+        write!(out, "<mspace width=\"{}em\"/>", length.em.get()).unwrap();
+    } else if length.em.is_zero() {
+        write!(out, "<mspace width=\"{}pt\"/>", length.abs.to_pt()).unwrap();
+    }
+}
+
+/// Write a base with attachments.
+fn write_attach(out: &mut String, elem: &AttachElem, styles: StyleChain) {
+    let base = elem.base();
+    let limits = base.is::<LimitsElem>()
+        || (!base.is::<ScriptsElem>()
+            && (base.to::<OpElem>().map_or(false, |op| op.limits(styles))
+                || base.to::<TextElem>().map_or(false, |text| {
+                    let mut chars = text.text().chars();
+                    matches!(
+                        (chars.next(), chars.next()),
+                        (Some(c), None) if LIMITS.contains(&c)
+                    )
+                })));
+
+    let (t, b) = (elem.t(styles), elem.b(styles));
+    let (over, under) = if limits { (t, b) } else { (None, None) };
+    let sup = elem.tr(styles).or(if limits { None } else { elem.t(styles) });
+    let sub = elem.br(styles).or(if limits { None } else { elem.b(styles) });
+    let (tl, bl) = (elem.tl(styles), elem.bl(styles));
+
+    // Write the base with its limits.
+    let mut core = String::new();
+    match (&under, &over) {
+        (Some(under), Some(over)) => {
+            core.push_str("<munderover>");
+            row(&mut core, &base, styles);
+            row(&mut core, under, styles);
+            row(&mut core, over, styles);
+            core.push_str("</munderover>");
+        }
+        (Some(under), None) => {
+            core.push_str("<munder>");
+            row(&mut core, &base, styles);
+            row(&mut core, under, styles);
+            core.push_str("</munder>");
+        }
+        (None, Some(over)) => {
+            core.push_str("<mover>");
+            row(&mut core, &base, styles);
+            row(&mut core, over, styles);
+            core.push_str("</mover>");
+        }
+        (None, None) => row(&mut core, &base, styles),
+    }
+
+    // Write the scripts around it.
+    if tl.is_some() || bl.is_some() {
+        out.push_str("<mmultiscripts>");
+        out.push_str(&core);
+        row_or_none(out, sub.as_ref(), styles);
+        row_or_none(out, sup.as_ref(), styles);
+        out.push_str("<mprescripts/>");
+        row_or_none(out, bl.as_ref(), styles);
+        row_or_none(out, tl.as_ref(), styles);
+        out.push_str("</mmultiscripts>");
+        return;
+    }
+
+    match (&sub, &sup) {
+        (Some(sub), Some(sup)) => {
+            out.push_str("<msubsup>");
+            out.push_str(&core);
+            row(out, sub, styles);
+            row(out, sup, styles);
+            out.push_str("</msubsup>");
+        }
+        (Some(sub), None) => {
+            out.push_str("<msub>");
+            out.push_str(&core);
+            row(out, sub, styles);
+            out.push_str("</msub>");
+        }
+        (None, Some(sup)) => {
+            out.push_str("<msup>");
+            out.push_str(&core);
+            row(out, sup, styles);
+            out.push_str("</msup>");
+        }
+        (None, None) => out.push_str(&core),
+    }
+}
+
+/// Write rows of cells as a table with optional delimiters.
+fn write_table(
+    out: &mut String,
+    rows: &[Vec<Content>],
+    open: Option<char>,
+    close: Option<char>,
+    styles: StyleChain,
+) {
+    out.push_str("<mrow>");
+    if let Some(open) = open {
+        write!(out, "<mo>{}</mo>", escape(&open.to_string())).unwrap();
+    }
+
+    out.push_str("<mtable>");
+    for cells in rows {
+        out.push_str("<mtr>");
+        for cell in cells {
+            out.push_str("<mtd>");
+            write_content(out, cell, styles);
+            out.push_str("</mtd>");
+        }
+        out.push_str("</mtr>");
+    }
+    out.push_str("</mtable>");
+
+    if let Some(close) = close {
+        write!(out, "<mo>{}</mo>", escape(&close.to_string())).unwrap();
+    }
+    out.push_str("</mrow>");
+}
+
+/// Write a body with a line or brace below it and an optional annotation.
+fn write_under(
+    out: &mut String,
+    body: &Content,
+    c: char,
+    annotation: Option<Content>,
+    styles: StyleChain,
+) {
+    if annotation.is_some() {
+        out.push_str("<munder>");
+    }
+    out.push_str("<munder accentunder=\"true\">");
+    row(out, body, styles);
+    write!(out, "<mo>{c}</mo></munder>").unwrap();
+    if let Some(annotation) = annotation {
+        row(out, &annotation, styles);
+        out.push_str("</munder>");
+    }
+}
+
+/// Write a body with a line or brace above it and an optional annotation.
+fn write_over(
+    out: &mut String,
+    body: &Content,
+    c: char,
+    annotation: Option<Content>,
+    styles: StyleChain,
+) {
+    if annotation.is_some() {
+        out.push_str("<mover>");
+    }
+    out.push_str("<mover accent=\"true\">");
+    row(out, body, styles);
+    write!(out, "<mo>{c}</mo></mover>").unwrap();
+    if let Some(annotation) = annotation {
+        row(out, &annotation, styles);
+        out.push_str("</mover>");
+    }
+}
+
+/// Write content with a math style.
+fn write_style(out: &mut String, elem: &MathStyleElem, styles: StyleChain) {
+    let bold = elem.bold(styles).unwrap_or(false);
+    let italic = elem.italic(styles);
+    let variant = match (elem.variant(styles), bold, italic) {
+        (Some(MathVariant::Sans), true, Some(true)) => Some("sans-serif-bold-italic"),
+        (Some(MathVariant::Sans), true, _) => Some("bold-sans-serif"),
+        (Some(MathVariant::Sans), false, Some(true)) => Some("sans-serif-italic"),
+        (Some(MathVariant::Sans), false, _) => Some("sans-serif"),
+        (Some(MathVariant::Cal), true, _) => Some("bold-script"),
+        (Some(MathVariant::Cal), false, _) => Some("script"),
+        (Some(MathVariant::Frak), true, _) => Some("bold-fraktur"),
+        (Some(MathVariant::Frak), false, _) => Some("fraktur"),
+        (Some(MathVariant::Mono), _, _) => Some("monospace"),
+        (Some(MathVariant::Bb), _, _) => Some("double-struck"),
+        (_, true, Some(true)) => Some("bold-italic"),
+        (_, true, _) => Some("bold"),
+        (_, false, Some(true)) => Some("italic"),
+        (_, false, Some(false)) => Some("normal"),
+        (_, false, None) => None,
+    };
+
+    match variant {
+        Some(variant) => {
+            write!(out, "<mstyle mathvariant=\"{variant}\">").unwrap();
+            write_content(out, &elem.body(), styles);
+            out.push_str("</mstyle>");
+        }
+        None => write_content(out, &elem.body(), styles),
+    }
+}
+
+/// Escape text for use in MathML.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::StrongElem;
+
+    fn text(text: &str) -> Content {
+        TextElem::packed(text)
+    }
+
+    fn export(body: impl Element) -> String {
+        let equation = EquationElem::new(body.pack());
+        mathml(&equation, StyleChain::default())
+    }
+
+    #[test]
+    fn test_mathml_frac() {
+        assert_eq!(
+            export(FracElem::new(text("x"), text("2"))),
+            "<math display=\"inline\"><mrow><mfrac>\
+             <mrow><mi>x</mi></mrow><mrow><mn>2</mn></mrow>\
+             </mfrac></mrow></math>",
+        );
+    }
+
+    #[test]
+    fn test_mathml_attach() {
+        // Plain bases take scripts.
+        assert_eq!(
+            export(
+                AttachElem::new(text("x"))
+                    .with_t(Some(text("2")))
+                    .with_b(Some(text("i")))
+            ),
+            "<math display=\"inline\"><mrow><msubsup>\
+             <mrow><mi>x</mi></mrow><mrow><mi>i</mi></mrow><mrow><mn>2</mn></mrow>\
+             </msubsup></mrow></math>",
+        );
+
+        // Large operators take limits.
+        assert_eq!(
+            export(AttachElem::new(text("∑")).with_t(Some(text("n")))),
+            "<math display=\"inline\"><mrow><mover>\
+             <mrow><mo>∑</mo></mrow><mrow><mi>n</mi></mrow>\
+             </mover></mrow></math>",
+        );
+
+        // Unless they are forced to take scripts.
+        let base = ScriptsElem::new(text("∑")).pack();
+        assert_eq!(
+            export(AttachElem::new(base).with_t(Some(text("n")))),
+            "<math display=\"inline\"><mrow><msup>\
+             <mrow><mo>∑</mo></mrow><mrow><mi>n</mi></mrow>\
+             </msup></mrow></math>",
+        );
+
+        // And plain bases can be forced to take limits.
+        let base = LimitsElem::new(text("x")).pack();
+        assert_eq!(
+            export(AttachElem::new(base).with_b(Some(text("i")))),
+            "<math display=\"inline\"><mrow><munder>\
+             <mrow><mi>x</mi></mrow><mrow><mi>i</mi></mrow>\
+             </munder></mrow></math>",
+        );
+    }
+
+    #[test]
+    fn test_mathml_root() {
+        assert_eq!(
+            export(RootElem::new(text("x"))),
+            "<math display=\"inline\"><mrow><msqrt><mi>x</mi></msqrt></mrow></math>",
+        );
+        assert_eq!(
+            export(RootElem::new(text("x")).with_index(Some(text("3")))),
+            "<math display=\"inline\"><mrow><mroot>\
+             <mrow><mi>x</mi></mrow><mrow><mn>3</mn></mrow>\
+             </mroot></mrow></math>",
+        );
+    }
+
+    #[test]
+    fn test_mathml_mat() {
+        let rows = vec![vec![text("1"), text("0")], vec![text("0"), text("1")]];
+        assert_eq!(
+            export(MatElem::new(rows)),
+            "<math display=\"inline\"><mrow><mrow><mo>(</mo><mtable>\
+             <mtr><mtd><mn>1</mn></mtd><mtd><mn>0</mn></mtd></mtr>\
+             <mtr><mtd><mn>0</mn></mtd><mtd><mn>1</mn></mtd></mtr>\
+             </mtable><mo>)</mo></mrow></mrow></math>",
+        );
+    }
+
+    #[test]
+    fn test_mathml_fallback() {
+        let equation =
+            EquationElem::new(StrongElem::new(text("a<b")).pack()).with_block(true);
+        assert_eq!(
+            mathml(&equation, StyleChain::default()),
+            "<math display=\"block\"><mrow><mtext>a&lt;b</mtext></mrow></math>",
+        );
+    }
+}
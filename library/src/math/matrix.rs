@@ -1,4 +1,5 @@
 use super::*;
+use crate::layout::Celled;
 
 const ROW_GAP: Em = Em::new(0.5);
 const COL_GAP: Em = Em::new(0.5);
@@ -87,10 +88,16 @@ pub struct MatElem {
     /// #let matrix = math.mat(..data)
     /// $ v := matrix $
     /// ```
+    ///
+    /// All rows must have the same number of cells. An empty cell still takes
+    /// up space in its row and column.
+    ///
+    /// ```example
+    /// $ mat(1, 2, 3; 4, "", 6) $
+    /// ```
     #[variadic]
     #[parse(
         let mut rows = vec![];
-        let mut width = 0;
 
         let values = args.all::<Spanned<Value>>()?;
         if values.iter().all(|spanned| matches!(spanned.v, Value::Content(_))) {
@@ -98,28 +105,46 @@ pub struct MatElem {
         } else {
             for Spanned { v, span } in values {
                 let array = v.cast::<Array>().at(span)?;
-                let row: Vec<_> = array.into_iter().map(Value::display).collect();
-                width = width.max(row.len());
+                let row: Vec<Content> = array.into_iter().map(Value::display).collect();
+                if let Some(first) = rows.first().map(Vec::len) {
+                    if row.len() != first {
+                        bail!(
+                            span,
+                            "all rows of a matrix must have the same number of cells \
+                             (expected {first}, found {})",
+                            row.len()
+                        );
+                    }
+                }
                 rows.push(row);
             }
         }
 
-        for row in &mut rows {
-            if row.len() < width {
-                row.resize(width, Content::empty());
-            }
-        }
-
         rows
     )]
     pub rows: Vec<Vec<Content>>,
+
+    /// How to align the cells.
+    ///
+    /// This can either be a single alignment, an array of alignments
+    /// (corresponding to each column) or a function that returns an alignment.
+    /// The function is passed the cell's column and row index, starting at
+    /// zero. If set to `{auto}`, the cells are centered. Cells with alignment
+    /// points (`&`) are aligned at these points instead.
+    ///
+    /// ```example
+    /// #set math.mat(align: (left, right))
+    /// $ mat(1, 20; 300, 4) $
+    /// ```
+    pub align: Celled<Smart<HorizontalAlign>>,
 }
 
 impl LayoutMath for MatElem {
     #[tracing::instrument(skip(ctx))]
     fn layout_math(&self, ctx: &mut MathContext) -> SourceResult<()> {
         let delim = self.delim(ctx.styles());
-        let frame = layout_mat_body(ctx, &self.rows())?;
+        let align = self.align(ctx.styles());
+        let frame = layout_mat_body(ctx, &self.rows(), &align)?;
         layout_delimiters(
             ctx,
             frame,
@@ -193,7 +218,7 @@ pub enum Delimiter {
 
 impl Delimiter {
     /// The delimiter's opening character.
-    fn open(self) -> char {
+    pub(super) fn open(self) -> char {
         match self {
             Self::Paren => '(',
             Self::Bracket => '[',
@@ -204,7 +229,7 @@ impl Delimiter {
     }
 
     /// The delimiter's closing character.
-    fn close(self) -> char {
+    pub(super) fn close(self) -> char {
         match self {
             Self::Paren => ')',
             Self::Bracket => ']',
@@ -232,7 +257,11 @@ fn layout_vec_body(
 }
 
 /// Layout the inner contents of a matrix.
-fn layout_mat_body(ctx: &mut MathContext, rows: &[Vec<Content>]) -> SourceResult<Frame> {
+fn layout_mat_body(
+    ctx: &mut MathContext,
+    rows: &[Vec<Content>],
+    align: &Celled<Smart<HorizontalAlign>>,
+) -> SourceResult<Frame> {
     let row_gap = ROW_GAP.scaled(ctx);
     let col_gap = COL_GAP.scaled(ctx);
 
@@ -253,6 +282,15 @@ fn layout_mat_body(ctx: &mut MathContext, rows: &[Vec<Content>]) -> SourceResult
             descent.set_max(cell.descent());
             col.push(cell);
         }
+
+        // A row of empty cells keeps the height of a parenthesis, like
+        // TeX's math strut, so that it doesn't collapse.
+        if ascent.is_zero() && descent.is_zero() {
+            let strut: MathFragment =
+                GlyphFragment::new(ctx, '(', Span::detached()).into();
+            *ascent = strut.ascent();
+            *descent = strut.descent();
+        }
     }
     ctx.unstyle();
 
@@ -261,13 +299,21 @@ fn layout_mat_body(ctx: &mut MathContext, rows: &[Vec<Content>]) -> SourceResult
         heights.iter().map(|&(a, b)| a + b).sum::<Abs>() + row_gap * (nrows - 1) as f64,
     ));
     let mut x = Abs::zero();
-    for col in cols {
+    for (i, col) in cols.into_iter().enumerate() {
         let AlignmentResult { points, width: rcol } = alignments(&col);
         let mut y = Abs::zero();
-        for (cell, &(ascent, descent)) in col.into_iter().zip(&heights) {
-            let cell = cell.into_aligned_frame(ctx, &points, Align::Center);
+        for (j, (cell, &(ascent, descent))) in col.into_iter().zip(&heights).enumerate() {
+            let align = align
+                .resolve(ctx.vt, i, j)?
+                .map(|align| align.0.resolve(ctx.styles()))
+                .unwrap_or(Align::Center);
+            let cell = cell.into_aligned_frame(ctx, &points, align);
             let pos = Point::new(
-                if points.is_empty() { x + (rcol - cell.width()) / 2.0 } else { x },
+                if points.is_empty() {
+                    x + align.position(rcol - cell.width())
+                } else {
+                    x
+                },
                 y + ascent - cell.ascent(),
             );
             frame.push_frame(pos, cell);
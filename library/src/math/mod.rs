@@ -9,6 +9,7 @@ mod cancel;
 mod delimited;
 mod frac;
 mod fragment;
+mod mathml;
 mod matrix;
 mod op;
 mod root;
@@ -24,6 +25,7 @@ pub use self::attach::*;
 pub use self::cancel::*;
 pub use self::delimited::*;
 pub use self::frac::*;
+pub use self::mathml::*;
 pub use self::matrix::*;
 pub use self::op::*;
 pub use self::root::*;
@@ -23,10 +23,26 @@ use super::*;
 #[element(LayoutMath)]
 pub struct OpElem {
     /// The operator's text.
+    ///
+    /// This can be a string or arbitrary content, like a styled name.
+    ///
+    /// ```example
+    /// $ op(bold("argmax"), limits: #true)_(x in X) f(x) $
+    /// ```
     #[required]
-    pub text: EcoString,
+    pub text: Content,
 
-    /// Whether the operator should force attachments to display as limits.
+    /// Whether the operator should display attachments as limits.
+    ///
+    /// Like in TeX, limits are only placed above and below the operator in
+    /// display math. In inline math, they become scripts on the side. To
+    /// force limits in inline math, wrap the operator in
+    /// [`limits`]($func/math.limits).
+    ///
+    /// ```example
+    /// Inline: $op("sup", limits: #true)_x$
+    /// $ op("sup", limits: #true)_x $
+    /// ```
     #[default(false)]
     pub limits: bool,
 }
@@ -34,8 +50,7 @@ pub struct OpElem {
 impl LayoutMath for OpElem {
     #[tracing::instrument(skip(ctx))]
     fn layout_math(&self, ctx: &mut MathContext) -> SourceResult<()> {
-        let fragment =
-            ctx.layout_text(&TextElem::new(self.text()).spanned(self.span()))?;
+        let fragment = ctx.layout_fragment(&self.text().spanned(self.span()))?;
         ctx.push(
             FrameFragment::new(ctx, fragment.into_frame())
                 .with_class(MathClass::Large)
@@ -50,7 +65,7 @@ macro_rules! ops {
         pub(super) fn define(math: &mut Scope) {
             $(math.define(
                 stringify!($name),
-                OpElem::new(ops!(@name $name $(: $value)?).into())
+                OpElem::new(TextElem::packed(ops!(@name $name $(: $value)?)))
                     .with_limits(ops!(@limit $($tts)*))
                     .pack()
             );)*
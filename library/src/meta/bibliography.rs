@@ -361,13 +361,28 @@ impl Show for CiteElem {
 
         let works = Works::new(vt).at(self.span())?;
         let location = self.0.location().unwrap();
-        works
-            .citations
-            .get(&location)
-            .cloned()
-            .flatten()
-            .ok_or("bibliography does not contain this key")
-            .at(self.span())
+        if let Some(citation) = works.citations.get(&location).cloned().flatten() {
+            return Ok(citation);
+        }
+
+        let missing: Vec<_> = self
+            .keys()
+            .into_iter()
+            .filter(|key| !BibliographyElem::has(vt, key))
+            .map(|key| eco_format!("`{key}`"))
+            .collect();
+
+        match missing.as_slice() {
+            [] => bail!(self.span(), "bibliography does not contain this key"),
+            [key] => bail!(self.span(), "bibliography does not contain the key {key}"),
+            keys => {
+                bail!(
+                    self.span(),
+                    "bibliography does not contain the keys {}",
+                    keys.join(", ")
+                )
+            }
+        }
     }
 }
 
@@ -1,3 +1,5 @@
+use typst::eval::Datetime;
+
 use crate::layout::{LayoutRoot, PageElem};
 use crate::prelude::*;
 
@@ -29,6 +31,20 @@ pub struct DocumentElem {
     /// The document's authors.
     pub author: Author,
 
+    /// The document's keywords. These are embedded into the PDF's metadata
+    /// and help search engines and document management systems.
+    pub keywords: Keywords,
+
+    /// The document's creation date. It is embedded into the PDF's metadata.
+    ///
+    /// ```example
+    /// #set document(
+    ///   keywords: ("typesetting", "report"),
+    ///   date: datetime(year: 2023, month: 5, day: 17),
+    /// )
+    /// ```
+    pub date: Option<Datetime>,
+
     /// The page runs.
     #[internal]
     #[variadic]
@@ -70,6 +86,8 @@ impl LayoutRoot for DocumentElem {
             pages,
             title: self.title(styles),
             author: self.author(styles).0,
+            keywords: self.keywords(styles).0,
+            date: self.date(styles),
         })
     }
 }
@@ -87,3 +105,17 @@ cast_from_value! {
 cast_to_value! {
     v: Author => v.0.into()
 }
+
+/// A list of keywords.
+#[derive(Debug, Default, Clone, Hash)]
+pub struct Keywords(Vec<EcoString>);
+
+cast_from_value! {
+    Keywords,
+    v: EcoString => Self(vec![v]),
+    v: Array => Self(v.into_iter().map(Value::cast).collect::<StrResult<_>>()?),
+}
+
+cast_to_value! {
+    v: Keywords => v.0.into()
+}
@@ -86,6 +86,18 @@ pub struct FigureElem {
     /// The figure's caption.
     pub caption: Option<Content>,
 
+    /// The caption's position. Either `{top}` or `{bottom}`.
+    ///
+    /// ```example
+    /// #figure(
+    ///   table(columns: 2)[A][B],
+    ///   caption: [A table.],
+    ///   caption-pos: top,
+    /// )
+    /// ```
+    #[default(CaptionPos::Bottom)]
+    pub caption_pos: CaptionPos,
+
     /// The kind of the figure this is.
     ///
     /// If set to `{auto}`, the figure will try to automatically determine its
@@ -222,6 +234,7 @@ impl Synthesize for FigureElem {
         )));
 
         self.push_caption(self.caption(styles));
+        self.push_caption_pos(self.caption_pos(styles));
         self.push_kind(Smart::Custom(kind));
         self.push_supplement(Smart::Custom(Some(Supplement::Content(supplement))));
         self.push_numbering(numbering);
@@ -239,8 +252,12 @@ impl Show for FigureElem {
 
         // Build the caption, if any.
         if let Some(caption) = self.full_caption(vt)? {
-            realized += VElem::weak(self.gap(styles).into()).pack();
-            realized += caption;
+            let gap = VElem::weak(self.gap(styles).into()).pack();
+            realized = if self.caption_pos(styles) == CaptionPos::Top {
+                caption + gap + realized
+            } else {
+                realized + gap + caption
+            };
         }
 
         // Wrap the contents in a block.
@@ -348,6 +365,31 @@ cast_to_value! {
     }
 }
 
+/// The `caption-pos` parameter of a [`FigureElem`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CaptionPos {
+    /// The caption is above the body.
+    Top,
+    /// The caption is below the body.
+    Bottom,
+}
+
+cast_from_value! {
+    CaptionPos,
+    align: VerticalAlign => match align.0 {
+        GenAlign::Specific(Align::Top) => Self::Top,
+        GenAlign::Specific(Align::Bottom) => Self::Bottom,
+        _ => Err("caption position must be top or bottom")?,
+    },
+}
+
+cast_to_value! {
+    v: CaptionPos => match v {
+        CaptionPos::Top => GenAlign::Specific(Align::Top).into(),
+        CaptionPos::Bottom => GenAlign::Specific(Align::Bottom).into(),
+    }
+}
+
 /// An element that can be auto-detected in a figure.
 ///
 /// This trait is used to determine the type of a figure.
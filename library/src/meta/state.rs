@@ -165,7 +165,8 @@ use crate::prelude::*;
 /// this. We initialize our state with `1` and then update it to its own final
 /// value plus 1. So it should be `2`, but then its final value is `2`, so it
 /// should be `3`, and so on. This example display `4` because Typst simply
-/// gives up after a few attempts.
+/// gives up after a few attempts and emits a warning that the layout did not
+/// converge.
 ///
 /// ```example
 /// #let s = state("x", 1)
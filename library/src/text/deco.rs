@@ -1,7 +1,7 @@
 use kurbo::{BezPath, Line, ParamCurve};
 use ttf_parser::{GlyphId, OutlineBuilder};
 
-use super::TextElem;
+use super::{ShapedText, TextElem};
 use crate::prelude::*;
 
 /// Underline text.
@@ -232,6 +232,69 @@ impl Show for StrikeElem {
     }
 }
 
+/// Highlight text with a background color.
+///
+/// Like a marker pen, the highlight follows the text across line breaks: Each
+/// line gets its own background that spans from the typographic ascender to
+/// the descender of the font.
+///
+/// ## Example { #example }
+/// ```example
+/// This is #highlight[important].
+/// ```
+///
+/// Display: Highlight
+/// Category: text
+#[element(Show)]
+pub struct HighlightElem {
+    /// The color to highlight the text with.
+    ///
+    /// ```example
+    /// This is #highlight(fill: blue.lighten(60%))[with blue].
+    /// ```
+    #[default(Color::Rgba(RgbaColor::new(0xFF, 0xFD, 0x11, 0xA1)).into())]
+    pub fill: Paint,
+
+    /// Amount by which the background extends beyond the text on the left and
+    /// right.
+    ///
+    /// ```example
+    /// A #highlight(extent: 4pt)[wider] highlight.
+    /// ```
+    #[resolve]
+    pub extent: Length,
+
+    /// How much to round the background's corners. See the [rectangle's
+    /// documentation]($func/rect.radius) for more details.
+    ///
+    /// ```example
+    /// A #highlight(radius: 3pt, extent: 2pt)[rounded] highlight.
+    /// ```
+    #[resolve]
+    #[fold]
+    pub radius: Corners<Option<Rel<Length>>>,
+
+    /// The content to highlight.
+    #[required]
+    pub body: Content,
+}
+
+impl Show for HighlightElem {
+    #[tracing::instrument(name = "HighlightElem::show", skip_all)]
+    fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        Ok(self.body().styled(TextElem::set_deco(Decoration {
+            line: DecoLine::Highlight {
+                fill: self.fill(styles),
+                radius: self.radius(styles).map(|side| side.unwrap_or_default()),
+            },
+            stroke: PartialStroke::default(),
+            offset: Smart::Auto,
+            extent: self.extent(styles),
+            evade: false,
+        })))
+    }
+}
+
 /// Defines a line that is positioned over, under or on top of text.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Decoration {
@@ -256,11 +319,108 @@ cast_from_value! {
 }
 
 /// A kind of decorative line.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum DecoLine {
     Underline,
     Strikethrough,
     Overline,
+    Highlight { fill: Paint, radius: Corners<Rel<Abs>> },
+}
+
+/// Add the highlight backgrounds of a line of text.
+///
+/// The runs are given in visual order with their horizontal extent in the
+/// line. Adjacent runs with the same highlight share one background, so that
+/// it isn't interrupted where the font or the style changes. A `None` run
+/// separates text runs that aren't adjacent.
+pub(crate) fn highlight(frame: &mut Frame, runs: &[Option<(Abs, Abs, &ShapedText)>]) {
+    let mut open: Vec<Background> = vec![];
+    let mut done: Vec<Background> = vec![];
+    let mut count = 0;
+
+    for run in runs {
+        let Some((start, end, text)) = run else {
+            done.append(&mut open);
+            continue;
+        };
+
+        let decos: Vec<_> = TextElem::deco_in(text.styles)
+            .into_iter()
+            .filter(|deco| matches!(deco.line, DecoLine::Highlight { .. }))
+            .collect();
+
+        let mut top = Abs::zero();
+        let mut bottom = Abs::zero();
+        for glyph in text.glyphs.iter() {
+            let metrics = glyph.font.metrics();
+            top.set_max(metrics.ascender.at(text.size));
+            bottom.set_max(-metrics.descender.at(text.size));
+        }
+
+        // Close the backgrounds that don't continue into this run and extend
+        // the others.
+        for mut bg in std::mem::take(&mut open) {
+            if decos.contains(&bg.deco) {
+                bg.end = *end;
+                bg.top.set_max(top);
+                bg.bottom.set_max(bottom);
+                open.push(bg);
+            } else {
+                done.push(bg);
+            }
+        }
+
+        for deco in decos {
+            if !open.iter().any(|bg| bg.deco == deco) {
+                open.push(Background {
+                    deco,
+                    order: count,
+                    start: *start,
+                    end: *end,
+                    top,
+                    bottom,
+                });
+                count += 1;
+            }
+        }
+    }
+
+    // Backgrounds that were opened later are nested and go on top.
+    done.append(&mut open);
+    done.sort_by_key(|bg| bg.order);
+
+    let baseline = frame.baseline();
+    let mut shapes = vec![];
+    for bg in done {
+        let DecoLine::Highlight { fill, radius } = bg.deco.line else { continue };
+        let size =
+            Size::new(bg.end - bg.start + 2.0 * bg.deco.extent, bg.top + bg.bottom);
+        let radius = radius.map(|side| side.relative_to(size.x.min(size.y) / 2.0));
+        let origin = Point::new(bg.start - bg.deco.extent, baseline - bg.top);
+        shapes.extend(
+            rounded_rect(size, radius, Some(fill), Sides::splat(None))
+                .into_iter()
+                .map(|shape| (origin, FrameItem::Shape(shape, Span::detached()))),
+        );
+    }
+
+    frame.prepend_multiple(shapes);
+}
+
+/// A highlight background spanning one or more runs of a line.
+struct Background {
+    /// The highlight decoration.
+    deco: Decoration,
+    /// In which order the background was started.
+    order: usize,
+    /// The horizontal start of the background's first run.
+    start: Abs,
+    /// The horizontal end of the background's last run.
+    end: Abs,
+    /// The maximal ascender of the covered runs.
+    top: Abs,
+    /// The maximal descender of the covered runs.
+    bottom: Abs,
 }
 
 /// Add line decorations to a single run of shaped text.
@@ -273,10 +433,12 @@ pub(super) fn decorate(
     width: Abs,
 ) {
     let font_metrics = text.font.metrics();
-    let metrics = match deco.line {
+    let metrics = match &deco.line {
         DecoLine::Strikethrough => font_metrics.strikethrough,
         DecoLine::Overline => font_metrics.overline,
         DecoLine::Underline => font_metrics.underline,
+        // Highlights are drawn for whole lines by `highlight`.
+        DecoLine::Highlight { .. } => return,
     };
 
     let offset = deco.offset.unwrap_or(-metrics.position.at(text.size)) - shift;
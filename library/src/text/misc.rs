@@ -1,4 +1,4 @@
-use super::TextElem;
+use super::{first_font, has_substitutions, search_text, TextElem, TextSize};
 use crate::prelude::*;
 
 /// A text space.
@@ -274,11 +274,11 @@ impl Case {
 
 /// Display text in small capitals.
 ///
-/// _Note:_ This enables the OpenType `smcp` feature for the font. Not all fonts
-/// support this feature. Sometimes smallcaps are part of a dedicated font and
-/// sometimes they are not available at all. In the future, this function will
-/// support selecting a dedicated smallcaps font as well as synthesizing
-/// smallcaps from normal letters, but this is not yet implemented.
+/// This enables the OpenType `smcp` feature for the font. If the body is plain
+/// text and the font does not support the feature, small capitals are
+/// synthesized instead: Lowercase letters are replaced by uppercase ones
+/// shrunk to the font's x-height, while letters that already are uppercase
+/// stay at full size.
 ///
 /// ## Example { #example }
 /// ```example
@@ -297,13 +297,51 @@ impl Case {
 ///
 /// Display: Small Capitals
 /// Category: text
-/// Returns: content
-#[func]
-pub fn smallcaps(
+#[element(Show)]
+pub struct SmallcapsElem {
     /// The text to display to small capitals.
-    body: Content,
-) -> Value {
-    Value::Content(body.styled(TextElem::set_smallcaps(true)))
+    #[required]
+    pub body: Content,
+}
+
+impl Show for SmallcapsElem {
+    #[tracing::instrument(name = "SmallcapsElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let body = self.body();
+        Ok(synthesize_smallcaps(vt, &body, styles)
+            .unwrap_or_else(|| body.styled(TextElem::set_smallcaps(true))))
+    }
+}
+
+/// Synthesize small capitals for plain text if the font lacks dedicated
+/// glyphs for its lowercase letters.
+fn synthesize_smallcaps(vt: &Vt, body: &Content, styles: StyleChain) -> Option<Content> {
+    let text = search_text(body)?;
+    let lower: String = text.chars().filter(|c| c.is_lowercase()).collect();
+    let font = first_font(vt, styles)?;
+    if lower.is_empty() || has_substitutions(&font, &lower, b"smcp") {
+        return None;
+    }
+
+    let metrics = font.metrics();
+    let size = TextSize(Em::new(metrics.x_height / metrics.cap_height).into());
+    let mut seq = vec![];
+    let mut rest = text.as_str();
+    while let Some(c) = rest.chars().next() {
+        let lowercase = c.is_lowercase();
+        let len = rest
+            .find(|c: char| c.is_lowercase() != lowercase)
+            .unwrap_or(rest.len());
+        let (run, tail) = rest.split_at(len);
+        seq.push(if lowercase {
+            TextElem::packed(run.to_uppercase()).styled(TextElem::set_size(size))
+        } else {
+            TextElem::packed(run)
+        });
+        rest = tail;
+    }
+
+    Some(Content::sequence(seq))
 }
 
 /// Create blind text.
@@ -31,12 +31,13 @@ pub(super) fn define(global: &mut Scope) {
     global.define("emph", EmphElem::func());
     global.define("lower", lower);
     global.define("upper", upper);
-    global.define("smallcaps", smallcaps);
+    global.define("smallcaps", SmallcapsElem::func());
     global.define("sub", SubElem::func());
     global.define("super", SuperElem::func());
     global.define("underline", UnderlineElem::func());
     global.define("strike", StrikeElem::func());
     global.define("overline", OverlineElem::func());
+    global.define("highlight", HighlightElem::func());
     global.define("raw", RawElem::func());
     global.define("lorem", lorem);
 }
@@ -60,6 +61,10 @@ pub(super) fn define(global: &mut Scope) {
 /// Display: Text
 /// Category: text
 #[element(Construct, PlainText)]
+#[scope(
+    scope.define("measure-glyph", text_measure_glyph);
+    scope
+)]
 pub struct TextElem {
     /// A prioritized sequence of font families.
     ///
@@ -85,11 +90,10 @@ pub struct TextElem {
     /// contains no match. This lets Typst search through all available fonts
     /// for the most similar one that has the necessary glyphs.
     ///
-    /// _Note:_ Currently, there are no warnings when fallback is disabled and
-    /// no glyphs are found. Instead, your text shows up in the form of "tofus":
-    /// Small boxes that indicate the lack of an appropriate glyph. In the
-    /// future, you will be able to instruct Typst to issue warnings so you know
-    /// something is up.
+    /// When no font has a glyph for a character, your text shows up in the
+    /// form of "tofus": Small boxes that indicate the lack of an appropriate
+    /// glyph. Typst then also issues a warning listing the character and the
+    /// fonts that were tried, once per character.
     ///
     /// ```example
     /// #set text(font: "Inria Serif")
@@ -538,6 +542,72 @@ impl PlainText for TextElem {
     }
 }
 
+/// Measure the glyph of a character.
+///
+/// The glyph is taken from the first of the [font families]($func/text.font)
+/// that contains the character and measured at the current font size. This
+/// returns a dictionary with the following entries, all of type
+/// [`length`]($type/length):
+///
+/// - `advance`: How far the glyph moves the pen forward.
+/// - `ascent`: How far the glyph's font extends above the baseline.
+/// - `descent`: How far the glyph's font extends below the baseline.
+/// - `bbox`: The glyph's bounding box as a dictionary with the entries
+///   `x-min`, `y-min`, `x-max`, and `y-max`, measured from the glyph's origin
+///   on the baseline with `y` pointing upwards. This is `{none}` for glyphs
+///   without an outline, like a space.
+///
+/// If none of the fonts contain the character, the result is `{none}`.
+///
+/// ```example
+/// #style(styles => {
+///   let glyph = text.measure-glyph("g", styles)
+///   [The "g" advances by #glyph.advance.]
+/// })
+/// ```
+///
+/// Display: Measure Glyph
+/// Category: text
+/// Returns: dictionary or none
+#[func]
+pub fn text_measure_glyph(
+    /// The character whose glyph to measure.
+    character: char,
+    /// The styles with which to select the font.
+    styles: Styles,
+) -> Value {
+    let styles = StyleChain::new(&styles);
+    let world = vm.world();
+    let variant = variant(styles);
+    let found = TextElem::font_in(styles).into_iter().find_map(|family| {
+        let id = world.book().select(family.as_str(), variant)?;
+        let font = world.font(id)?;
+        let glyph = font.ttf().glyph_index(character)?;
+        Some((font, glyph))
+    });
+
+    let Some((font, glyph)) = found else { return Ok(Value::None) };
+    let size = TextElem::size_in(styles);
+    let at = |units: i16| font.to_em(units).at(size);
+    let metrics = font.metrics();
+    let bbox = font.ttf().glyph_bounding_box(glyph).map(|rect| {
+        Value::from(dict! {
+            "x-min" => at(rect.x_min),
+            "y-min" => at(rect.y_min),
+            "x-max" => at(rect.x_max),
+            "y-max" => at(rect.y_max),
+        })
+    });
+
+    dict! {
+        "advance" => font.advance(glyph.0).unwrap_or_default().at(size),
+        "ascent" => metrics.ascender.at(size),
+        "descent" => (-metrics.descender).at(size),
+        "bbox" => bbox,
+    }
+    .into()
+}
+
 /// A lowercased font family like "arial".
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct FontFamily(EcoString);
@@ -758,21 +828,32 @@ cast_from_value! {
     FontFeatures,
     values: Array => Self(values
         .into_iter()
-        .map(|v| {
-            let tag = v.cast::<EcoString>()?;
-            Ok((Tag::from_bytes_lossy(tag.as_bytes()), 1))
-        })
+        .map(|v| v.cast::<EcoString>())
+        .collect::<StrResult<Vec<_>>>()?
+        .iter()
+        .map(|tag| Ok((parse_feature_tag(tag)?, 1)))
         .collect::<StrResult<_>>()?),
     values: Dict => Self(values
         .into_iter()
         .map(|(k, v)| {
             let num = v.cast::<u32>()?;
-            let tag = Tag::from_bytes_lossy(k.as_bytes());
+            let tag = parse_feature_tag(&k)?;
             Ok((tag, num))
         })
         .collect::<StrResult<_>>()?),
 }
 
+/// Parse an OpenType feature tag, which must consist of four ASCII characters.
+fn parse_feature_tag(tag: &str) -> StrResult<Tag> {
+    if tag.len() != 4 || !tag.bytes().all(|b| matches!(b, b' '..=b'~')) {
+        return Err(eco_format!(
+            "feature tag must be four ASCII characters, found {tag:?}"
+        ));
+    }
+
+    Ok(Tag::from_bytes_lossy(tag.as_bytes()))
+}
+
 cast_to_value! {
     v: FontFeatures => Value::Dict(
         v.0.into_iter()
@@ -5,7 +5,7 @@ use typst::syntax::{self, LinkedNode};
 use super::{
     FontFamily, FontList, Hyphenate, LinebreakElem, SmartQuoteElem, TextElem, TextSize,
 };
-use crate::layout::BlockElem;
+use crate::layout::{BlockElem, GridElem, ParElem, Sizing, TrackSizings};
 use crate::meta::{Figurable, LocalName};
 use crate::prelude::*;
 
@@ -123,6 +123,45 @@ pub struct RawElem {
     /// ````
     #[default(HorizontalAlign(GenAlign::Start))]
     pub align: HorizontalAlign,
+
+    /// The colors to highlight with.
+    ///
+    /// A theme is a dictionary that maps [scope
+    /// selectors](https://macromates.com/manual/en/scope_selectors) to colors.
+    /// Text whose scope no selector matches keeps the text color. If set to
+    /// `{auto}`, uses Typst's built-in light theme.
+    ///
+    /// ````example
+    /// #set raw(theme: (
+    ///   "comment": luma(150),
+    ///   "keyword, storage": red,
+    ///   "string": blue,
+    /// ))
+    ///
+    /// ```rust
+    /// // Say hello.
+    /// let greeting = "Hello";
+    /// ```
+    /// ````
+    pub theme: Smart<RawTheme>,
+
+    /// Whether to number the lines of a raw block.
+    ///
+    /// The numbers are right-aligned in a gutter that is as wide as the
+    /// largest number. A line that is too long and wraps keeps a single
+    /// number. This option is ignored if this is not a raw block.
+    ///
+    /// ````example
+    /// #set raw(line-numbers: true)
+    ///
+    /// ```rust
+    /// fn main() {
+    ///     println!("Hello World!");
+    /// }
+    /// ```
+    /// ````
+    #[default(false)]
+    pub line_numbers: bool,
 }
 
 impl RawElem {
@@ -145,6 +184,7 @@ impl RawElem {
 impl Synthesize for RawElem {
     fn synthesize(&mut self, _vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
         self.push_lang(self.lang(styles));
+        self.push_theme(self.theme(styles));
         Ok(())
     }
 }
@@ -154,50 +194,72 @@ impl Show for RawElem {
     fn show(&self, _: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
         let text = self.text();
         let lang = self.lang(styles).as_ref().map(|s| s.to_lowercase());
-        let foreground = THEME
+        let custom = self.theme(styles).as_custom().map(|theme| theme.to_syntect());
+        let theme = custom.as_ref().unwrap_or(&THEME);
+        let foreground = theme
             .settings
             .foreground
             .map(to_typst)
             .map_or(Color::BLACK, Color::from);
 
-        let mut realized = if matches!(lang.as_deref(), Some("typ" | "typst" | "typc")) {
+        let lines = if matches!(lang.as_deref(), Some("typ" | "typst" | "typc")) {
             let root = match lang.as_deref() {
                 Some("typc") => syntax::parse_code(&text),
                 _ => syntax::parse(&text),
             };
 
-            let mut seq = vec![];
-            let highlighter = synt::Highlighter::new(&THEME);
+            let mut lines = vec![vec![]];
+            let highlighter = synt::Highlighter::new(theme);
             highlight_themed(
                 &LinkedNode::new(&root),
                 vec![],
                 &highlighter,
                 &mut |node, style| {
-                    seq.push(styled(&text[node.range()], foreground.into(), style));
+                    for (i, piece) in text[node.range()].split('\n').enumerate() {
+                        if i != 0 {
+                            lines.push(vec![]);
+                        }
+                        if !piece.is_empty() {
+                            let line = lines.last_mut().unwrap();
+                            line.push(styled(piece, foreground.into(), style));
+                        }
+                    }
                 },
             );
 
-            Content::sequence(seq)
+            lines.into_iter().map(Content::sequence).collect()
         } else if let Some(syntax) =
             lang.and_then(|token| SYNTAXES.find_syntax_by_token(&token))
         {
+            let mut highlighter = syntect::easy::HighlightLines::new(syntax, theme);
+            text.lines()
+                .map(|line| {
+                    Content::sequence(
+                        highlighter
+                            .highlight_line(line, &SYNTAXES)
+                            .into_iter()
+                            .flatten()
+                            .map(|(style, piece)| {
+                                styled(piece, foreground.into(), style)
+                            }),
+                    )
+                })
+                .collect()
+        } else {
+            text.split('\n').map(TextElem::packed).collect::<Vec<_>>()
+        };
+
+        let mut realized = if self.block(styles) && self.line_numbers(styles) {
+            numbered(lines, styles)
+        } else {
             let mut seq = vec![];
-            let mut highlighter = syntect::easy::HighlightLines::new(syntax, &THEME);
-            for (i, line) in text.lines().enumerate() {
+            for (i, line) in lines.into_iter().enumerate() {
                 if i != 0 {
                     seq.push(LinebreakElem::new().pack());
                 }
-
-                for (style, piece) in
-                    highlighter.highlight_line(line, &SYNTAXES).into_iter().flatten()
-                {
-                    seq.push(styled(piece, foreground.into(), style));
-                }
+                seq.push(line);
             }
-
             Content::sequence(seq)
-        } else {
-            TextElem::packed(text)
         };
 
         if self.block(styles) {
@@ -252,6 +314,77 @@ impl PlainText for RawElem {
     }
 }
 
+/// Lay out lines of raw text next to a gutter with their numbers.
+fn numbered(lines: Vec<Content>, styles: StyleChain) -> Content {
+    // The numbered lines are rows of a grid, so the gutter between them
+    // takes the place of the paragraph's leading. It is converted into
+    // `em` to scale with the raw text's smaller font size.
+    let leading = Em::from_length(ParElem::leading_in(styles), TextElem::size_in(styles));
+    let muted = Color::Luma(LumaColor::new(0x8a));
+
+    let mut cells = vec![];
+    for (i, line) in lines.into_iter().enumerate() {
+        let number = TextElem::packed(eco_format!("{}", i + 1))
+            .styled(TextElem::set_fill(muted.into()))
+            .aligned(Axes::with_x(Some(GenAlign::End.into())));
+        cells.push(number);
+        cells.push(line);
+    }
+
+    GridElem::new(cells)
+        .with_columns(TrackSizings(vec![Sizing::Auto, Sizing::Fr(Fr::one())]))
+        .with_column_gutter(TrackSizings(vec![Em::new(1.0).into()]))
+        .with_row_gutter(TrackSizings(vec![leading.into()]))
+        .pack()
+}
+
+/// A syntax highlighting theme, as a list of scope selectors and colors.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct RawTheme(Vec<(EcoString, Color)>);
+
+impl RawTheme {
+    /// Convert the theme into a syntect theme.
+    fn to_syntect(&self) -> synt::Theme {
+        synt::Theme {
+            scopes: self
+                .0
+                .iter()
+                .map(|(selector, color)| synt::ThemeItem {
+                    scope: selector.parse().unwrap(),
+                    style: synt::StyleModifier {
+                        foreground: Some(to_syn(color.to_rgba())),
+                        background: None,
+                        font_style: None,
+                    },
+                })
+                .collect(),
+            ..synt::Theme::default()
+        }
+    }
+}
+
+cast_from_value! {
+    RawTheme,
+    dict: Dict => {
+        let mut items = vec![];
+        for (selector, value) in dict.iter() {
+            if selector.parse::<synt::ScopeSelectors>().is_err() {
+                Err(eco_format!("invalid scope selector: {selector:?}"))?;
+            }
+            items.push((selector.clone().into(), value.clone().cast()?));
+        }
+        Self(items)
+    },
+}
+
+cast_to_value! {
+    v: RawTheme => Value::Dict(
+        v.0.into_iter()
+            .map(|(selector, color)| (selector.into(), color.into()))
+            .collect()
+    )
+}
+
 /// Highlight a syntax node in a theme by calling `f` with ranges and their
 /// styles.
 fn highlight_themed<F>(
@@ -1,4 +1,8 @@
-use super::{variant, SpaceElem, TextElem, TextSize};
+use rustybuzz::Tag;
+use ttf_parser::gsub::SubstitutionSubtable;
+use typst::font::Font;
+
+use super::{variant, FontFeatures, SpaceElem, TextElem, TextSize};
 use crate::prelude::*;
 
 /// Set text in subscript.
@@ -14,11 +18,12 @@ use crate::prelude::*;
 /// Category: text
 #[element(Show)]
 pub struct SubElem {
-    /// Whether to prefer the dedicated subscript characters of the font.
+    /// Whether to prefer the dedicated subscript glyphs of the font.
     ///
     /// If this is enabled, Typst first tries to transform the text to subscript
-    /// codepoints. If that fails, it falls back to rendering lowered and shrunk
-    /// normal letters.
+    /// codepoints and then to use the font's `subs` OpenType feature. If the
+    /// font supports neither for all of the text, it falls back to rendering
+    /// lowered and shrunk normal letters.
     ///
     /// ```example
     /// N#sub(typographic: true)[1]
@@ -28,14 +33,14 @@ pub struct SubElem {
     pub typographic: bool,
 
     /// The baseline shift for synthetic subscripts. Does not apply if
-    /// `typographic` is true and the font has subscript codepoints for the
-    /// given `body`.
+    /// `typographic` is true and the font has subscript glyphs for all
+    /// of the `body`.
     #[default(Em::new(0.2).into())]
     pub baseline: Length,
 
     /// The font size for synthetic subscripts. Does not apply if
-    /// `typographic` is true and the font has subscript codepoints for the
-    /// given `body`.
+    /// `typographic` is true and the font has subscript glyphs for all
+    /// of the `body`.
     #[default(TextSize(Em::new(0.6).into()))]
     pub size: TextSize,
 
@@ -50,12 +55,8 @@ impl Show for SubElem {
         let body = self.body();
         let mut transformed = None;
         if self.typographic(styles) {
-            if let Some(text) = search_text(&body, true) {
-                if is_shapable(vt, &text, styles) {
-                    transformed = Some(TextElem::packed(text));
-                }
-            }
-        };
+            transformed = typographic(vt, &body, true, styles);
+        }
 
         Ok(transformed.unwrap_or_else(|| {
             body.styled(TextElem::set_baseline(self.baseline(styles)))
@@ -77,11 +78,12 @@ impl Show for SubElem {
 /// Category: text
 #[element(Show)]
 pub struct SuperElem {
-    /// Whether to prefer the dedicated superscript characters of the font.
+    /// Whether to prefer the dedicated superscript glyphs of the font.
     ///
     /// If this is enabled, Typst first tries to transform the text to
-    /// superscript codepoints. If that fails, it falls back to rendering
-    /// raised and shrunk normal letters.
+    /// superscript codepoints and then to use the font's `sups` OpenType
+    /// feature. If the font supports neither for all of the text, it falls
+    /// back to rendering raised and shrunk normal letters.
     ///
     /// ```example
     /// N#super(typographic: true)[1]
@@ -91,14 +93,14 @@ pub struct SuperElem {
     pub typographic: bool,
 
     /// The baseline shift for synthetic superscripts. Does not apply if
-    /// `typographic` is true and the font has superscript codepoints for the
-    /// given `body`.
+    /// `typographic` is true and the font has superscript glyphs for all
+    /// of the `body`.
     #[default(Em::new(-0.5).into())]
     pub baseline: Length,
 
     /// The font size for synthetic superscripts. Does not apply if
-    /// `typographic` is true and the font has superscript codepoints for the
-    /// given `body`.
+    /// `typographic` is true and the font has superscript glyphs for all
+    /// of the `body`.
     #[default(TextSize(Em::new(0.6).into()))]
     pub size: TextSize,
 
@@ -113,12 +115,8 @@ impl Show for SuperElem {
         let body = self.body();
         let mut transformed = None;
         if self.typographic(styles) {
-            if let Some(text) = search_text(&body, false) {
-                if is_shapable(vt, &text, styles) {
-                    transformed = Some(TextElem::packed(text));
-                }
-            }
-        };
+            transformed = typographic(vt, &body, false, styles);
+        }
 
         Ok(transformed.unwrap_or_else(|| {
             body.styled(TextElem::set_baseline(self.baseline(styles)))
@@ -127,20 +125,46 @@ impl Show for SuperElem {
     }
 }
 
-/// Find and transform the text contained in `content` to the given script kind
-/// if and only if it only consists of `Text`, `Space`, and `Empty` leafs.
-fn search_text(content: &Content, sub: bool) -> Option<EcoString> {
+/// Try to set the body with the font's dedicated sub- or superscript glyphs.
+///
+/// Returns `None` if neither the font's Unicode sub- or superscript codepoints
+/// nor its OpenType feature cover all of the text. The whole body is then set
+/// synthetically, so that all of its characters look alike. The codepoints are
+/// tried first, so that text which they cover looks the same as before the
+/// feature was supported.
+fn typographic(
+    vt: &Vt,
+    body: &Content,
+    sub: bool,
+    styles: StyleChain,
+) -> Option<Content> {
+    let text = search_text(body)?;
+    let font = first_font(vt, styles)?;
+
+    if let Some(converted) = convert_script(&text, sub) {
+        if converted.chars().all(|c| font.ttf().glyph_index(c).is_some()) {
+            return Some(TextElem::packed(converted));
+        }
+    }
+
+    let feature = if sub { b"subs" } else { b"sups" };
+    has_substitutions(&font, &text, feature).then(|| {
+        let features = FontFeatures(vec![(Tag::from_bytes(feature), 1)]);
+        body.clone().styled(TextElem::set_features(features))
+    })
+}
+
+/// Find the text contained in `content` if and only if it only consists of
+/// `Text`, `Space`, and `Empty` leafs.
+pub(super) fn search_text(content: &Content) -> Option<EcoString> {
     if content.is::<SpaceElem>() {
         Some(' '.into())
     } else if let Some(elem) = content.to::<TextElem>() {
-        convert_script(&elem.text(), sub)
+        Some(elem.text())
     } else if let Some(children) = content.to_sequence() {
         let mut full = EcoString::new();
         for item in children {
-            match search_text(item, sub) {
-                Some(text) => full.push_str(&text),
-                None => return None,
-            }
+            full.push_str(&search_text(item)?);
         }
         Some(full)
     } else {
@@ -148,21 +172,41 @@ fn search_text(content: &Content, sub: bool) -> Option<EcoString> {
     }
 }
 
-/// Checks whether the first retrievable family contains all code points of the
-/// given string.
-fn is_shapable(vt: &Vt, text: &str, styles: StyleChain) -> bool {
+/// The first retrievable font of the families.
+pub(super) fn first_font(vt: &Vt, styles: StyleChain) -> Option<Font> {
     let world = vt.world;
-    for family in TextElem::font_in(styles) {
-        if let Some(font) = world
+    TextElem::font_in(styles).into_iter().find_map(|family| {
+        world
             .book()
             .select(family.as_str(), variant(styles))
             .and_then(|id| world.font(id))
-        {
-            return text.chars().all(|c| font.ttf().glyph_index(c).is_some());
-        }
-    }
+    })
+}
+
+/// Checks whether the given OpenType feature of the font substitutes the
+/// glyphs of all non-whitespace characters of the text.
+pub(super) fn has_substitutions(font: &Font, text: &str, feature: &[u8; 4]) -> bool {
+    let ttf = font.ttf();
+    let Some(gsub) = ttf.tables().gsub else { return false };
+    let tag = Tag::from_bytes(feature);
+    let lookups: Vec<_> = gsub
+        .features
+        .into_iter()
+        .filter(|record| record.tag == tag)
+        .flat_map(|record| record.lookup_indices)
+        .filter_map(|index| gsub.lookups.get(index))
+        .collect();
 
-    false
+    !lookups.is_empty()
+        && text.chars().filter(|c| !c.is_whitespace()).all(|c| {
+            let Some(glyph) = ttf.glyph_index(c) else { return false };
+            lookups.iter().any(|lookup| {
+                lookup
+                    .subtables
+                    .into_iter::<SubstitutionSubtable>()
+                    .any(|subtable| subtable.coverage().contains(glyph))
+            })
+        })
 }
 
 /// Convert a string to sub- or superscript codepoints if all characters
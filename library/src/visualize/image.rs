@@ -9,7 +9,8 @@ use crate::text::families;
 
 /// A raster or vector graphic.
 ///
-/// Supported formats are PNG, JPEG, GIF and SVG.
+/// Supported formats are PNG, JPEG, GIF, WebP and SVG. Raster formats are
+/// detected from the file's contents, SVG images from the file extension.
 ///
 /// _Note:_ Work on SVG export is ongoing and there might be visual inaccuracies
 /// in the resulting PDF. Make sure to double-check embedded SVG images. If you
@@ -174,13 +175,26 @@ fn load(
 ) -> StrResult<Image> {
     let full = Path::new(full);
     let buffer = world.file(full)?;
+    if is_avif(&buffer) {
+        return Err("AVIF images are not supported in this build".into());
+    }
+
     let ext = full.extension().and_then(OsStr::to_str).unwrap_or_default();
-    let format = match ext.to_lowercase().as_str() {
-        "png" => ImageFormat::Raster(RasterFormat::Png),
-        "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
-        "gif" => ImageFormat::Raster(RasterFormat::Gif),
-        "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
-        _ => return Err("unknown image format".into()),
+    let format = match ImageFormat::detect(&buffer) {
+        Some(format) => format,
+        None => match ext.to_lowercase().as_str() {
+            "png" => ImageFormat::Raster(RasterFormat::Png),
+            "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
+            "gif" => ImageFormat::Raster(RasterFormat::Gif),
+            "webp" => ImageFormat::Raster(RasterFormat::Webp),
+            "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
+            _ => return Err("unknown image format".into()),
+        },
     };
     Image::with_fonts(buffer, format, world, fallback_family, alt)
 }
+
+/// Whether the data starts with the file type box of an AVIF image.
+fn is_avif(data: &[u8]) -> bool {
+    matches!(data.get(4..12), Some(b"ftypavif" | b"ftypavis"))
+}
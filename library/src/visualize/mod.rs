@@ -5,12 +5,14 @@ mod line;
 mod path;
 mod polygon;
 mod shape;
+mod sparkline;
 
 pub use self::image::*;
 pub use self::line::*;
 pub use self::path::*;
 pub use self::polygon::*;
 pub use self::shape::*;
+pub use self::sparkline::*;
 
 use crate::prelude::*;
 
@@ -24,4 +26,5 @@ pub(super) fn define(global: &mut Scope) {
     global.define("circle", CircleElem::func());
     global.define("polygon", PolygonElem::func());
     global.define("path", PathElem::func());
+    global.define("sparkline", sparkline);
 }
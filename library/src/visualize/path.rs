@@ -23,10 +23,15 @@ pub struct PathElem {
     /// How to fill the path. See the
     /// [rectangle's documentation]($func/rect.fill) for more details.
     ///
-    /// Currently all paths are filled according to the
-    /// [non-zero winding rule](https://en.wikipedia.org/wiki/Nonzero-rule).
+    /// Which parts of a self-intersecting path are filled is determined by the
+    /// [`fill-rule`]($func/path.fill-rule).
     pub fill: Option<Paint>,
 
+    /// How to fill the parts of a self-intersecting path. See the
+    /// [polygon's documentation]($func/polygon.fill-rule) for more details.
+    #[default(FillRule::NonZero)]
+    pub fill_rule: FillRule,
+
     /// How to stroke the path. This can be:
     ///
     /// See the [line's documentation]($func/line.stroke) for more details. Can
@@ -43,6 +48,27 @@ pub struct PathElem {
     #[default(false)]
     pub closed: bool,
 
+    /// Whether to infer control points for vertices that don't specify any,
+    /// such that the path passes smoothly through them.
+    ///
+    /// The inferred tangent at each vertex is parallel to the line between
+    /// its neighbours, as in a Catmull-Rom spline. The ends of an open path
+    /// keep a sharp corner. Vertices with explicit control points are not
+    /// affected.
+    ///
+    /// ```example
+    /// #path(
+    ///   smooth: true,
+    ///   stroke: blue,
+    ///   (0pt, 40pt),
+    ///   (30pt, 0pt),
+    ///   (60pt, 40pt),
+    ///   (90pt, 0pt),
+    /// )
+    /// ```
+    #[default(false)]
+    pub smooth: bool,
+
     /// The vertices of the path.
     ///
     /// Each vertex can be defined in 3 ways:
@@ -85,48 +111,73 @@ impl Layout for PathElem {
             return Ok(Fragment::frame(Frame::new(size)));
         }
 
+        // Determine the control points of each vertex, relative to the vertex,
+        // for the curves coming into and going out of it.
+        let closed = self.closed(styles);
+        let smooth = self.smooth(styles);
+        let controls: Vec<(Point, Point)> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, vertex)| match vertex {
+                Vertex(_) if smooth => {
+                    let last = points.len() - 1;
+                    let prev = if i > 0 {
+                        points[i - 1]
+                    } else if closed {
+                        points[last]
+                    } else {
+                        points[i]
+                    };
+                    let next = if i < last {
+                        points[i + 1]
+                    } else if closed {
+                        points[0]
+                    } else {
+                        points[i]
+                    };
+                    let tangent = (next - prev) / 6.0;
+                    (-tangent, tangent)
+                }
+                _ => (
+                    resolve(vertex.control_point_to()),
+                    resolve(vertex.control_point_from()),
+                ),
+            })
+            .collect();
+
         // Only create a path if there are more than zero points.
         // Construct a closed path given all points.
         let mut path = Path::new();
         path.move_to(points[0]);
 
-        let mut add_cubic =
-            |from_point: Point, to_point: Point, from: PathVertex, to: PathVertex| {
-                let from_control_point = resolve(from.control_point_from()) + from_point;
-                let to_control_point = resolve(to.control_point_to()) + to_point;
-                path.cubic_to(from_control_point, to_control_point, to_point);
-
-                let p0 = kurbo::Point::new(from_point.x.to_raw(), from_point.y.to_raw());
-                let p1 = kurbo::Point::new(
-                    from_control_point.x.to_raw(),
-                    from_control_point.y.to_raw(),
-                );
-                let p2 = kurbo::Point::new(
-                    to_control_point.x.to_raw(),
-                    to_control_point.y.to_raw(),
-                );
-                let p3 = kurbo::Point::new(to_point.x.to_raw(), to_point.y.to_raw());
-                let extrema = CubicBez::new(p0, p1, p2, p3).bounding_box();
-                size.x.set_max(Abs::raw(extrema.x1));
-                size.y.set_max(Abs::raw(extrema.y1));
-            };
-
-        for (vertex_window, point_window) in vertices.windows(2).zip(points.windows(2)) {
-            let from = vertex_window[0];
-            let to = vertex_window[1];
-            let from_point = point_window[0];
-            let to_point = point_window[1];
-
-            add_cubic(from_point, to_point, from, to);
-        }
+        let mut add_cubic = |from: usize, to: usize| {
+            let from_point = points[from];
+            let to_point = points[to];
+            let from_control_point = controls[from].1 + from_point;
+            let to_control_point = controls[to].0 + to_point;
+            path.cubic_to(from_control_point, to_control_point, to_point);
+
+            let p0 = kurbo::Point::new(from_point.x.to_raw(), from_point.y.to_raw());
+            let p1 = kurbo::Point::new(
+                from_control_point.x.to_raw(),
+                from_control_point.y.to_raw(),
+            );
+            let p2 = kurbo::Point::new(
+                to_control_point.x.to_raw(),
+                to_control_point.y.to_raw(),
+            );
+            let p3 = kurbo::Point::new(to_point.x.to_raw(), to_point.y.to_raw());
+            let extrema = CubicBez::new(p0, p1, p2, p3).bounding_box();
+            size.x.set_max(Abs::raw(extrema.x1));
+            size.y.set_max(Abs::raw(extrema.y1));
+        };
 
-        if self.closed(styles) {
-            let from = *vertices.last().unwrap(); // We checked that we have at least one element.
-            let to = vertices[0];
-            let from_point = *points.last().unwrap();
-            let to_point = points[0];
+        for i in 1..points.len() {
+            add_cubic(i - 1, i);
+        }
 
-            add_cubic(from_point, to_point, from, to);
+        if closed {
+            add_cubic(points.len() - 1, 0);
             path.close_path();
         }
 
@@ -139,7 +190,12 @@ impl Layout for PathElem {
         };
 
         let mut frame = Frame::new(size);
-        let shape = Shape { geometry: Geometry::Path(path), stroke, fill };
+        let shape = Shape {
+            geometry: Geometry::Path(path),
+            stroke,
+            fill,
+            fill_rule: self.fill_rule(styles),
+        };
         frame.push(Point::zero(), FrameItem::Shape(shape, self.span()));
 
         Ok(Fragment::frame(frame))
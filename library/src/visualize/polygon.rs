@@ -1,3 +1,5 @@
+use std::f64::consts::TAU;
+
 use crate::prelude::*;
 
 /// A closed polygon.
@@ -19,14 +21,36 @@ use crate::prelude::*;
 /// Display: Polygon
 /// Category: visualize
 #[element(Layout)]
+#[scope(
+    scope.define("regular", polygon_regular);
+    scope
+)]
 pub struct PolygonElem {
     /// How to fill the polygon. See the
     /// [rectangle's documentation]($func/rect.fill) for more details.
     ///
-    /// Currently all polygons are filled according to the
-    /// [non-zero winding rule](https://en.wikipedia.org/wiki/Nonzero-rule).
+    /// Which parts of a self-intersecting polygon are filled is determined
+    /// by the [`fill-rule`]($func/polygon.fill-rule).
     pub fill: Option<Paint>,
 
+    /// How to fill the parts of a self-intersecting polygon.
+    ///
+    /// - `{"non-zero"}`: Fill everything that the outline winds around, in
+    ///   the sense of the
+    ///   [non-zero winding rule](https://en.wikipedia.org/wiki/Nonzero-rule).
+    /// - `{"even-odd"}`: Only fill regions that are enclosed an odd number of
+    ///   times, in the sense of the
+    ///   [even-odd rule](https://en.wikipedia.org/wiki/Even-odd_rule). This
+    ///   leaves holes where the outline overlaps itself.
+    ///
+    /// ```example
+    /// #let star = ((50pt, 0pt), (80pt, 90pt), (0pt, 35pt), (100pt, 35pt), (20pt, 90pt))
+    /// #polygon(fill: blue, ..star)
+    /// #polygon(fill: blue, fill-rule: "even-odd", ..star)
+    /// ```
+    #[default(FillRule::NonZero)]
+    pub fill_rule: FillRule,
+
     /// How to stroke the polygon. This can be:
     ///
     /// See the [line's documentation]($func/line.stroke) for more details. Can
@@ -85,9 +109,86 @@ impl Layout for PolygonElem {
         }
         path.close_path();
 
-        let shape = Shape { geometry: Geometry::Path(path), stroke, fill };
+        let shape = Shape {
+            geometry: Geometry::Path(path),
+            stroke,
+            fill,
+            fill_rule: self.fill_rule(styles),
+        };
         frame.push(Point::zero(), FrameItem::Shape(shape, self.span()));
 
         Ok(Fragment::frame(frame))
     }
 }
+
+/// A regular polygon.
+///
+/// The polygon's corners lie evenly spaced on a circle. Its bounding box is
+/// tightly fit around the corners, so the polygon can be placed like any other
+/// shape.
+///
+/// ## Example { #example }
+/// ```example
+/// #polygon.regular(fill: blue.lighten(80%), stroke: blue)
+/// #polygon.regular(vertices: 6, radius: 20pt, fill: green)
+/// #polygon.regular(rotation: 180deg, fill: red)
+/// ```
+///
+/// Display: Regular Polygon
+/// Category: visualize
+/// Returns: content
+#[func]
+pub fn polygon_regular(
+    /// How to fill the polygon. See the [general polygon's
+    /// documentation]($func/polygon.fill) for more details.
+    #[named]
+    fill: Option<Option<Paint>>,
+    /// How to stroke the polygon. See the [general polygon's
+    /// documentation]($func/polygon.stroke) for more details.
+    #[named]
+    stroke: Option<Smart<Option<PartialStroke>>>,
+    /// The number of vertices. Must be at least three.
+    #[named]
+    #[default(3)]
+    vertices: usize,
+    /// The distance of each vertex from the polygon's center.
+    #[named]
+    #[default(Em::one().into())]
+    radius: Length,
+    /// How much to rotate the polygon clockwise. At `{0deg}`, the first vertex
+    /// points straight up.
+    #[named]
+    #[default]
+    rotation: Angle,
+) -> Value {
+    if vertices < 3 {
+        bail!(args.span, "regular polygon must have at least 3 vertices");
+    }
+
+    let corners: Vec<(f64, f64)> = (0..vertices)
+        .map(|i| {
+            let angle = rotation + Angle::rad(TAU * i as f64 / vertices as f64);
+            (angle.sin(), -angle.cos())
+        })
+        .collect();
+
+    // Shift the corners so that the top-left of their bounding box lies at the
+    // origin.
+    let min_x = corners.iter().map(|&(x, _)| x).fold(f64::INFINITY, f64::min);
+    let min_y = corners.iter().map(|&(_, y)| y).fold(f64::INFINITY, f64::min);
+    let points = corners
+        .into_iter()
+        .map(|(x, y)| {
+            Axes::new(radius * (x - min_x), radius * (y - min_y)).map(Rel::from)
+        })
+        .collect();
+
+    let mut elem = PolygonElem::new(points);
+    if let Some(fill) = fill {
+        elem.push_fill(fill);
+    }
+    if let Some(stroke) = stroke {
+        elem.push_stroke(stroke);
+    }
+    elem.pack().into()
+}
@@ -0,0 +1,92 @@
+use crate::layout::{BoxElem, PlaceElem, Sizing};
+use crate::prelude::*;
+use crate::visualize::{CircleElem, PathElem, PathVertex};
+
+/// A small inline line chart.
+///
+/// The data points are spread evenly across the chart's width and scaled so
+/// that the smallest value touches the bottom and the largest value touches the
+/// top. If all values are equal, a flat line is drawn through the middle. A
+/// single value is shown as a dot.
+///
+/// The chart is an inline box that sits on the baseline, so it can be used in
+/// running text and in table cells.
+///
+/// ## Example { #example }
+/// ```example
+/// Visitors grew steadily
+/// #sparkline((3, 4, 2, 5, 7, 6, 9))
+/// over the last week.
+/// ```
+///
+/// Display: Sparkline
+/// Category: visualize
+/// Returns: content
+#[func]
+pub fn sparkline(
+    /// The values to plot.
+    data: Vec<f64>,
+    /// The width of the chart.
+    #[named]
+    #[default(Em::new(3.0).into())]
+    width: Length,
+    /// The height of the chart. By default, it matches the font size.
+    #[named]
+    #[default(Em::one().into())]
+    height: Length,
+    /// How to stroke the line. See the [line's documentation]($func/line.stroke)
+    /// for more details. Its paint also colors the dot for a single value.
+    #[named]
+    stroke: Option<PartialStroke>,
+) -> Value {
+    if data.is_empty() {
+        bail!(args.span, "sparkline must have at least one data point");
+    }
+
+    let body = if data.len() == 1 {
+        let paint = stroke
+            .as_ref()
+            .and_then(|stroke| stroke.paint.clone().as_custom())
+            .unwrap_or(Color::BLACK.into());
+        let thickness = stroke
+            .as_ref()
+            .and_then(|stroke| stroke.thickness.as_custom())
+            .unwrap_or(Abs::pt(1.0).into());
+        let size = Smart::Custom(Rel::from(thickness * 2.0));
+        let dot = CircleElem::new()
+            .with_width(size)
+            .with_height(size)
+            .with_fill(Some(paint));
+        PlaceElem::new(dot.pack())
+            .with_alignment(Align::CENTER_HORIZON.into())
+            .pack()
+    } else {
+        let min = data.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = data.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let last = (data.len() - 1) as f64;
+        let vertices = data
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                let y = if max > min { (max - value) / (max - min) } else { 0.5 };
+                PathVertex::Vertex(Axes::new(
+                    Ratio::new(i as f64 / last).into(),
+                    Ratio::new(y).into(),
+                ))
+            })
+            .collect();
+
+        let mut path = PathElem::new(vertices);
+        if let Some(stroke) = stroke {
+            path.push_stroke(Smart::Custom(Some(stroke)));
+        }
+        path.pack()
+    };
+
+    BoxElem::new()
+        .with_body(Some(body))
+        .with_width(Sizing::Rel(width.into()))
+        .with_height(Smart::Custom(height.into()))
+        .pack()
+        .into()
+}
@@ -9,11 +9,13 @@ use std::string::FromUtf8Error;
 
 use comemo::Tracked;
 use ecow::EcoString;
+use serde::Serialize;
 
-use crate::syntax::{ErrorPos, Span, Spanned};
+use crate::eval::PackageSpec;
+use crate::syntax::{ErrorPos, Source, SourceId, Span, Spanned};
 use crate::World;
 
-/// Early-return with a [`SourceError`].
+/// Early-return with a [`SourceDiagnostic`].
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __bail {
@@ -29,12 +31,12 @@ macro_rules! __bail {
 #[doc(inline)]
 pub use crate::__bail as bail;
 
-/// Construct a [`SourceError`].
+/// Construct an error [`SourceDiagnostic`].
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __error {
     ($span:expr, $message:expr $(,)?) => {
-        $crate::diag::SourceError::new($span, $message)
+        $crate::diag::SourceDiagnostic::error($span, $message)
     };
 
     ($span:expr, $fmt:expr, $($arg:expr),+ $(,)?) => {
@@ -44,33 +46,65 @@ macro_rules! __error {
 
 #[doc(inline)]
 pub use crate::__error as error;
+
+/// Construct a warning [`SourceDiagnostic`].
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __warning {
+    ($span:expr, $message:expr $(,)?) => {
+        $crate::diag::SourceDiagnostic::warning($span, $message)
+    };
+
+    ($span:expr, $fmt:expr, $($arg:expr),+ $(,)?) => {
+        $crate::diag::warning!($span, $crate::diag::eco_format!($fmt, $($arg),+))
+    };
+}
+
+#[doc(inline)]
+pub use crate::__warning as warning;
 #[doc(hidden)]
 pub use ecow::eco_format;
 
 /// A result that can carry multiple source errors.
-pub type SourceResult<T> = Result<T, Box<Vec<SourceError>>>;
+pub type SourceResult<T> = Result<T, Box<Vec<SourceDiagnostic>>>;
 
-/// An error in a source file.
+/// An error or warning in a source file.
 ///
 /// The contained spans will only be detached if any of the input source files
 /// were detached.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub struct SourceError {
-    /// The span of the erroneous node in the source code.
+pub struct SourceDiagnostic {
+    /// Whether the diagnostic is an error or a warning.
+    pub severity: Severity,
+    /// The span of the relevant node in the source code.
     pub span: Span,
-    /// The position in the node where the error should be annotated.
+    /// The position in the node where the diagnostic should be annotated.
     pub pos: ErrorPos,
     /// A diagnostic message describing the problem.
     pub message: EcoString,
-    /// The trace of function calls leading to the error.
+    /// The trace of function calls leading to the diagnostic.
     pub trace: Vec<Spanned<Tracepoint>>,
 }
 
-impl SourceError {
+/// An error in a source file.
+#[deprecated(note = "use `SourceDiagnostic` instead")]
+pub type SourceError = SourceDiagnostic;
+
+/// The severity of a [`SourceDiagnostic`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Severity {
+    /// A fatal error.
+    Error,
+    /// A non-fatal warning.
+    Warning,
+}
+
+impl SourceDiagnostic {
     /// Create a new, bare error.
     #[track_caller]
-    pub fn new(span: Span, message: impl Into<EcoString>) -> Self {
+    pub fn error(span: Span, message: impl Into<EcoString>) -> Self {
         Self {
+            severity: Severity::Error,
             span,
             pos: ErrorPos::Full,
             trace: vec![],
@@ -78,17 +112,36 @@ impl SourceError {
         }
     }
 
-    /// Adjust the position in the node where the error should be annotated.
+    /// Create a new, bare warning.
+    #[track_caller]
+    pub fn warning(span: Span, message: impl Into<EcoString>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            span,
+            pos: ErrorPos::Full,
+            trace: vec![],
+            message: message.into(),
+        }
+    }
+
+    /// Adjust the position in the node where the diagnostic should be
+    /// annotated.
     pub fn with_pos(mut self, pos: ErrorPos) -> Self {
         self.pos = pos;
         self
     }
 
     /// The range in the source file identified by
-    /// [`self.span.source()`](Span::source) where the error should be
+    /// [`self.span.source()`](Span::source) where the diagnostic should be
     /// annotated.
     pub fn range(&self, world: &dyn World) -> Range<usize> {
-        let full = world.source(self.span.source()).range(self.span);
+        self.range_in(world.source(self.span.source()))
+    }
+
+    /// The range in the given source file where the diagnostic should be
+    /// annotated.
+    fn range_in(&self, source: &Source) -> Range<usize> {
+        let full = source.range(self.span);
         match self.pos {
             ErrorPos::Full => full,
             ErrorPos::Start => full.start..full.start,
@@ -97,7 +150,7 @@ impl SourceError {
     }
 }
 
-/// A part of an error's [trace](SourceError::trace).
+/// A part of a diagnostic's [trace](SourceDiagnostic::trace).
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Tracepoint {
     /// A function call.
@@ -159,6 +212,69 @@ impl<T> Trace<T> for SourceResult<T> {
     }
 }
 
+/// Serialize errors and warnings into a JSON array of diagnostics.
+///
+/// Each diagnostic is an object with the fields `file`, `range` (an object
+/// with `start` and `end` byte offsets), `line` and `column` (one-based, at
+/// the start of the range), `severity`, and `message`. The location fields are
+/// `null` for errors with detached spans.
+pub fn serialize_errors(errors: &[SourceDiagnostic], world: &dyn World) -> String {
+    serialize_with(errors, |id| world.source(id))
+}
+
+/// Serialize diagnostics, looking up the source files their spans point into.
+fn serialize_with<'a>(
+    errors: &[SourceDiagnostic],
+    source: impl Fn(SourceId) -> &'a Source,
+) -> String {
+    let diagnostics: Vec<_> = errors
+        .iter()
+        .map(|error| SerializedDiagnostic::new(error, &source))
+        .collect();
+    serde_json::to_string(&diagnostics).expect("diagnostics are serializable")
+}
+
+/// The JSON representation of a single diagnostic.
+#[derive(Serialize)]
+struct SerializedDiagnostic<'a> {
+    file: Option<String>,
+    range: Option<Range<usize>>,
+    line: Option<usize>,
+    column: Option<usize>,
+    severity: &'static str,
+    message: &'a str,
+}
+
+impl<'a> SerializedDiagnostic<'a> {
+    fn new<'s>(
+        error: &'a SourceDiagnostic,
+        source: &impl Fn(SourceId) -> &'s Source,
+    ) -> Self {
+        let mut diagnostic = Self {
+            file: None,
+            range: None,
+            line: None,
+            column: None,
+            severity: match error.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            },
+            message: &error.message,
+        };
+
+        if !error.span.is_detached() {
+            let source = source(error.span.source());
+            let range = error.range_in(source);
+            diagnostic.file = Some(source.path().display().to_string());
+            diagnostic.line = source.byte_to_line(range.start).map(|line| line + 1);
+            diagnostic.column = source.byte_to_column(range.start).map(|col| col + 1);
+            diagnostic.range = Some(range);
+        }
+
+        diagnostic
+    }
+}
+
 /// A result type with a string error message.
 pub type StrResult<T> = Result<T, EcoString>;
 
@@ -193,6 +309,8 @@ pub enum FileError {
     NotSource,
     /// The file was not valid UTF-8, but should have been.
     InvalidUtf8,
+    /// The package the file is part of could not be loaded.
+    Package(PackageError),
     /// Another error.
     Other,
 }
@@ -225,11 +343,18 @@ impl Display for FileError {
             Self::IsDirectory => f.pad("failed to load file (is a directory)"),
             Self::NotSource => f.pad("not a typst source file"),
             Self::InvalidUtf8 => f.pad("file is not valid utf-8"),
+            Self::Package(error) => error.fmt(f),
             Self::Other => f.pad("failed to load file"),
         }
     }
 }
 
+impl From<PackageError> for FileError {
+    fn from(error: PackageError) -> Self {
+        Self::Package(error)
+    }
+}
+
 impl From<Utf8Error> for FileError {
     fn from(_: Utf8Error) -> Self {
         Self::InvalidUtf8
@@ -248,6 +373,43 @@ impl From<FileError> for EcoString {
     }
 }
 
+/// A result type with a package-related error.
+pub type PackageResult<T> = Result<T, PackageError>;
+
+/// An error that occurred while trying to load a package.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum PackageError {
+    /// The specified package does not exist.
+    NotFound(PackageSpec),
+    /// Failed to retrieve the package through the network.
+    NetworkFailed,
+    /// The package archive was malformed.
+    MalformedArchive,
+    /// Another error.
+    Other,
+}
+
+impl std::error::Error for PackageError {}
+
+impl Display for PackageError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound(spec) => {
+                write!(f, "package not found (searched for {spec})")
+            }
+            Self::NetworkFailed => f.pad("failed to load package (network failed)"),
+            Self::MalformedArchive => f.pad("failed to load package (archive malformed)"),
+            Self::Other => f.pad("failed to load package"),
+        }
+    }
+}
+
+impl From<PackageError> for EcoString {
+    fn from(error: PackageError) -> Self {
+        eco_format!("{error}")
+    }
+}
+
 /// Format a user-facing error message for an XML-like file format.
 pub fn format_xml_like_error(format: &str, error: roxmltree::Error) -> EcoString {
     match error {
@@ -276,3 +438,32 @@ pub fn format_xml_like_error(format: &str, error: roxmltree::Error) -> EcoString
         _ => eco_format!("failed to parse {format}"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_errors() {
+        let source = Source::new(
+            SourceId::from_u16(0),
+            Path::new("main.typ"),
+            "Hello\n#foo".into(),
+        );
+        let span = source.root().leaf_at(9).unwrap().span();
+        let diagnostics = [
+            error!(span, "unknown variable"),
+            warning!(Span::detached(), "something is off"),
+        ];
+
+        assert_eq!(
+            serialize_with(&diagnostics, |_| &source),
+            concat!(
+                r#"[{"file":"main.typ","range":{"start":7,"end":10},"line":2,"#,
+                r#""column":2,"severity":"error","message":"unknown variable"},"#,
+                r#"{"file":null,"range":null,"line":null,"column":null,"#,
+                r#""severity":"warning","message":"something is off"}]"#,
+            )
+        );
+    }
+}
@@ -6,16 +6,17 @@ use std::ops::Range;
 use std::str::FromStr;
 use std::sync::Arc;
 
-use ecow::EcoString;
+use ecow::{eco_format, EcoString};
 
-use crate::eval::{cast_from_value, cast_to_value, dict, Dict, Value};
+use crate::diag::StrResult;
+use crate::eval::{cast_from_value, cast_to_value, dict, Datetime, Dict, Value};
 use crate::font::Font;
 use crate::geom::{
     self, rounded_rect, Abs, Align, Axes, Color, Corners, Dir, Em, Geometry, Length,
     Numeric, Paint, Point, Rel, RgbaColor, Shape, Sides, Size, Stroke, Transform,
 };
 use crate::image::Image;
-use crate::model::{Content, Location, MetaElem, StyleChain};
+use crate::model::{Content, Introspector, Location, MetaElem, StyleChain};
 use crate::syntax::Span;
 
 /// A finished document with metadata and page frames.
@@ -27,6 +28,126 @@ pub struct Document {
     pub title: Option<EcoString>,
     /// The document's author.
     pub author: Vec<EcoString>,
+    /// The document's keywords.
+    pub keywords: Vec<EcoString>,
+    /// The document's creation date.
+    pub date: Option<Datetime>,
+}
+
+impl Document {
+    /// Start building a document from already laid out pages.
+    pub fn builder() -> DocumentBuilder {
+        DocumentBuilder::default()
+    }
+
+    /// Collect the document's headings in the order in which they appear.
+    ///
+    /// This is the basis for the bookmarks in exported PDFs.
+    pub fn outline(&self) -> Vec<OutlineEntry> {
+        self.outline_in(&Introspector::new(&self.pages))
+    }
+
+    /// Collect the document's headings with an introspector that was already
+    /// built from this document's pages.
+    pub(crate) fn outline_in(&self, introspector: &Introspector) -> Vec<OutlineEntry> {
+        introspector
+            .query(&item!(heading_func).select())
+            .iter()
+            .filter_map(|heading| {
+                let body = heading.expect_field::<Content>("body");
+                Some(OutlineEntry {
+                    title: body.plain_text().trim().into(),
+                    level: heading.expect_field::<NonZeroUsize>("level"),
+                    position: introspector.position(heading.location()?),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Assembles a [`Document`] from frames and metadata without compiling any
+/// source, for example to merge the outputs of several compilations.
+#[derive(Debug, Default, Clone)]
+pub struct DocumentBuilder {
+    document: Document,
+}
+
+impl DocumentBuilder {
+    /// Append a page.
+    pub fn page(mut self, frame: Frame) -> Self {
+        self.document.pages.push(frame);
+        self
+    }
+
+    /// Append multiple pages.
+    pub fn pages(mut self, frames: impl IntoIterator<Item = Frame>) -> Self {
+        self.document.pages.extend(frames);
+        self
+    }
+
+    /// Set the document's title.
+    pub fn title(mut self, title: impl Into<EcoString>) -> Self {
+        self.document.title = Some(title.into());
+        self
+    }
+
+    /// Add an author to the document.
+    pub fn author(mut self, author: impl Into<EcoString>) -> Self {
+        self.document.author.push(author.into());
+        self
+    }
+
+    /// Add a keyword to the document.
+    pub fn keyword(mut self, keyword: impl Into<EcoString>) -> Self {
+        self.document.keywords.push(keyword.into());
+        self
+    }
+
+    /// Set the document's creation date.
+    pub fn date(mut self, date: Datetime) -> Self {
+        self.document.date = Some(date);
+        self
+    }
+
+    /// Check the document and finish building it.
+    ///
+    /// Fails if there are no pages, if a page's size is not finite, or if any
+    /// piece of metadata is empty.
+    pub fn build(self) -> StrResult<Document> {
+        let document = self.document;
+        if document.pages.is_empty() {
+            Err("document must have at least one page")?;
+        }
+
+        if let Some(i) = document.pages.iter().position(|page| !page.size().is_finite()) {
+            Err(eco_format!("page {} must have a finite size", i + 1))?;
+        }
+
+        if document.title.as_ref().map_or(false, |title| title.trim().is_empty()) {
+            Err("document title must not be empty")?;
+        }
+
+        if document.author.iter().any(|author| author.trim().is_empty()) {
+            Err("document author must not be empty")?;
+        }
+
+        if document.keywords.iter().any(|keyword| keyword.trim().is_empty()) {
+            Err("document keyword must not be empty")?;
+        }
+
+        Ok(document)
+    }
+}
+
+/// A heading in a document's outline.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct OutlineEntry {
+    /// The heading's title as plain text.
+    pub title: EcoString,
+    /// The heading's nesting level, starting at 1.
+    pub level: NonZeroUsize,
+    /// Where the heading is in the document.
+    pub position: Position,
 }
 
 /// A finished layout with items at fixed positions.
@@ -115,6 +236,59 @@ impl Frame {
     pub fn items(&self) -> std::slice::Iter<'_, (Point, FrameItem)> {
         self.items.iter()
     }
+
+    /// The span of the topmost item at the given point, for mapping a click
+    /// in the output back to the source.
+    ///
+    /// Items that come later in the frame are drawn over earlier ones, so
+    /// they are hit first. Text is hit glyph by glyph, with each glyph
+    /// covering its advance horizontally and the font size above the baseline
+    /// vertically. Shapes are hit within their bounding box. Items without a
+    /// span, like most backgrounds, are transparent to hits.
+    pub fn pick(&self, point: Point) -> Option<Span> {
+        for (pos, item) in self.items().rev() {
+            let local = point - *pos;
+            match item {
+                FrameItem::Group(group) => {
+                    let Some(inverse) = group.transform.invert() else { continue };
+                    let local = local.transform(inverse);
+                    if group.clips && !in_rect(Point::zero(), group.frame.size(), local) {
+                        continue;
+                    }
+                    if let Some(span) = group.frame.pick(local) {
+                        return Some(span);
+                    }
+                }
+                FrameItem::Text(text) => {
+                    let mut x = Abs::zero();
+                    for glyph in &text.glyphs {
+                        let width = glyph.x_advance.at(text.size);
+                        let origin = Point::new(x, -text.size);
+                        if !glyph.span.0.is_detached()
+                            && in_rect(origin, Size::new(width, text.size), local)
+                        {
+                            return Some(glyph.span.0);
+                        }
+                        x += width;
+                    }
+                }
+                FrameItem::Shape(shape, span) if !span.is_detached() => {
+                    let (origin, size) = shape.geometry.bbox();
+                    if in_rect(origin, size, local) {
+                        return Some(*span);
+                    }
+                }
+                FrameItem::Image(_, size, span) if !span.is_detached() => {
+                    if in_rect(Point::zero(), *size, local) {
+                        return Some(*span);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
 }
 
 /// Insert items and subframes.
@@ -399,6 +573,15 @@ impl Debug for Frame {
     }
 }
 
+/// Whether a point lies within the rectangle with the given top-left corner
+/// and size.
+fn in_rect(origin: Point, size: Size, point: Point) -> bool {
+    origin.x <= point.x
+        && point.x <= origin.x + size.x
+        && origin.y <= point.y
+        && point.y <= origin.y + size.y
+}
+
 /// The building block frames are composed of.
 #[derive(Clone, Hash)]
 pub enum FrameItem {
@@ -710,6 +893,71 @@ mod tests {
         assert!(!option_eq(region, "AB"));
     }
 
+    #[test]
+    fn test_frame_pick() {
+        use crate::geom::Ratio;
+        use crate::syntax::SourceId;
+
+        let span = |n| Span::new(SourceId::from_u16(0), n);
+        let rect = |w, h| Geometry::Rect(Size::new(Abs::pt(w), Abs::pt(h)));
+        let pt = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+
+        let mut inner = Frame::new(Size::new(Abs::pt(10.0), Abs::pt(10.0)));
+        inner.push(
+            Point::zero(),
+            FrameItem::Shape(rect(10.0, 10.0).stroked(Stroke::default()), span(30)),
+        );
+
+        let mut frame = Frame::new(Size::new(Abs::pt(100.0), Abs::pt(100.0)));
+        frame.push(
+            Point::zero(),
+            FrameItem::Shape(
+                rect(100.0, 100.0).stroked(Stroke::default()),
+                Span::detached(),
+            ),
+        );
+        frame.push(
+            pt(10.0, 10.0),
+            FrameItem::Shape(rect(30.0, 30.0).stroked(Stroke::default()), span(10)),
+        );
+        frame.push(
+            pt(20.0, 20.0),
+            FrameItem::Shape(rect(30.0, 30.0).stroked(Stroke::default()), span(20)),
+        );
+
+        let mut group = GroupItem::new(inner);
+        group.transform = Transform::scale(Ratio::new(2.0), Ratio::new(2.0));
+        frame.push(pt(60.0, 60.0), FrameItem::Group(group));
+
+        assert_eq!(frame.pick(pt(15.0, 15.0)), Some(span(10)));
+        assert_eq!(frame.pick(pt(25.0, 25.0)), Some(span(20)));
+        assert_eq!(frame.pick(pt(75.0, 75.0)), Some(span(30)));
+        assert_eq!(frame.pick(pt(65.0, 78.0)), Some(span(30)));
+        assert_eq!(frame.pick(pt(90.0, 10.0)), None);
+    }
+
+    #[test]
+    fn test_document_builder() {
+        let page = || Frame::new(Size::new(Abs::pt(100.0), Abs::pt(50.0)));
+        let document = Document::builder()
+            .page(page())
+            .pages([page(), page()])
+            .title("Report")
+            .author("Jane")
+            .keyword("draft")
+            .build()
+            .unwrap();
+        assert_eq!(document.pages.len(), 3);
+        assert_eq!(document.title.as_deref(), Some("Report"));
+        assert_eq!(document.author, vec![EcoString::from("Jane")]);
+        assert_eq!(document.keywords, vec![EcoString::from("draft")]);
+
+        let unbounded = Frame::new(Size::new(Abs::pt(100.0), Abs::inf()));
+        assert!(Document::builder().build().is_err());
+        assert!(Document::builder().page(unbounded).build().is_err());
+        assert!(Document::builder().page(page()).title(" ").build().is_err());
+    }
+
     #[test]
     fn test_document_is_send() {
         fn ensure_send<T: Send>() {}
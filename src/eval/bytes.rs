@@ -0,0 +1,164 @@
+use std::fmt::{self, Debug, Formatter, Write};
+use std::ops::Add;
+
+use base64::Engine;
+use ecow::{eco_format, EcoString};
+
+use super::{Array, Value};
+use crate::diag::StrResult;
+use crate::eval::cast_from_value;
+use crate::util::Buffer;
+
+/// An immutable sequence of bytes.
+///
+/// Bytes are never implicitly converted to or from strings. They must be
+/// decoded and encoded explicitly.
+#[derive(Clone, Hash, Eq, PartialEq)]
+pub struct Bytes(Buffer);
+
+impl Bytes {
+    /// The length of the sequence in bytes.
+    pub fn len(&self) -> i64 {
+        self.0.len() as i64
+    }
+
+    /// Whether the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Return a view into the bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// The byte at the given index.
+    pub fn at(&self, index: i64) -> StrResult<u8> {
+        self.locate(index)
+            .and_then(|i| self.0.get(i).copied())
+            .ok_or_else(|| out_of_bounds(index, self.len()))
+    }
+
+    /// Extract a contiguous subsequence.
+    pub fn slice(&self, start: i64, end: Option<i64>) -> StrResult<Self> {
+        let len = self.len();
+        let start = self.locate(start).ok_or_else(|| out_of_bounds(start, len))?;
+        let end = end.unwrap_or(len);
+        let end = self.locate(end).ok_or_else(|| out_of_bounds(end, len))?.max(start);
+        Ok(Self::from(&self.0[start..end]))
+    }
+
+    /// The bytes as an array of integers.
+    pub fn to_array(&self) -> Array {
+        self.0.iter().map(|&byte| Value::Int(byte.into())).collect()
+    }
+
+    /// Decode the bytes as UTF-8.
+    pub fn to_utf8(&self) -> StrResult<&str> {
+        std::str::from_utf8(&self.0).map_err(|_| "bytes are not valid utf-8".into())
+    }
+
+    /// Encode the bytes in base64.
+    pub fn to_base64(&self) -> EcoString {
+        base64::engine::general_purpose::STANDARD.encode(&*self.0).into()
+    }
+
+    /// Decode bytes from base64.
+    pub fn from_base64(text: &str) -> StrResult<Self> {
+        base64::engine::general_purpose::STANDARD
+            .decode(text)
+            .map(Self::from)
+            .map_err(|_| "string is not valid base64".into())
+    }
+
+    /// Encode the bytes as lowercase hexadecimal digits.
+    pub fn to_hex(&self) -> EcoString {
+        let mut hex = EcoString::new();
+        for byte in self.0.iter() {
+            write!(hex, "{byte:02x}").unwrap();
+        }
+        hex
+    }
+
+    /// Decode bytes from hexadecimal digits.
+    pub fn from_hex(text: &str) -> StrResult<Self> {
+        let digits = text.as_bytes();
+        if digits.len() % 2 != 0 {
+            Err("hexadecimal string must have an even number of digits")?;
+        }
+
+        digits
+            .chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or_else(|| "string is not valid hexadecimal".into())
+            })
+            .collect::<StrResult<Vec<u8>>>()
+            .map(Self::from)
+    }
+
+    /// Resolve an index, which may be negative to count from the back.
+    fn locate(&self, index: i64) -> Option<usize> {
+        let wrapped =
+            if index >= 0 { Some(index) } else { self.len().checked_add(index) };
+        wrapped
+            .and_then(|v| usize::try_from(v).ok())
+            .filter(|&v| v <= self.0.len())
+    }
+}
+
+impl From<Buffer> for Bytes {
+    fn from(buffer: Buffer) -> Self {
+        Self(buffer)
+    }
+}
+
+impl From<&[u8]> for Bytes {
+    fn from(slice: &[u8]) -> Self {
+        Self(slice.into())
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(vec: Vec<u8>) -> Self {
+        Self(vec.into())
+    }
+}
+
+impl From<Bytes> for Buffer {
+    fn from(bytes: Bytes) -> Self {
+        bytes.0
+    }
+}
+
+impl Add for Bytes {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        if self.is_empty() {
+            return other;
+        }
+
+        let mut vec = self.0.to_vec();
+        vec.extend_from_slice(&other.0);
+        vec.into()
+    }
+}
+
+impl Debug for Bytes {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "bytes({})", self.len())
+    }
+}
+
+cast_from_value! {
+    Bytes: "bytes",
+}
+
+/// The out of bounds access error message.
+#[cold]
+fn out_of_bounds(index: i64, len: i64) -> EcoString {
+    eco_format!("byte index out of bounds (index: {}, len: {})", index, len)
+}
@@ -6,6 +6,7 @@ use ecow::{eco_format, EcoString, EcoVec};
 use time::error::{Format, InvalidFormatDescription};
 use time::{format_description, PrimitiveDateTime};
 
+use super::Duration;
 use crate::eval::cast_from_value;
 use crate::util::pretty_array_like;
 
@@ -106,6 +107,35 @@ impl Datetime {
         }
     }
 
+    /// The duration between this and an earlier datetime.
+    ///
+    /// Returns `None` if the two datetimes are not of the same kind.
+    pub fn duration_since(&self, earlier: Self) -> Option<Duration> {
+        Some(Duration::from(match (*self, earlier) {
+            (Datetime::Date(a), Datetime::Date(b)) => a - b,
+            (Datetime::Time(a), Datetime::Time(b)) => a - b,
+            (Datetime::Datetime(a), Datetime::Datetime(b)) => a - b,
+            _ => return None,
+        }))
+    }
+
+    /// Shift the datetime by a duration.
+    ///
+    /// Dates are only shifted by whole days and times wrap around at
+    /// midnight. Returns `None` if the result is out of range.
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        let duration = time::Duration::from(duration);
+        Some(match self {
+            Datetime::Date(date) => Datetime::Date(
+                date.checked_add(time::Duration::days(duration.whole_days()))?,
+            ),
+            Datetime::Time(time) => Datetime::Time(*time + duration),
+            Datetime::Datetime(datetime) => {
+                Datetime::Datetime(datetime.checked_add(duration)?)
+            }
+        })
+    }
+
     /// Create a datetime from year, month, and day.
     pub fn from_ymd(year: i32, month: u8, day: u8) -> Option<Self> {
         Some(Datetime::Date(
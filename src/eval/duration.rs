@@ -0,0 +1,106 @@
+use std::fmt::{self, Debug, Formatter};
+
+use ecow::{eco_format, EcoVec};
+
+use crate::eval::cast_from_value;
+use crate::util::pretty_array_like;
+
+/// A span of time, as produced by subtracting two datetimes.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Duration(time::Duration);
+
+impl Duration {
+    /// Create a duration from its components.
+    ///
+    /// Returns `None` if the total number of seconds overflows.
+    pub fn from_parts(
+        weeks: i64,
+        days: i64,
+        hours: i64,
+        minutes: i64,
+        seconds: i64,
+    ) -> Option<Self> {
+        let total = [(weeks, 604_800), (days, 86_400), (hours, 3_600), (minutes, 60)]
+            .into_iter()
+            .try_fold(seconds, |acc, (n, secs)| acc.checked_add(n.checked_mul(secs)?))?;
+        Some(Self(time::Duration::seconds(total)))
+    }
+
+    /// The duration in seconds.
+    pub fn seconds(&self) -> f64 {
+        self.0.as_seconds_f64()
+    }
+
+    /// The duration in minutes.
+    pub fn minutes(&self) -> f64 {
+        self.seconds() / 60.0
+    }
+
+    /// The duration in hours.
+    pub fn hours(&self) -> f64 {
+        self.seconds() / 3_600.0
+    }
+
+    /// The duration in days.
+    pub fn days(&self) -> f64 {
+        self.seconds() / 86_400.0
+    }
+
+    /// The duration in weeks.
+    pub fn weeks(&self) -> f64 {
+        self.seconds() / 604_800.0
+    }
+
+    /// The negated duration. Returns `None` on overflow.
+    pub fn checked_neg(self) -> Option<Self> {
+        self.0.checked_neg().map(Self)
+    }
+
+    /// The sum of two durations. Returns `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// The difference of two durations. Returns `None` on overflow.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+}
+
+impl From<time::Duration> for Duration {
+    fn from(duration: time::Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<Duration> for time::Duration {
+    fn from(duration: Duration) -> Self {
+        duration.0
+    }
+}
+
+impl Debug for Duration {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut rest = self.0.whole_seconds();
+        let mut parts = EcoVec::new();
+        for (unit, secs) in
+            [("weeks", 604_800), ("days", 86_400), ("hours", 3_600), ("minutes", 60)]
+        {
+            let n = rest / secs;
+            if n != 0 {
+                parts.push(eco_format!("{unit}: {n}"));
+            }
+            rest %= secs;
+        }
+
+        if rest != 0 || parts.is_empty() {
+            parts.push(eco_format!("seconds: {rest}"));
+        }
+
+        write!(f, "duration{}", &pretty_array_like(&parts, false))
+    }
+}
+
+cast_from_value! {
+    Duration: "duration",
+}
@@ -105,6 +105,8 @@ pub struct LangItems {
     pub math_frac: fn(num: Content, denom: Content) -> Content,
     /// A root in math: `√x`, `∛x` or `∜x`.
     pub math_root: fn(index: Option<Content>, radicand: Content) -> Content,
+    /// The MathML markup of an equation, if the content is one.
+    pub mathml: fn(&Content) -> Option<String>,
     /// Dispatch a method on a library value.
     pub library_method: fn(
         vm: &mut Vm,
@@ -152,6 +154,7 @@ impl Hash for LangItems {
         self.math_accent.hash(state);
         self.math_frac.hash(state);
         self.math_root.hash(state);
+        (self.mathml as usize).hash(state);
         (self.library_method as usize).hash(state);
     }
 }
@@ -1,10 +1,11 @@
 //! Methods on values.
 
-use ecow::EcoString;
+use ecow::{eco_format, EcoString};
 
-use super::{Args, Str, Value, Vm};
-use crate::diag::{At, SourceResult};
-use crate::eval::Datetime;
+use super::{array, cast_from_value, Args, Array, Dynamic, Str, Value, Vm};
+use crate::diag::{At, SourceResult, StrResult};
+use crate::eval::{Bytes, Datetime, Duration, Rng};
+use crate::geom::{Abs, Color, Length, MixSpace, Ratio};
 use crate::model::{Location, Selector};
 use crate::syntax::Span;
 
@@ -24,6 +25,31 @@ pub fn call(
             "lighten" => Value::Color(color.lighten(args.expect("amount")?)),
             "darken" => Value::Color(color.darken(args.expect("amount")?)),
             "negate" => Value::Color(color.negate()),
+            "mix" => {
+                let space = args.named("space")?.unwrap_or_default();
+                mix(color, args.all()?, space).at(span)?
+            }
+            "components" => Value::Array(match color {
+                Color::Luma(luma) => array![Value::Int(luma.0.into())],
+                Color::Rgba(rgba) => array![
+                    Value::Int(rgba.r.into()),
+                    Value::Int(rgba.g.into()),
+                    Value::Int(rgba.b.into()),
+                    Value::Int(rgba.a.into()),
+                ],
+                Color::Cmyk(cmyk) => [cmyk.c, cmyk.m, cmyk.y, cmyk.k]
+                    .into_iter()
+                    .map(|c| Value::Ratio(Ratio::new(c as f64 / 255.0)))
+                    .collect(),
+            }),
+            _ => return missing(),
+        },
+
+        Value::Length(length) => match method {
+            "pt" => Value::Float(absolute(length).at(span)?.to_pt()),
+            "mm" => Value::Float(absolute(length).at(span)?.to_mm()),
+            "cm" => Value::Float(absolute(length).at(span)?.to_cm()),
+            "inches" => Value::Float(absolute(length).at(span)?.to_inches()),
             _ => return missing(),
         },
 
@@ -75,6 +101,12 @@ pub fn call(
             "func" => content.func().into(),
             "has" => Value::Bool(content.has(&args.expect::<EcoString>("field")?)),
             "at" => content.at(&args.expect::<EcoString>("field")?, None).at(span)?,
+            "fields" => Value::Dict(
+                content
+                    .fields()
+                    .map(|(name, value)| (Str::from(name.clone()), value))
+                    .collect(),
+            ),
             "location" => content
                 .location()
                 .ok_or("this method can only be called on content returned by query(..)")
@@ -210,6 +242,29 @@ pub fn call(
                     }
                     _ => return missing(),
                 }
+            } else if let Some(bytes) = dynamic.downcast::<Bytes>() {
+                match method {
+                    "len" => Value::Int(bytes.len()),
+                    "at" => Value::Int(bytes.at(args.expect("index")?).at(span)?.into()),
+                    "slice" => {
+                        let start = args.expect("start")?;
+                        let mut end = args.eat()?;
+                        if end.is_none() {
+                            end = args.named("count")?.map(|c: i64| start + c);
+                        }
+                        Value::dynamic(bytes.slice(start, end).at(span)?)
+                    }
+                    _ => return missing(),
+                }
+            } else if let Some(&duration) = dynamic.downcast::<Duration>() {
+                match method {
+                    "seconds" => duration.seconds().into(),
+                    "minutes" => duration.minutes().into(),
+                    "hours" => duration.hours().into(),
+                    "days" => duration.days().into(),
+                    "weeks" => duration.weeks().into(),
+                    _ => return missing(),
+                }
             } else {
                 return (vm.items.library_method)(vm, &dynamic, method, args, span);
             }
@@ -252,6 +307,26 @@ pub fn call_mut(
             _ => return missing(),
         },
 
+        Value::Dyn(dynamic) => {
+            let Some(mut rng) = dynamic.downcast::<Rng>().copied() else {
+                return missing();
+            };
+            match method {
+                "next" => output = rng.next().into(),
+                "int" => {
+                    let min = args.expect("minimum")?;
+                    let max = args.expect("maximum")?;
+                    output = rng
+                        .int(min, max)
+                        .ok_or("minimum must be smaller than maximum")
+                        .at(span)?
+                        .into();
+                }
+                _ => return missing(),
+            }
+            *dynamic = Dynamic::new(rng);
+        }
+
         _ => return missing(),
     }
 
@@ -292,11 +367,76 @@ pub fn is_mutating(method: &str) -> bool {
     matches!(method, "push" | "pop" | "insert" | "remove")
 }
 
+/// Whether a method is only mutating for some types, so that it depends on
+/// the value whether it is mutating.
+pub fn is_sometimes_mutating(method: &str) -> bool {
+    matches!(method, "next" | "int")
+}
+
+/// Whether a method that is only mutating for some types mutates the given
+/// value.
+pub fn is_mutating_on(value: &Value, method: &str) -> bool {
+    is_sometimes_mutating(method)
+        && matches!(value, Value::Dyn(dynamic) if dynamic.is::<Rng>())
+}
+
 /// Whether a specific method is an accessor.
 pub fn is_accessor(method: &str) -> bool {
     matches!(method, "first" | "last" | "at")
 }
 
+/// Mix a color with others.
+///
+/// The weight that is not explicitly assigned is distributed evenly among the
+/// base color and the other colors without a weight.
+fn mix(base: Color, others: Vec<WeightedColor>, space: MixSpace) -> StrResult<Value> {
+    if others.iter().any(|other| other.1.map_or(false, |w| w.get() < 0.0)) {
+        return Err("weights must not be negative".into());
+    }
+
+    let explicit: f64 = others.iter().filter_map(|other| other.1).map(Ratio::get).sum();
+    if explicit > 1.0 {
+        return Err("weights must not add up to more than 100%".into());
+    }
+
+    let implicit = 1 + others.iter().filter(|other| other.1.is_none()).count();
+    let share = (1.0 - explicit) / implicit as f64;
+    let mut colors = vec![(base, share)];
+    for WeightedColor(color, weight) in others {
+        colors.push((color, weight.map_or(share, Ratio::get)));
+    }
+
+    Color::mix(&colors, space)
+        .map(Value::Color)
+        .ok_or_else(|| "at least one color must have a positive weight".into())
+}
+
+/// The absolute part of a length that has no font-relative part.
+fn absolute(length: Length) -> StrResult<Abs> {
+    if !length.em.is_zero() {
+        return Err(eco_format!(
+            "cannot convert {length:?} to an absolute length without a font size"
+        ));
+    }
+
+    Ok(length.abs)
+}
+
+/// A color with an optional weight.
+struct WeightedColor(Color, Option<Ratio>);
+
+cast_from_value! {
+    WeightedColor,
+    color: Color => Self(color, None),
+    array: Array => {
+        let mut iter = array.into_iter();
+        match (iter.next(), iter.next(), iter.next()) {
+            (Some(color), Some(weight), None) => Self(color.cast()?, Some(weight.cast()?)),
+            _ => Err("array must contain exactly a color and a weight")?,
+        }
+    },
+}
+
 /// The missing method error message.
 #[cold]
 fn missing_method(type_name: &str, method: &str) -> String {
@@ -306,7 +446,19 @@ fn missing_method(type_name: &str, method: &str) -> String {
 /// List the available methods for a type and whether they take arguments.
 pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
     match type_name {
-        "color" => &[("lighten", true), ("darken", true), ("negate", false)],
+        "color" => &[
+            ("lighten", true),
+            ("darken", true),
+            ("negate", false),
+            ("mix", true),
+            ("components", false),
+        ],
+        "length" => &[
+            ("pt", false),
+            ("mm", false),
+            ("cm", false),
+            ("inches", false),
+        ],
         "string" => &[
             ("len", false),
             ("at", true),
@@ -326,7 +478,13 @@ pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
             ("starts-with", true),
             ("trim", true),
         ],
-        "content" => &[("func", false), ("has", true), ("at", true), ("location", false)],
+        "content" => &[
+            ("func", false),
+            ("has", true),
+            ("at", true),
+            ("fields", false),
+            ("location", false),
+        ],
         "array" => &[
             ("all", true),
             ("any", true),
@@ -374,6 +532,15 @@ pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
             ("update", true),
         ],
         "state" => &[("display", true), ("at", true), ("final", true), ("update", true)],
+        "bytes" => &[("len", false), ("at", true), ("slice", true)],
+        "duration" => &[
+            ("seconds", false),
+            ("minutes", false),
+            ("hours", false),
+            ("days", false),
+            ("weeks", false),
+        ],
+        "random generator" => &[("next", false), ("int", true)],
         _ => &[],
     }
 }
@@ -13,11 +13,15 @@ mod str;
 #[macro_use]
 mod value;
 mod args;
+mod bytes;
 mod datetime;
+mod duration;
 mod func;
 mod methods;
 mod module;
 pub mod ops;
+mod package;
+mod random;
 mod scope;
 mod symbol;
 
@@ -26,12 +30,16 @@ pub use once_cell::sync::Lazy;
 
 pub use self::args::*;
 pub use self::array::*;
+pub use self::bytes::*;
 pub use self::cast::*;
 pub use self::datetime::*;
 pub use self::dict::*;
+pub use self::duration::*;
 pub use self::func::*;
 pub use self::library::*;
 pub use self::module::*;
+pub use self::package::*;
+pub use self::random::*;
 pub use self::scope::*;
 pub use self::str::*;
 pub use self::symbol::*;
@@ -48,7 +56,7 @@ use ecow::{EcoString, EcoVec};
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::diag::{
-    bail, error, At, SourceError, SourceResult, StrResult, Trace, Tracepoint,
+    bail, error, At, SourceDiagnostic, SourceResult, StrResult, Trace, Tracepoint,
 };
 use crate::model::{
     Content, Introspector, Label, Locator, Recipe, ShowableSelector, Styles, Transform,
@@ -58,7 +66,7 @@ use crate::syntax::ast::AstNode;
 use crate::syntax::{
     ast, parse_code, Source, SourceId, Span, Spanned, SyntaxKind, SyntaxNode,
 };
-use crate::util::PathExt;
+use crate::util::{hash128, PathExt};
 use crate::World;
 
 const MAX_ITERATIONS: usize = 10_000;
@@ -254,7 +262,7 @@ pub enum Flow {
 
 impl Flow {
     /// Return an error stating that this control flow is forbidden.
-    pub fn forbidden(&self) -> SourceError {
+    pub fn forbidden(&self) -> SourceDiagnostic {
         match *self {
             Self::Break(span) => {
                 error!(span, "cannot break outside of loop")
@@ -313,11 +321,14 @@ impl<'a> Route<'a> {
     }
 }
 
-/// Traces which values existed for the expression at a span.
+/// Traces which values existed for the expression at a span and collects
+/// warnings.
 #[derive(Default, Clone)]
 pub struct Tracer {
     span: Option<Span>,
     values: Vec<Value>,
+    warnings: Vec<SourceDiagnostic>,
+    warnings_set: HashSet<u128>,
 }
 
 impl Tracer {
@@ -326,7 +337,12 @@ impl Tracer {
 
     /// Create a new tracer, possibly with a span under inspection.
     pub fn new(span: Option<Span>) -> Self {
-        Self { span, values: vec![] }
+        Self { span, ..Self::default() }
+    }
+
+    /// The warnings emitted so far.
+    pub fn warnings(&self) -> &[SourceDiagnostic] {
+        &self.warnings
     }
 
     /// Get the traced values.
@@ -352,6 +368,24 @@ impl Tracer {
             self.values.push(v);
         }
     }
+
+    /// Emit a warning.
+    ///
+    /// Warnings that are emitted multiple times, for instance because the
+    /// document is laid out repeatedly, are only recorded once.
+    pub fn warn(&mut self, warning: SourceDiagnostic) {
+        if self.warnings_set.insert(hash128(&(&warning.span, &warning.message))) {
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Emit a warning unless one with the same key was already emitted, no
+    /// matter where and with which message.
+    pub fn warn_once(&mut self, key: u128, warning: SourceDiagnostic) {
+        if self.warnings_set.insert(key) {
+            self.warnings.push(warning);
+        }
+    }
 }
 
 /// Evaluate an expression.
@@ -1113,8 +1147,28 @@ impl Eval for ast::FuncCall {
                 }
                 (target.field(&field).at(field_span)?, args)
             } else {
-                let target = target.eval(vm)?;
-                let args = args.eval(vm)?;
+                // Whether a method like `next` mutates only becomes clear once
+                // the receiver is known. Thus, it is accessed mutably unless it
+                // is a temporary, which such methods can't mutate anyway.
+                let (target, args) =
+                    if methods::is_sometimes_mutating(&field) && target.is_place() {
+                        let args = args.eval(vm)?;
+                        let target = target.access(vm)?;
+                        if methods::is_mutating_on(target, &field) {
+                            return methods::call_mut(target, &field, args, span).trace(
+                                vm.world(),
+                                point,
+                                span,
+                            );
+                        }
+                        (target.clone(), args)
+                    } else {
+                        let value = target.eval(vm)?;
+                        if methods::is_mutating_on(&value, &field) {
+                            bail!(target.span(), "cannot mutate a temporary value");
+                        }
+                        (value, args.eval(vm)?)
+                    };
 
                 if !matches!(target, Value::Symbol(_) | Value::Module(_) | Value::Func(_))
                     || methods_on(target.type_name()).iter().any(|(m, _)| m == &field)
@@ -1627,6 +1681,10 @@ impl Eval for ast::ForLoop {
                 // Iterate over values of array.
                 iter!(for pattern in array);
             }
+            (ast::Pattern::Normal(_), Value::Dyn(dynamic)) if dynamic.is::<Bytes>() => {
+                // Iterate over the bytes as integers.
+                iter!(for pattern in dynamic.downcast::<Bytes>().unwrap().to_array());
+            }
             (ast::Pattern::Normal(_), _) => {
                 bail!(self.iter().span(), "cannot loop over {}", iter.type_name());
             }
@@ -1732,6 +1790,10 @@ fn import(
     accept_functions: bool,
 ) -> SourceResult<Module> {
     let path = match source {
+        Value::Str(path) if path.starts_with('@') => {
+            let spec = path.parse::<PackageSpec>().at(span)?;
+            return import_package(vm, spec, span);
+        }
         Value::Str(path) => path,
         Value::Module(module) => return Ok(module),
         v => {
@@ -1760,6 +1822,25 @@ fn import(
         .trace(world, point, span)
 }
 
+/// Import a package's entrypoint.
+fn import_package(vm: &mut Vm, spec: PackageSpec, span: Span) -> SourceResult<Module> {
+    let world = vm.world();
+    let root = world.package(&spec).at(span)?;
+    let id = world.resolve(&root.join(PACKAGE_ENTRYPOINT)).at(span)?;
+
+    // Prevent cyclic importing.
+    if vm.route.contains(id) {
+        bail!(span, "cyclic import");
+    }
+
+    // Evaluate the entrypoint.
+    let source = world.source(id);
+    let point = || Tracepoint::Import;
+    Ok(eval(world, vm.route, TrackedMut::reborrow_mut(&mut vm.vt.tracer), source)
+        .trace(world, point, span)?
+        .with_name(spec.name))
+}
+
 impl Eval for ast::LoopBreak {
     type Output = Value;
 
@@ -1818,6 +1899,23 @@ impl Access for ast::Expr {
     }
 }
 
+impl ast::Expr {
+    /// Whether the expression refers to a place that can be accessed instead
+    /// of evaluating to a temporary value.
+    fn is_place(&self) -> bool {
+        match self {
+            Self::Ident(_) | Self::FieldAccess(_) => true,
+            Self::Parenthesized(v) => v.expr().is_place(),
+            Self::FuncCall(v) => matches!(
+                v.callee(),
+                ast::Expr::FieldAccess(access)
+                    if methods::is_accessor(access.field().as_str())
+            ),
+            _ => false,
+        }
+    }
+}
+
 impl Access for ast::Ident {
     fn access<'a>(&self, vm: &'a mut Vm) -> SourceResult<&'a mut Value> {
         let span = self.span();
@@ -32,6 +32,12 @@ impl Module {
         }))
     }
 
+    /// Update the module's name.
+    pub fn with_name(mut self, name: impl Into<EcoString>) -> Self {
+        Arc::make_mut(&mut self.0).name = name.into();
+        self
+    }
+
     /// Update the module's scope.
     pub fn with_scope(mut self, scope: Scope) -> Self {
         Arc::make_mut(&mut self.0).scope = scope;
@@ -5,7 +5,7 @@ use std::fmt::Debug;
 
 use ecow::eco_format;
 
-use super::{format_str, Regex, Value};
+use super::{format_str, Bytes, Datetime, Duration, Dynamic, Regex, Value};
 use crate::diag::StrResult;
 use crate::geom::{Axes, Axis, GenAlign, Length, Numeric, PartialStroke, Rel, Smart};
 use Value::*;
@@ -61,6 +61,12 @@ pub fn neg(value: Value) -> StrResult<Value> {
         Ratio(v) => Ratio(-v),
         Relative(v) => Relative(-v),
         Fraction(v) => Fraction(-v),
+        Dyn(v) if v.is::<Duration>() => Value::dynamic(
+            v.downcast::<Duration>()
+                .unwrap()
+                .checked_neg()
+                .ok_or("duration is too large")?,
+        ),
         v => mismatch!("cannot apply '-' to {}", v),
     })
 }
@@ -128,6 +134,22 @@ pub fn add(lhs: Value, rhs: Value) -> StrResult<Value> {
                 }));
             };
 
+            if let (Some(a), Some(b)) = (a.downcast::<Bytes>(), b.downcast::<Bytes>()) {
+                return Ok(Value::dynamic(a.clone() + b.clone()));
+            }
+
+            if let Some(&duration) = b.downcast::<Duration>() {
+                if let Some(value) = shift(&a, duration) {
+                    return value;
+                }
+            }
+
+            if let Some(&duration) = a.downcast::<Duration>() {
+                if let Some(value) = shift(&b, duration) {
+                    return value;
+                }
+            }
+
             mismatch!("cannot add {} and {}", a, b);
         }
 
@@ -159,10 +181,48 @@ pub fn sub(lhs: Value, rhs: Value) -> StrResult<Value> {
 
         (Fraction(a), Fraction(b)) => Fraction(a - b),
 
+        (Dyn(a), Dyn(b)) => {
+            if let (Some(&a), Some(&b)) =
+                (a.downcast::<Datetime>(), b.downcast::<Datetime>())
+            {
+                return a.duration_since(b).map(Value::dynamic).ok_or_else(|| {
+                    "cannot subtract datetimes of different kinds".into()
+                });
+            }
+
+            if let Some(&duration) = b.downcast::<Duration>() {
+                let duration = duration.checked_neg().ok_or("duration is too large")?;
+                if let Some(value) = shift(&a, duration) {
+                    return value;
+                }
+            }
+
+            mismatch!("cannot subtract {1} from {0}", a, b);
+        }
+
         (a, b) => mismatch!("cannot subtract {1} from {0}", a, b),
     })
 }
 
+/// Shift a datetime or duration by a duration.
+fn shift(value: &Dynamic, duration: Duration) -> Option<StrResult<Value>> {
+    if let Some(&other) = value.downcast::<Duration>() {
+        Some(
+            other
+                .checked_add(duration)
+                .map(Value::dynamic)
+                .ok_or_else(|| "duration is too large".into()),
+        )
+    } else {
+        value.downcast::<Datetime>().map(|datetime| {
+            datetime
+                .checked_add(duration)
+                .map(Value::dynamic)
+                .ok_or_else(|| "datetime is out of range".into())
+        })
+    }
+}
+
 /// Compute the product of two values.
 pub fn mul(lhs: Value, rhs: Value) -> StrResult<Value> {
     Ok(match (lhs, rhs) {
@@ -380,6 +440,9 @@ pub fn compare(lhs: &Value, rhs: &Value) -> StrResult<Ordering> {
         (Relative(a), Relative(b)) => try_cmp_values(a, b)?,
         (Fraction(a), Fraction(b)) => a.cmp(b),
         (Str(a), Str(b)) => a.cmp(b),
+        (Dyn(a), Dyn(b)) if a.is::<Duration>() && b.is::<Duration>() => {
+            a.downcast::<Duration>().cmp(&b.downcast::<Duration>())
+        }
 
         // Some technically different things should be comparable.
         (Int(a), Float(b)) => try_cmp_values(&(*a as f64), b)?,
@@ -0,0 +1,116 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use ecow::{eco_format, EcoString};
+use unscanny::Scanner;
+
+/// The name of a package's entrypoint file, relative to its root directory.
+pub const PACKAGE_ENTRYPOINT: &str = "lib.typ";
+
+/// Identifies a package, as in `@preview/example:0.1.0`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PackageSpec {
+    /// The namespace the package lives in.
+    pub namespace: EcoString,
+    /// The name of the package within its namespace.
+    pub name: EcoString,
+    /// The package's version.
+    pub version: PackageVersion,
+}
+
+impl FromStr for PackageSpec {
+    type Err = EcoString;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut s = Scanner::new(s);
+        if !s.eat_if('@') {
+            return Err("package specification must start with '@'".into());
+        }
+
+        let namespace = s.eat_until('/');
+        if namespace.is_empty() {
+            return Err("package specification is missing namespace".into());
+        } else if !is_ident(namespace) {
+            return Err(eco_format!("`{namespace}` is not a valid package namespace"));
+        }
+
+        s.eat_if('/');
+
+        let name = s.eat_until(':');
+        if name.is_empty() {
+            return Err("package specification is missing name".into());
+        } else if !is_ident(name) {
+            return Err(eco_format!("`{name}` is not a valid package name"));
+        }
+
+        s.eat_if(':');
+
+        let version = s.after();
+        if version.is_empty() {
+            return Err("package specification is missing version".into());
+        }
+
+        Ok(Self {
+            namespace: namespace.into(),
+            name: name.into(),
+            version: version.parse()?,
+        })
+    }
+}
+
+impl Display for PackageSpec {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "@{}/{}:{}", self.namespace, self.name, self.version)
+    }
+}
+
+/// A package's version.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct PackageVersion {
+    /// The package's major version.
+    pub major: u32,
+    /// The package's minor version.
+    pub minor: u32,
+    /// The package's patch version.
+    pub patch: u32,
+}
+
+impl FromStr for PackageVersion {
+    type Err = EcoString;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('.');
+        let mut next = |kind: &str| -> Result<u32, EcoString> {
+            let part = parts
+                .next()
+                .filter(|s| !s.is_empty())
+                .ok_or_else(|| eco_format!("version number is missing {kind} version"))?;
+            part.parse::<u32>()
+                .map_err(|_| eco_format!("`{part}` is not a valid {kind} version"))
+        };
+
+        let major = next("major")?;
+        let minor = next("minor")?;
+        let patch = next("patch")?;
+        if let Some(rest) = parts.next() {
+            return Err(eco_format!(
+                "version number has unexpected fourth component: `{rest}`"
+            ));
+        }
+
+        Ok(Self { major, minor, patch })
+    }
+}
+
+impl Display for PackageVersion {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Whether a string is a valid namespace or package name.
+fn is_ident(string: &str) -> bool {
+    let mut chars = string.chars();
+    chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
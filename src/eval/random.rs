@@ -0,0 +1,59 @@
+use std::fmt::{self, Debug, Formatter};
+
+use crate::eval::cast_from_value;
+
+/// A seeded pseudo-random number generator.
+///
+/// This is a xorshift generator, so the same seed always yields the same
+/// sequence of numbers.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Create a generator from a seed.
+    pub fn new(seed: i64) -> Self {
+        // Scramble the seed so that similar seeds produce unrelated sequences
+        // and the state is never zero, where xorshift would get stuck.
+        let mut z = (seed as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        Self(if z == 0 { 0xACE5 } else { z })
+    }
+
+    /// Advance the generator and return the next raw 64 bits.
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Return a pseudo-random float between `0.0` (inclusive) and `1.0`
+    /// (exclusive).
+    pub fn next(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Return a pseudo-random integer between `min` (inclusive) and `max`
+    /// (exclusive).
+    pub fn int(&mut self, min: i64, max: i64) -> Option<i64> {
+        if min >= max {
+            return None;
+        }
+
+        let span = (max as i128 - min as i128) as u128;
+        let offset = (self.next_u64() as u128 * span) >> 64;
+        Some((min as i128 + offset as i128) as i64)
+    }
+}
+
+impl Debug for Rng {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad("random(..)")
+    }
+}
+
+cast_from_value! {
+    Rng: "random generator",
+}
@@ -145,7 +145,9 @@ impl Str {
             StrPattern::Str(pat) => {
                 self.0.match_indices(pat.as_str()).next().map(match_to_dict)
             }
-            StrPattern::Regex(re) => re.captures(self).map(captures_to_dict),
+            StrPattern::Regex(re) => {
+                re.captures(self).map(|caps| captures_to_dict(&caps))
+            }
         }
     }
 
@@ -161,7 +163,7 @@ impl Str {
                 .collect(),
             StrPattern::Regex(re) => re
                 .captures_iter(self)
-                .map(captures_to_dict)
+                .map(|caps| captures_to_dict(&caps))
                 .map(Value::Dict)
                 .collect(),
         }
@@ -272,16 +274,28 @@ impl Str {
         // the current string.
         let mut output = EcoString::with_capacity(self.as_str().len());
 
-        // Replace one match of a pattern with the replacement.
+        // Replace one match of a pattern with the replacement. For regex
+        // matches, the capture groups are available to expand references
+        // like `$1` in a replacement string.
         let mut last_match = 0;
-        let mut handle_match = |range: Range<usize>, dict: Dict| -> SourceResult<()> {
+        let mut handle_match = |range: Range<usize>,
+                                dict: Dict,
+                                caps: Option<&regex::Captures>|
+         -> SourceResult<()> {
             // Push everything until the match.
             output.push_str(&self[last_match..range.start]);
             last_match = range.end;
 
             // Determine and push the replacement.
             match &with {
-                Replacement::Str(s) => output.push_str(s),
+                Replacement::Str(s) => match caps {
+                    Some(caps) => {
+                        let mut expanded = String::new();
+                        caps.expand(s, &mut expanded);
+                        output.push_str(&expanded);
+                    }
+                    None => output.push_str(s),
+                },
                 Replacement::Func(func) => {
                     let args = Args::new(func.span(), [dict.into()]);
                     let piece = func.call_vm(vm, args)?.cast::<Str>().at(func.span())?;
@@ -298,14 +312,15 @@ impl Str {
             StrPattern::Str(pat) => {
                 for m in self.match_indices(pat.as_str()).take(count) {
                     let (start, text) = m;
-                    handle_match(start..start + text.len(), match_to_dict(m))?;
+                    handle_match(start..start + text.len(), match_to_dict(m), None)?;
                 }
             }
             StrPattern::Regex(re) => {
                 for caps in re.captures_iter(self).take(count) {
                     // Extract the entire match over all capture groups.
                     let m = caps.get(0).unwrap();
-                    handle_match(m.start()..m.end(), captures_to_dict(caps))?;
+                    let range = m.start()..m.end();
+                    handle_match(range, captures_to_dict(&caps), Some(&caps))?;
                 }
             }
         }
@@ -378,7 +393,7 @@ fn match_to_dict((start, text): (usize, &str)) -> Dict {
 }
 
 /// Convert regex captures to a dictionary.
-fn captures_to_dict(cap: regex::Captures) -> Dict {
+fn captures_to_dict(cap: &regex::Captures) -> Dict {
     let m = cap.get(0).expect("missing first match");
     dict! {
         "start" => Value::Int(m.start() as i64),
@@ -137,6 +137,59 @@ impl Value {
         format_str!("{:?}", self)
     }
 
+    /// Convert a JSON value into a Typst value.
+    ///
+    /// Objects become dictionaries and `null` becomes `none`. Numbers become
+    /// integers if they fit into one and floats otherwise.
+    pub fn from_json(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Self::None,
+            serde_json::Value::Bool(v) => Self::Bool(v),
+            serde_json::Value::Number(v) => match v.as_i64() {
+                Some(int) => Self::Int(int),
+                None => Self::Float(v.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(v) => Self::Str(v.into()),
+            serde_json::Value::Array(v) => {
+                Self::Array(v.into_iter().map(Self::from_json).collect())
+            }
+            serde_json::Value::Object(v) => Self::Dict(
+                v.into_iter()
+                    .map(|(key, value)| (key.into(), Self::from_json(value)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Convert the value into a JSON value.
+    ///
+    /// Dictionaries become objects and `none` becomes `null`. Symbols become
+    /// strings. Fails for values without a JSON equivalent, like lengths,
+    /// colors, and content, and for infinite or NaN floats.
+    pub fn to_json(&self) -> StrResult<serde_json::Value> {
+        Ok(match self {
+            Self::None => serde_json::Value::Null,
+            Self::Bool(v) => serde_json::Value::Bool(*v),
+            Self::Int(v) => serde_json::Value::from(*v),
+            Self::Float(v) => serde_json::Number::from_f64(*v)
+                .map(serde_json::Value::Number)
+                .ok_or("cannot convert infinite or NaN float to json")?,
+            Self::Symbol(v) => serde_json::Value::String(v.get().into()),
+            Self::Str(v) => serde_json::Value::String(v.as_str().into()),
+            Self::Array(v) => serde_json::Value::Array(
+                v.iter().map(Self::to_json).collect::<StrResult<_>>()?,
+            ),
+            Self::Dict(v) => serde_json::Value::Object(
+                v.iter()
+                    .map(|(key, value)| {
+                        Ok((String::from(key.as_str()), value.to_json()?))
+                    })
+                    .collect::<StrResult<_>>()?,
+            ),
+            v => Err(eco_format!("cannot convert {} to json", v.type_name()))?,
+        })
+    }
+
     /// Attach a span to the value, if possible.
     pub fn spanned(self, span: Span) -> Self {
         match self {
@@ -448,4 +501,22 @@ mod tests {
         test(dict!["one" => 1], "(one: 1)");
         test(dict!["two" => false, "one" => 1], "(two: false, one: 1)");
     }
+
+    #[test]
+    fn test_value_json() {
+        let value = Value::Dict(dict![
+            "name" => "typst",
+            "list" => array![1, 2.5, Value::None, true],
+        ]);
+        let json = value.to_json().unwrap();
+        assert_eq!(json["name"], "typst");
+        assert_eq!(json["list"][2], serde_json::Value::Null);
+        assert_eq!(Value::from_json(json), value);
+
+        assert_eq!(
+            Value::Length(Abs::pt(1.0).into()).to_json(),
+            Err("cannot convert length to json".into())
+        );
+        assert!(Value::Array(array![f64::INFINITY]).to_json().is_err());
+    }
 }
@@ -0,0 +1,625 @@
+//! Exporting into semantic HTML.
+
+use std::fmt::Write;
+
+use ecow::{eco_format, EcoString};
+
+use crate::doc::{Destination, Document, Frame, FrameItem, Meta, OutlineEntry, TextItem};
+use crate::geom::{Abs, Point, Size, Transform};
+use crate::model::Introspector;
+
+/// Export a document into an HTML document.
+///
+/// Since HTML reflows, only the document's structure is exported and the
+/// exact layout is discarded. The text of each page is split into blocks at
+/// larger vertical gaps and changes of the font size. Blocks that start at a
+/// heading become `<h1>` to `<h6>` elements. Blocks that start with a list
+/// marker, like a bullet or a number followed by a period, become list items
+/// and are nested by the markers' indentation. All other blocks become
+/// paragraphs. Text within links is wrapped in `<a>` elements and equations
+/// are exported as MathML from their content. Each page becomes a `<section>`
+/// with the id `page-{n}` so that links into the document keep working.
+///
+/// # Limitations
+/// Apart from headings, links, and equations, the structure is guessed from
+/// the layout. Hence, paragraphs that are set closely together or continue
+/// with a different font size are not told apart correctly, text in shapes,
+/// tables, and multiple columns is read line by line across the page, and
+/// list items are only recognized by the markers listed above.
+#[tracing::instrument(skip_all)]
+pub fn html(document: &Document) -> String {
+    let introspector = Introspector::new(&document.pages);
+    let outline = document.outline_in(&introspector);
+    write_html(document, &introspector, &outline)
+}
+
+/// Export a document with the given outline into an HTML document.
+fn write_html(
+    document: &Document,
+    introspector: &Introspector,
+    outline: &[OutlineEntry],
+) -> String {
+    let lang = document
+        .pages
+        .iter()
+        .find_map(first_text)
+        .map(|text| text.lang.as_str().to_string());
+
+    let mut html = String::from("<!DOCTYPE html>\n<html");
+    if let Some(lang) = lang {
+        write!(html, " lang=\"{}\"", escape(&lang)).unwrap();
+    }
+    html.push_str(">\n<head>\n<meta charset=\"utf-8\">\n");
+    if let Some(title) = &document.title {
+        writeln!(html, "<title>{}</title>", escape(title)).unwrap();
+    }
+    if !document.author.is_empty() {
+        let author = document.author.join(", ");
+        writeln!(html, "<meta name=\"author\" content=\"{}\">", escape(&author)).unwrap();
+    }
+    if !document.keywords.is_empty() {
+        let keywords = document.keywords.join(", ");
+        writeln!(html, "<meta name=\"keywords\" content=\"{}\">", escape(&keywords))
+            .unwrap();
+    }
+    html.push_str("</head>\n<body>\n");
+
+    for (i, frame) in document.pages.iter().enumerate() {
+        let mut page = Page {
+            introspector,
+            runs: vec![],
+            links: vec![],
+            equations: vec![],
+        };
+        page.collect(frame, Transform::identity());
+
+        let headings: Vec<_> = outline
+            .iter()
+            .filter(|entry| entry.position.page.get() == i + 1)
+            .collect();
+
+        writeln!(html, "<section id=\"page-{}\">", i + 1).unwrap();
+        for block in page.blocks(&headings) {
+            html.push_str(&block);
+            html.push('\n');
+        }
+        html.push_str("</section>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// The text and links of a page.
+struct Page<'a> {
+    /// Resolves links to locations in the document.
+    introspector: &'a Introspector,
+    /// The text runs in the order in which they appear in the frames.
+    runs: Vec<Run>,
+    /// The areas of links and their targets.
+    links: Vec<Link>,
+    /// The areas of equations and their MathML markup.
+    equations: Vec<Equation>,
+}
+
+/// A piece of text at a fixed position.
+struct Run {
+    /// The position of the run's start on the baseline.
+    pos: Point,
+    /// The width of the run.
+    width: Abs,
+    /// The font size of the run.
+    size: Abs,
+    /// The run's text.
+    text: EcoString,
+    /// Whether the text is MathML markup instead of plain text.
+    math: bool,
+}
+
+/// An equation's area on a page.
+struct Equation {
+    /// The top left corner of the area.
+    min: Point,
+    /// The bottom right corner of the area.
+    max: Point,
+    /// The equation's MathML markup.
+    markup: String,
+    /// Whether a run for the equation was already emitted.
+    emitted: bool,
+}
+
+/// A link area on a page.
+struct Link {
+    /// The top left corner of the area.
+    min: Point,
+    /// The bottom right corner of the area.
+    max: Point,
+    /// The target of the link.
+    href: EcoString,
+}
+
+impl Page<'_> {
+    /// Collect the text runs and links of a frame.
+    fn collect(&mut self, frame: &Frame, ts: Transform) {
+        for (pos, item) in frame.items() {
+            let ts = ts.pre_concat(Transform::translate(pos.x, pos.y));
+            match item {
+                FrameItem::Group(group) => {
+                    self.collect(&group.frame, ts.pre_concat(group.transform))
+                }
+                FrameItem::Text(text) => {
+                    let run = Run {
+                        pos: Point::zero().transform(ts),
+                        width: text.width(),
+                        size: text.size,
+                        text: text.text.clone(),
+                        math: false,
+                    };
+
+                    // The text of an equation is replaced by its markup at
+                    // the position of the equation's first run.
+                    let (x, y) =
+                        (run.pos.x + run.width / 2.0, run.pos.y - run.size / 4.0);
+                    match self.equations.iter_mut().find(|eq| {
+                        eq.min.x <= x && x <= eq.max.x && eq.min.y <= y && y <= eq.max.y
+                    }) {
+                        Some(eq) if eq.emitted => {}
+                        Some(eq) => {
+                            eq.emitted = true;
+                            self.runs.push(Run {
+                                pos: Point::new(eq.min.x, run.pos.y),
+                                width: eq.max.x - eq.min.x,
+                                text: eq.markup.as_str().into(),
+                                math: true,
+                                ..run
+                            });
+                        }
+                        None => self.runs.push(run),
+                    }
+                }
+                FrameItem::Meta(Meta::Elem(elem), size) => {
+                    // Metadata is prepended to a frame, so an equation is
+                    // known before its text is visited.
+                    if let Some(markup) = (item!(mathml))(elem) {
+                        let (min, max) = area(ts, *size);
+                        self.equations.push(Equation {
+                            min,
+                            max,
+                            markup,
+                            emitted: false,
+                        });
+                    }
+                }
+                FrameItem::Meta(Meta::Link(dest), size) => {
+                    let href = match dest {
+                        Destination::Url(url) => url.clone(),
+                        Destination::Position(pos) => eco_format!("#page-{}", pos.page),
+                        Destination::Location(loc) => {
+                            let pos = self.introspector.position(*loc);
+                            eco_format!("#page-{}", pos.page)
+                        }
+                    };
+
+                    let (min, max) = area(ts, *size);
+                    self.links.push(Link { min, max, href });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Group the runs into lines and the lines into HTML blocks.
+    fn blocks(&self, headings: &[&OutlineEntry]) -> Vec<String> {
+        let mut lines: Vec<Vec<&Run>> = vec![];
+        for run in &self.runs {
+            match lines.last_mut() {
+                Some(line) if (line[0].pos.y - run.pos.y).abs() < run.size / 4.0 => {
+                    line.push(run)
+                }
+                _ => lines.push(vec![run]),
+            }
+        }
+
+        let mut blocks: Vec<Block> = vec![];
+        let mut prev: Option<(Abs, Abs)> = None;
+        for line in lines {
+            let y = line[0].pos.y;
+            let size = line.iter().map(|run| run.size).max().unwrap_or_default();
+            let marker = Marker::detect(&line);
+            let continues = marker.is_none()
+                && prev.map_or(false, |(prev_y, prev_size)| {
+                    prev_size == size && y > prev_y && y - prev_y < size * 1.6
+                })
+                && blocks.last().map_or(false, |block| {
+                    // The wrapped lines of a list item are indented to the
+                    // start of its body.
+                    block
+                        .marker
+                        .map_or(true, |marker| indent(&line) > marker.body - size / 4.0)
+                });
+
+            match blocks.last_mut() {
+                Some(block) if continues => block.lines.push(line),
+                _ => blocks.push(Block { lines: vec![line], marker }),
+            }
+
+            prev = Some((y, size));
+        }
+
+        // A heading starts the first block whose baseline lies below the
+        // heading's position.
+        let mut levels = vec![None; blocks.len()];
+        for heading in headings {
+            let y = heading.position.point.y;
+            if let Some(i) = blocks.iter().position(|block| block.lines[0][0].pos.y >= y)
+            {
+                levels[i].get_or_insert(heading.level.get().min(6));
+            }
+        }
+
+        let mut out = vec![];
+        let mut lists: Vec<Marker> = vec![];
+        for (block, level) in blocks.iter().zip(levels) {
+            // Headings take precedence over list markers as numbered headings
+            // start with a number, too.
+            let Some(marker) = block.marker.filter(|_| level.is_none()) else {
+                while let Some(list) = lists.pop() {
+                    close_list(&mut out, list);
+                }
+
+                // A block equation stands on its own.
+                if let [line] = block.lines.as_slice() {
+                    if let [run] = line.as_slice() {
+                        if run.math && run.text.starts_with("<math display=\"block\"") {
+                            out.push(run.text.to_string());
+                            continue;
+                        }
+                    }
+                }
+
+                let tag = match level {
+                    Some(level) => format!("h{level}"),
+                    None => "p".into(),
+                };
+                out.push(format!(
+                    "<{tag}>{}</{tag}>",
+                    self.block_text(&block.lines, false)
+                ));
+                continue;
+            };
+
+            // Close the lists that are nested deeper than the item and a list
+            // of a different kind at the same depth.
+            let tolerance = marker.size / 4.0;
+            while let Some(list) = lists.last() {
+                let deeper = list.x > marker.x + tolerance;
+                let other =
+                    list.x > marker.x - tolerance && list.ordered != marker.ordered;
+                if !deeper && !other {
+                    break;
+                }
+                close_list(&mut out, lists.pop().unwrap());
+            }
+
+            match lists.last() {
+                Some(list) if list.x > marker.x - tolerance => {
+                    out.last_mut().unwrap().push_str("</li>");
+                }
+                _ => {
+                    out.push(match (marker.ordered, marker.number) {
+                        (false, _) => "<ul>".into(),
+                        (true, Some(number)) if number != 1 => {
+                            format!("<ol start=\"{number}\">")
+                        }
+                        (true, _) => "<ol>".into(),
+                    });
+                    lists.push(marker);
+                }
+            }
+
+            out.push(format!("<li>{}", self.block_text(&block.lines, true)));
+        }
+
+        while let Some(list) = lists.pop() {
+            close_list(&mut out, list);
+        }
+
+        out
+    }
+
+    /// The HTML for the text of a block, with links. If `skip_marker` is set,
+    /// the block's first run is left out.
+    fn block_text(&self, block: &[Vec<&Run>], skip_marker: bool) -> String {
+        let mut out = String::new();
+        let mut open: Option<&str> = None;
+        let mut skip = skip_marker;
+        for (i, line) in block.iter().enumerate() {
+            let mut end: Option<Abs> = None;
+            for (k, run) in line.iter().enumerate() {
+                let blank = run.text.trim().is_empty();
+                if skip {
+                    skip = blank;
+                    continue;
+                }
+
+                let href = self.link_at(run).map(|link| link.href.as_str());
+                if open != href && open.take().is_some() {
+                    out.push_str("</a>");
+                }
+
+                // Separate lines and runs that are apart on the same line,
+                // unless there already is a space.
+                let apart = end.map_or(false, |end| run.pos.x - end > run.size / 8.0);
+                if ((i > 0 && k == 0) || apart)
+                    && !blank
+                    && !out.is_empty()
+                    && !out.ends_with(' ')
+                    && !out.ends_with('-')
+                {
+                    out.push(' ');
+                }
+
+                if open.is_none() {
+                    if let Some(href) = href {
+                        write!(out, "<a href=\"{}\">", escape(href)).unwrap();
+                        open = Some(href);
+                    }
+                }
+
+                if run.math {
+                    out.push_str(&run.text);
+                } else {
+                    out.push_str(&escape(run.text.as_str()));
+                }
+                end = Some(run.pos.x + run.width);
+            }
+        }
+
+        if open.is_some() {
+            out.push_str("</a>");
+        }
+
+        out.trim().to_string()
+    }
+
+    /// The link whose area contains the middle of a run.
+    fn link_at(&self, run: &Run) -> Option<&Link> {
+        let x = run.pos.x + run.width / 2.0;
+        let y = run.pos.y - run.size / 4.0;
+        self.links.iter().find(|link| {
+            link.min.x <= x && x <= link.max.x && link.min.y <= y && y <= link.max.y
+        })
+    }
+}
+
+/// Lines of text that form an HTML block.
+struct Block<'a> {
+    /// The runs of each line.
+    lines: Vec<Vec<&'a Run>>,
+    /// The marker if the block is a list item.
+    marker: Option<Marker>,
+}
+
+/// The marker of a list item.
+#[derive(Copy, Clone)]
+struct Marker {
+    /// Whether the marker is a number instead of a bullet.
+    ordered: bool,
+    /// The number of an ordered marker.
+    number: Option<usize>,
+    /// The horizontal position of the marker.
+    x: Abs,
+    /// The horizontal position of the item's body.
+    body: Abs,
+    /// The font size of the marker.
+    size: Abs,
+}
+
+impl Marker {
+    /// Detect a list marker at the start of a line.
+    ///
+    /// Markers are laid out apart from the item's body and thus form a run
+    /// of their own, followed by the body on the same line.
+    fn detect(line: &[&Run]) -> Option<Self> {
+        let mut runs = line.iter().filter(|run| !run.text.trim().is_empty());
+        let first = runs.next()?;
+        let body = runs.next()?;
+        if body.pos.x <= first.pos.x + first.width {
+            return None;
+        }
+
+        let text = first.text.trim();
+        let (ordered, number) = if matches!(text, "•" | "‣" | "–" | "-" | "◦") {
+            (false, None)
+        } else {
+            let digits = text.strip_suffix(|c| matches!(c, '.' | ')'))?;
+            if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            (true, digits.parse().ok())
+        };
+
+        Some(Self {
+            ordered,
+            number,
+            x: first.pos.x,
+            body: body.pos.x,
+            size: first.size,
+        })
+    }
+}
+
+/// The top left and bottom right corners of a transformed area.
+fn area(ts: Transform, size: Size) -> (Point, Point) {
+    let a = Point::zero().transform(ts);
+    let b = size.to_point().transform(ts);
+    (Point::new(a.x.min(b.x), a.y.min(b.y)), Point::new(a.x.max(b.x), a.y.max(b.y)))
+}
+
+/// Close the last item of a list and the list itself.
+fn close_list(out: &mut Vec<String>, list: Marker) {
+    if let Some(last) = out.last_mut() {
+        last.push_str("</li>");
+    }
+    out.push(if list.ordered { "</ol>" } else { "</ul>" }.into());
+}
+
+/// The horizontal position of the first visible run in a line.
+fn indent(line: &[&Run]) -> Abs {
+    line.iter()
+        .find(|run| !run.text.trim().is_empty())
+        .map_or(line[0].pos.x, |run| run.pos.x)
+}
+
+/// The first text item in a frame.
+fn first_text(frame: &Frame) -> Option<&TextItem> {
+    frame.items().find_map(|(_, item)| match item {
+        FrameItem::Group(group) => first_text(&group.frame),
+        FrameItem::Text(text) => Some(text),
+        _ => None,
+    })
+}
+
+/// Escape text for use in HTML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::doc::{Lang, Position};
+    use crate::font::Font;
+    use crate::geom::Color;
+
+    /// Export a page with text runs at the given positions.
+    fn export(runs: &[(f64, f64, &str)], outline: &[OutlineEntry]) -> String {
+        let path =
+            concat!(env!("CARGO_MANIFEST_DIR"), "/assets/fonts/LinLibertine_R.ttf");
+        let font = Font::new(std::fs::read(path).unwrap().into(), 0).unwrap();
+
+        let mut frame = Frame::new(Size::new(Abs::pt(200.0), Abs::pt(200.0)));
+        for &(x, y, text) in runs {
+            let item = TextItem {
+                font: font.clone(),
+                size: Abs::pt(10.0),
+                fill: Color::BLACK.into(),
+                lang: Lang::ENGLISH,
+                text: text.into(),
+                glyphs: vec![],
+            };
+            frame.push(Point::new(Abs::pt(x), Abs::pt(y)), FrameItem::Text(item));
+        }
+
+        let document = Document { pages: vec![frame], ..Default::default() };
+        let introspector = Introspector::new(&document.pages);
+        let html = write_html(&document, &introspector, outline);
+        let start = html.find("<section id=\"page-1\">\n").unwrap() + 22;
+        let end = html.find("</section>").unwrap();
+        html[start..end].to_string()
+    }
+
+    #[test]
+    fn test_html_paragraphs() {
+        let runs = [
+            (0.0, 10.0, "Hello"),
+            (20.0, 10.0, " "),
+            (22.0, 10.0, "world"),
+            (0.0, 22.0, "again"),
+            (0.0, 50.0, "Next"),
+        ];
+        assert_eq!(export(&runs, &[]), "<p>Hello world again</p>\n<p>Next</p>\n");
+    }
+
+    #[test]
+    fn test_html_headings() {
+        let heading = OutlineEntry {
+            title: "Intro".into(),
+            level: NonZeroUsize::new(2).unwrap(),
+            position: Position {
+                page: NonZeroUsize::new(1).unwrap(),
+                point: Point::zero(),
+            },
+        };
+        let runs = [(0.0, 12.0, "1."), (14.0, 12.0, "Intro"), (0.0, 40.0, "Body")];
+        assert_eq!(export(&runs, &[heading]), "<h2>1. Intro</h2>\n<p>Body</p>\n");
+    }
+
+    #[test]
+    fn test_html_equations() {
+        let run = |x, y, text: &str, math| Run {
+            pos: Point::new(Abs::pt(x), Abs::pt(y)),
+            width: Abs::pt(10.0),
+            size: Abs::pt(10.0),
+            text: text.into(),
+            math,
+        };
+
+        let introspector = Introspector::new(&[]);
+        let page = Page {
+            introspector: &introspector,
+            runs: vec![
+                run(0.0, 10.0, "a<b", false),
+                run(20.0, 10.0, "<math display=\"inline\"><mi>x</mi></math>", true),
+                run(0.0, 40.0, "<math display=\"block\"><mi>y</mi></math>", true),
+            ],
+            links: vec![],
+            equations: vec![],
+        };
+
+        assert_eq!(
+            page.blocks(&[]),
+            [
+                "<p>a&lt;b <math display=\"inline\"><mi>x</mi></math></p>",
+                "<math display=\"block\"><mi>y</mi></math>",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_html_lists() {
+        let runs = [
+            (0.0, 10.0, "•"),
+            (8.0, 10.0, "One"),
+            (0.0, 22.0, "•"),
+            (8.0, 22.0, "Two"),
+            (8.0, 34.0, "wrapped"),
+            (12.0, 46.0, "‣"),
+            (20.0, 46.0, "Deep"),
+            (0.0, 58.0, "3."),
+            (10.0, 58.0, "Third"),
+            (0.0, 70.0, "4."),
+            (10.0, 70.0, "Fourth"),
+            (0.0, 90.0, "End"),
+        ];
+        assert_eq!(
+            export(&runs, &[]),
+            concat!(
+                "<ul>\n",
+                "<li>One</li>\n",
+                "<li>Two wrapped\n",
+                "<ul>\n",
+                "<li>Deep</li>\n",
+                "</ul></li>\n",
+                "</ul>\n",
+                "<ol start=\"3\">\n",
+                "<li>Third</li>\n",
+                "<li>Fourth</li>\n",
+                "</ol>\n",
+                "<p>End</p>\n",
+            )
+        );
+    }
+}
@@ -1,7 +1,11 @@
 //! Exporting into external formats.
 
+mod html;
 mod pdf;
 mod render;
+mod svg;
 
-pub use self::pdf::pdf;
-pub use self::render::render;
+pub use self::html::html;
+pub use self::pdf::{pdf, pdf_with_options, PdfColorSpace, PdfOptions, PdfStandard};
+pub use self::render::{render, render_merged};
+pub use self::svg::svg;
@@ -0,0 +1,132 @@
+use pdf_writer::types::FunctionShadingType;
+use pdf_writer::{Finish, Ref};
+
+use super::{AbsExt, PdfColorSpace, PdfContext, RefExt};
+use crate::geom::{Color, LinearGradient, Point, Ratio, Transform};
+
+/// A gradient painted across a specific area of a page.
+///
+/// PDF shading patterns live in the page's default coordinate space, so the
+/// same gradient needs a separate pattern for every place it is used in.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct PdfGradient {
+    /// The gradient to paint.
+    pub gradient: LinearGradient,
+    /// The start of the gradient's axis in the painted area's coordinates.
+    pub start: Point,
+    /// The end of the gradient's axis in the painted area's coordinates.
+    pub end: Point,
+    /// Maps from the painted area's coordinates to the page's coordinates.
+    pub transform: Transform,
+}
+
+/// Write all used gradients as shading patterns.
+#[tracing::instrument(skip_all)]
+pub fn write_gradients(ctx: &mut PdfContext) {
+    let gradients: Vec<_> = ctx.gradient_map.items().cloned().collect();
+    for gradient in gradients {
+        let pattern_ref = ctx.alloc.bump();
+        ctx.gradient_refs.push(pattern_ref);
+
+        let function_ref = write_function(ctx, &gradient.gradient.stops);
+
+        let mut pattern = ctx.writer.shading_pattern(pattern_ref);
+        let mut shading = pattern.function_shading();
+        shading.shading_type(FunctionShadingType::Axial);
+        match ctx.options.color_space {
+            PdfColorSpace::Preserve => {
+                shading.color_space().srgb();
+            }
+            PdfColorSpace::Cmyk => {
+                shading.color_space().device_cmyk();
+            }
+        }
+
+        let PdfGradient { start, end, .. } = &gradient;
+        shading.coords([
+            start.x.to_f32(),
+            start.y.to_f32(),
+            end.x.to_f32(),
+            end.y.to_f32(),
+        ]);
+        shading.function(function_ref);
+        shading.extend([true, true]);
+        shading.finish();
+
+        let Transform { sx, ky, kx, sy, tx, ty } = gradient.transform;
+        pattern.matrix([
+            sx.get() as _,
+            ky.get() as _,
+            kx.get() as _,
+            sy.get() as _,
+            tx.to_f32(),
+            ty.to_f32(),
+        ]);
+    }
+}
+
+/// Write a function interpolating between the gradient's stops over the
+/// domain `[0, 1]`.
+///
+/// Each pair of adjacent stops becomes a linear function. These are then
+/// stitched together at the stops' offsets.
+fn write_function(ctx: &mut PdfContext, stops: &[(Ratio, Color)]) -> Ref {
+    let mut stops = stops.to_vec();
+    if let Some(&(offset, color)) = stops.first() {
+        if offset > Ratio::zero() {
+            stops.insert(0, (Ratio::zero(), color));
+        }
+    }
+
+    if let Some(&(offset, color)) = stops.last() {
+        if offset < Ratio::one() {
+            stops.push((Ratio::one(), color));
+        }
+    }
+
+    let mut functions = vec![];
+    for window in stops.windows(2) {
+        let [(_, first), (_, second)] = window else { continue };
+        let c0 = components(ctx, *first);
+        let c1 = components(ctx, *second);
+        let function_ref = ctx.alloc.bump();
+        ctx.writer
+            .exponential_function(function_ref)
+            .domain([0.0, 1.0])
+            .c0(c0)
+            .c1(c1)
+            .n(1.0);
+        functions.push(function_ref);
+    }
+
+    let bounds = stops
+        .iter()
+        .skip(1)
+        .take(functions.len().saturating_sub(1))
+        .map(|(offset, _)| offset.get() as f32);
+
+    let function_ref = ctx.alloc.bump();
+    ctx.writer
+        .stitching_function(function_ref)
+        .domain([0.0, 1.0])
+        .functions(functions.iter().copied())
+        .bounds(bounds)
+        .encode(functions.iter().flat_map(|_| [0.0, 1.0]));
+
+    function_ref
+}
+
+/// The components of a color in the shading's color space.
+fn components(ctx: &PdfContext, color: Color) -> Vec<f32> {
+    let f = |c| c as f32 / 255.0;
+    match ctx.options.color_space {
+        PdfColorSpace::Preserve => {
+            let c = color.to_rgba();
+            vec![f(c.r), f(c.g), f(c.b)]
+        }
+        PdfColorSpace::Cmyk => {
+            let c = color.to_cmyk();
+            vec![f(c.c), f(c.m), f(c.y), f(c.k)]
+        }
+    }
+}
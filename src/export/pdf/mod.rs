@@ -1,9 +1,11 @@
 //! Exporting into PDF documents.
 
 mod font;
+mod gradient;
 mod image;
 mod outline;
 mod page;
+mod tags;
 
 use std::cmp::Eq;
 use std::collections::{BTreeMap, HashMap};
@@ -11,30 +13,121 @@ use std::hash::Hash;
 
 use ecow::EcoString;
 use pdf_writer::types::Direction;
-use pdf_writer::{Finish, Name, PdfWriter, Ref, TextStr};
+use pdf_writer::{Filter, Finish, Name, PdfWriter, Ref, TextStr};
 use xmp_writer::{LangId, RenditionClass, XmpWriter};
 
+use self::gradient::PdfGradient;
 use self::page::Page;
+use self::tags::{Heading, Tag};
 use crate::doc::{Document, Lang};
+use crate::eval::Datetime;
 use crate::font::Font;
 use crate::geom::{Abs, Dir, Em};
 use crate::image::Image;
 use crate::model::Introspector;
+use crate::util::Buffer;
 
 /// Export a document into a PDF file.
 ///
 /// Returns the raw bytes making up the PDF file.
-#[tracing::instrument(skip_all)]
 pub fn pdf(document: &Document) -> Vec<u8> {
-    let mut ctx = PdfContext::new(document);
+    pdf_with_options(document, PdfOptions::default())
+}
+
+/// Export a document into a PDF file with custom options.
+///
+/// Returns the raw bytes making up the PDF file.
+#[tracing::instrument(skip_all)]
+pub fn pdf_with_options(document: &Document, options: PdfOptions) -> Vec<u8> {
+    let mut ctx = PdfContext::new(document, options);
     page::construct_pages(&mut ctx, &document.pages);
     font::write_fonts(&mut ctx);
     image::write_images(&mut ctx);
+    gradient::write_gradients(&mut ctx);
     page::write_page_tree(&mut ctx);
     write_catalog(&mut ctx);
     ctx.writer.finish()
 }
 
+/// Settings for PDF export.
+#[derive(Debug, Clone, Hash)]
+pub struct PdfOptions {
+    /// The color space in which colors are written into content streams.
+    pub color_space: PdfColorSpace,
+    /// A PDF/A conformance level the file should claim.
+    ///
+    /// PDF/A requires an output intent, so the conformance is only claimed if
+    /// an [ICC profile](Self::icc_profile) is given as well and ignored
+    /// otherwise. Apart from that, the file is not validated against the
+    /// standard: fonts are always embedded, but it is up to the caller to
+    /// avoid content that PDF/A forbids, like images with unsupported color
+    /// profiles.
+    pub standard: Option<PdfStandard>,
+    /// An ICC profile describing the intended output device.
+    ///
+    /// If present, it is embedded as the document's output intent. The profile
+    /// must match the chosen color space, that is, it should be a CMYK profile
+    /// when exporting with [`PdfColorSpace::Cmyk`] and an RGB profile
+    /// otherwise.
+    pub icc_profile: Option<Buffer>,
+    /// Whether to generate bookmarks from the document's headings. Viewers
+    /// typically show them in a navigation sidebar.
+    pub bookmarks: bool,
+    /// Whether to tag the content with a structure tree for assistive
+    /// technology. Paragraphs and headings are inferred from the laid out
+    /// text and images become figures with their alternative text. Shapes
+    /// are marked as decorative artifacts.
+    pub tagged: bool,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        Self {
+            color_space: PdfColorSpace::default(),
+            standard: None,
+            icc_profile: None,
+            bookmarks: true,
+            tagged: false,
+        }
+    }
+}
+
+/// The color space in which colors are written into a PDF.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PdfColorSpace {
+    /// Write colors in the space they were specified in.
+    #[default]
+    Preserve,
+    /// Convert all colors to device CMYK.
+    Cmyk,
+}
+
+/// A PDF/A conformance level.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PdfStandard {
+    /// PDF/A-2b (basic conformance).
+    A2b,
+    /// PDF/A-3b (basic conformance).
+    A3b,
+}
+
+impl PdfStandard {
+    /// The part of the PDF/A standard.
+    fn part(self) -> i32 {
+        match self {
+            Self::A2b => 2,
+            Self::A3b => 3,
+        }
+    }
+
+    /// The conformance level within the part.
+    fn conformance(self) -> &'static str {
+        match self {
+            Self::A2b | Self::A3b => "B",
+        }
+    }
+}
+
 /// Identifies the color space definitions.
 const SRGB: Name<'static> = Name(b"srgb");
 const D65_GRAY: Name<'static> = Name(b"d65gray");
@@ -42,17 +135,24 @@ const D65_GRAY: Name<'static> = Name(b"d65gray");
 /// Context for exporting a whole PDF document.
 pub struct PdfContext<'a> {
     document: &'a Document,
+    options: PdfOptions,
     introspector: Introspector,
     writer: PdfWriter,
     pages: Vec<Page>,
+    /// For each page the items that were tagged in its content stream.
+    page_tags: Vec<Vec<Tag>>,
+    /// For each page the areas of its headings.
+    page_headings: Vec<Vec<Heading>>,
     page_heights: Vec<f32>,
     alloc: Ref,
     page_tree_ref: Ref,
     font_refs: Vec<Ref>,
     image_refs: Vec<Ref>,
+    gradient_refs: Vec<Ref>,
     page_refs: Vec<Ref>,
     font_map: Remapper<Font>,
     image_map: Remapper<Image>,
+    gradient_map: Remapper<PdfGradient>,
     /// For each font a mapping from used glyphs to their text representation.
     /// May contain multiple chars in case of ligatures or similar things. The
     /// same glyph can have a different text representation within one document,
@@ -64,22 +164,27 @@ pub struct PdfContext<'a> {
 }
 
 impl<'a> PdfContext<'a> {
-    fn new(document: &'a Document) -> Self {
+    fn new(document: &'a Document, options: PdfOptions) -> Self {
         let mut alloc = Ref::new(1);
         let page_tree_ref = alloc.bump();
         Self {
             document,
+            options,
             introspector: Introspector::new(&document.pages),
             writer: PdfWriter::new(),
             pages: vec![],
+            page_tags: vec![],
+            page_headings: vec![],
             page_heights: vec![],
             alloc,
             page_tree_ref,
             page_refs: vec![],
             font_refs: vec![],
             image_refs: vec![],
+            gradient_refs: vec![],
             font_map: Remapper::new(),
             image_map: Remapper::new(),
+            gradient_map: Remapper::new(),
             glyph_sets: HashMap::new(),
             languages: HashMap::new(),
         }
@@ -102,7 +207,11 @@ fn write_catalog(ctx: &mut PdfContext) {
     };
 
     // Write the outline tree.
-    let outline_root_id = outline::write_outline(ctx);
+    let outline_root_id =
+        if ctx.options.bookmarks { outline::write_outline(ctx) } else { None };
+
+    // Write the structure tree.
+    let struct_tree_root_id = tags::write_structure_tree(ctx);
 
     // Write the document information.
     let mut info = ctx.writer.document_info(ctx.alloc.bump());
@@ -117,6 +226,20 @@ fn write_catalog(ctx: &mut PdfContext) {
         info.author(TextStr(&authors.join(", ")));
         xmp.creator(authors.iter().map(|s| s.as_str()));
     }
+
+    let keywords = &ctx.document.keywords;
+    if !keywords.is_empty() {
+        let joined = keywords.join(", ");
+        info.keywords(TextStr(&joined));
+        xmp.pdf_keywords(&joined);
+    }
+    if let Some(date) = ctx.document.date.and_then(pdf_date) {
+        info.creation_date(date);
+    }
+    if let Some(date) = ctx.document.date.and_then(xmp_date) {
+        xmp.create_date(date);
+    }
+
     info.creator(TextStr("Typst"));
     info.finish();
     xmp.creator_tool("Typst");
@@ -125,6 +248,11 @@ fn write_catalog(ctx: &mut PdfContext) {
     xmp.language(ctx.languages.keys().map(|lang| LangId(lang.as_str())));
     xmp.rendition_class(RenditionClass::Proof);
     xmp.pdf_version("1.7");
+    let standard = ctx.options.standard.filter(|_| ctx.options.icc_profile.is_some());
+    if let Some(standard) = standard {
+        xmp.pdfa_part(standard.part());
+        xmp.pdfa_conformance(standard.conformance());
+    }
 
     let xmp_buf = xmp.finish(None);
     let meta_ref = ctx.alloc.bump();
@@ -133,12 +261,42 @@ fn write_catalog(ctx: &mut PdfContext) {
     meta_stream.pair(Name(b"Subtype"), Name(b"XML"));
     meta_stream.finish();
 
+    // Write the output intent's color profile.
+    let intent = ctx.options.icc_profile.as_ref().map(|profile| {
+        let icc_ref = ctx.alloc.bump();
+        let compressed = deflate(profile);
+        let mut stream = ctx.writer.icc_profile(icc_ref, &compressed);
+        stream.filter(Filter::FlateDecode);
+        match ctx.options.color_space {
+            PdfColorSpace::Preserve => {
+                stream.n(3);
+                stream.alternate().srgb();
+            }
+            PdfColorSpace::Cmyk => {
+                stream.n(4);
+                stream.alternate().device_cmyk();
+            }
+        }
+        stream.finish();
+        icc_ref
+    });
+
     // Write the document catalog.
     let mut catalog = ctx.writer.catalog(ctx.alloc.bump());
     catalog.pages(ctx.page_tree_ref);
-    catalog.viewer_preferences().direction(dir);
+    let mut preferences = catalog.viewer_preferences();
+    preferences.direction(dir);
+    if ctx.options.tagged && ctx.document.title.is_some() {
+        preferences.pair(Name(b"DisplayDocTitle"), true);
+    }
+    preferences.finish();
     catalog.pair(Name(b"Metadata"), meta_ref);
 
+    if let Some(struct_tree_root_id) = struct_tree_root_id {
+        catalog.pair(Name(b"StructTreeRoot"), struct_tree_root_id);
+        catalog.insert(Name(b"MarkInfo")).dict().pair(Name(b"Marked"), true);
+    }
+
     if let Some(outline_root_id) = outline_root_id {
         catalog.outlines(outline_root_id);
     }
@@ -146,6 +304,59 @@ fn write_catalog(ctx: &mut PdfContext) {
     if let Some(lang) = lang {
         catalog.lang(TextStr(lang.as_str()));
     }
+
+    if let Some(icc_ref) = intent {
+        let subtype = if ctx.options.standard.is_some() {
+            Name(b"GTS_PDFA1")
+        } else {
+            Name(b"GTS_PDFX")
+        };
+
+        let mut intents = catalog.insert(Name(b"OutputIntents")).array();
+        let mut intent = intents.push().dict();
+        intent.pair(Name(b"Type"), Name(b"OutputIntent"));
+        intent.pair(Name(b"S"), subtype);
+        intent.pair(Name(b"OutputConditionIdentifier"), TextStr("Custom"));
+        intent.pair(Name(b"DestOutputProfile"), icc_ref);
+        intent.finish();
+        intents.finish();
+    }
+}
+
+/// Convert a datetime into a PDF date. Only datetimes with a year can be
+/// converted.
+fn pdf_date(datetime: Datetime) -> Option<pdf_writer::Date> {
+    let mut date = pdf_writer::Date::new(datetime.year()?.try_into().ok()?);
+    if let Some(month) = datetime.month() {
+        date = date.month(month);
+    }
+    if let Some(day) = datetime.day() {
+        date = date.day(day);
+    }
+    if let Some(hour) = datetime.hour() {
+        date = date.hour(hour);
+    }
+    if let Some(minute) = datetime.minute() {
+        date = date.minute(minute);
+    }
+    if let Some(second) = datetime.second() {
+        date = date.second(second);
+    }
+    Some(date)
+}
+
+/// Convert a datetime into an XMP date. Only datetimes with a year can be
+/// converted.
+fn xmp_date(datetime: Datetime) -> Option<xmp_writer::DateTime> {
+    Some(xmp_writer::DateTime {
+        year: datetime.year()?.try_into().ok()?,
+        month: datetime.month(),
+        day: datetime.day(),
+        hour: datetime.hour(),
+        minute: datetime.minute(),
+        second: datetime.second(),
+        timezone: None,
+    })
 }
 
 /// Compress data with the DEFLATE algorithm.
@@ -1,26 +1,13 @@
-use std::num::NonZeroUsize;
-
 use pdf_writer::{Finish, Ref, TextStr};
 
 use super::{AbsExt, PdfContext, RefExt};
+use crate::doc::OutlineEntry;
 use crate::geom::Abs;
-use crate::model::Content;
 
 /// Construct the outline for the document.
 #[tracing::instrument(skip_all)]
 pub fn write_outline(ctx: &mut PdfContext) -> Option<Ref> {
-    let mut tree: Vec<HeadingNode> = vec![];
-    for heading in ctx.introspector.query(&item!(heading_func).select()) {
-        let leaf = HeadingNode::leaf((*heading).clone());
-
-        let mut children = &mut tree;
-        while children.last().map_or(false, |last| last.level < leaf.level) {
-            children = &mut children.last_mut().unwrap().children;
-        }
-
-        children.push(leaf);
-    }
-
+    let tree = build_tree(ctx.document.outline_in(&ctx.introspector));
     if tree.is_empty() {
         return None;
     }
@@ -43,21 +30,38 @@ pub fn write_outline(ctx: &mut PdfContext) -> Option<Ref> {
     Some(root_id)
 }
 
+/// Nest the entries of a document outline by their levels.
+///
+/// An entry becomes a child of the closest preceding entry with a lower level,
+/// even if levels in between are skipped.
+fn build_tree(entries: Vec<OutlineEntry>) -> Vec<HeadingNode> {
+    let mut tree: Vec<HeadingNode> = vec![];
+    for entry in entries {
+        let leaf = HeadingNode::leaf(entry);
+
+        let mut children = &mut tree;
+        while children
+            .last()
+            .map_or(false, |last| last.entry.level < leaf.entry.level)
+        {
+            children = &mut children.last_mut().unwrap().children;
+        }
+
+        children.push(leaf);
+    }
+    tree
+}
+
 /// A heading in the outline panel.
 #[derive(Debug, Clone)]
 struct HeadingNode {
-    element: Content,
-    level: NonZeroUsize,
+    entry: OutlineEntry,
     children: Vec<HeadingNode>,
 }
 
 impl HeadingNode {
-    fn leaf(element: Content) -> Self {
-        HeadingNode {
-            level: element.expect_field::<NonZeroUsize>("level"),
-            element,
-            children: Vec::new(),
-        }
+    fn leaf(entry: OutlineEntry) -> Self {
+        HeadingNode { entry, children: Vec::new() }
     }
 
     fn len(&self) -> usize {
@@ -95,11 +99,9 @@ fn write_outline_item(
         outline.count(-(node.children.len() as i32));
     }
 
-    let body = node.element.expect_field::<Content>("body");
-    outline.title(TextStr(body.plain_text().trim()));
+    outline.title(TextStr(&node.entry.title));
 
-    let loc = node.element.location().unwrap();
-    let pos = ctx.introspector.position(loc);
+    let pos = node.entry.position;
     let index = pos.page.get() - 1;
     if let Some(&height) = ctx.page_heights.get(index) {
         let y = (pos.point.y - Abs::pt(10.0)).max(Abs::zero());
@@ -125,3 +127,41 @@ fn write_outline_item(
 
     id
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::doc::Position;
+    use crate::geom::Point;
+    use crate::util::NonZeroExt;
+
+    fn entry(title: &str, level: usize) -> OutlineEntry {
+        OutlineEntry {
+            title: title.into(),
+            level: NonZeroUsize::new(level).unwrap(),
+            position: Position { page: NonZeroUsize::ONE, point: Point::zero() },
+        }
+    }
+
+    fn shape(nodes: &[HeadingNode]) -> String {
+        nodes
+            .iter()
+            .map(|node| format!("{}[{}]", node.entry.title, shape(&node.children)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_build_tree_skipped_level() {
+        let tree = build_tree(vec![
+            entry("A", 1),
+            entry("B", 3),
+            entry("C", 2),
+            entry("D", 3),
+            entry("E", 1),
+        ]);
+        assert_eq!(shape(&tree), "A[B[] C[D[]]] E[]");
+    }
+}
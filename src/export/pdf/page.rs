@@ -1,18 +1,23 @@
-use ecow::eco_format;
+use std::num::NonZeroUsize;
+
+use ecow::{eco_format, EcoString};
 use pdf_writer::types::{
     ActionType, AnnotationType, ColorSpaceOperand, LineCapStyle, LineJoinStyle,
 };
 use pdf_writer::writers::ColorSpace;
 use pdf_writer::{Content, Filter, Finish, Name, Rect, Ref, Str};
 
-use super::{deflate, AbsExt, EmExt, PdfContext, RefExt, D65_GRAY, SRGB};
+use super::gradient::PdfGradient;
+use super::tags::{Heading, Tag, TagKind};
+use super::{deflate, AbsExt, EmExt, PdfColorSpace, PdfContext, RefExt, D65_GRAY, SRGB};
 use crate::doc::{Destination, Frame, FrameItem, GroupItem, Meta, TextItem};
 use crate::font::Font;
 use crate::geom::{
-    self, Abs, Color, Em, Geometry, LineCap, LineJoin, Numeric, Paint, Point, Ratio,
-    Shape, Size, Stroke, Transform,
+    self, Abs, Color, Em, FillRule, Geometry, LineCap, LineJoin, LinearGradient, Numeric,
+    Paint, Point, Ratio, Shape, Size, Stroke, Transform,
 };
 use crate::image::Image;
+use crate::model;
 
 /// Construct page objects.
 #[tracing::instrument(skip_all)]
@@ -37,6 +42,8 @@ pub fn construct_page(ctx: &mut PdfContext, frame: &Frame) {
         saves: vec![],
         bottom: 0.0,
         links: vec![],
+        tags: vec![],
+        headings: vec![],
     };
 
     let size = frame.size();
@@ -63,13 +70,15 @@ pub fn construct_page(ctx: &mut PdfContext, frame: &Frame) {
     };
 
     ctx.parent.pages.push(page);
+    ctx.parent.page_tags.push(ctx.tags);
+    ctx.parent.page_headings.push(ctx.headings);
 }
 
 /// Write the page tree.
 #[tracing::instrument(skip_all)]
 pub fn write_page_tree(ctx: &mut PdfContext) {
-    for page in std::mem::take(&mut ctx.pages).into_iter() {
-        write_page(ctx, page);
+    for (i, page) in std::mem::take(&mut ctx.pages).into_iter().enumerate() {
+        write_page(ctx, i, page);
     }
 
     let mut pages = ctx.writer.pages(ctx.page_tree_ref);
@@ -98,13 +107,21 @@ pub fn write_page_tree(ctx: &mut PdfContext) {
     }
 
     images.finish();
+
+    let mut patterns = resources.patterns();
+    for (pattern_ref, p) in ctx.gradient_map.pdf_indices(&ctx.gradient_refs) {
+        let name = eco_format!("P{}", p);
+        patterns.pair(Name(name.as_bytes()), pattern_ref);
+    }
+
+    patterns.finish();
     resources.finish();
     pages.finish();
 }
 
 /// Write a page tree node.
 #[tracing::instrument(skip_all)]
-fn write_page(ctx: &mut PdfContext, page: Page) {
+fn write_page(ctx: &mut PdfContext, i: usize, page: Page) {
     let content_id = ctx.alloc.bump();
 
     let mut page_writer = ctx.writer.page(page.id);
@@ -115,6 +132,11 @@ fn write_page(ctx: &mut PdfContext, page: Page) {
     page_writer.media_box(Rect::new(0.0, 0.0, w, h));
     page_writer.contents(content_id);
 
+    if ctx.options.tagged {
+        page_writer.pair(Name(b"StructParents"), i as i32);
+        page_writer.pair(Name(b"Tabs"), Name(b"S"));
+    }
+
     let mut annotations = page_writer.annotations();
     for (dest, rect) in page.links {
         let mut annotation = annotations.push();
@@ -174,6 +196,10 @@ struct PageContext<'a, 'b> {
     saves: Vec<State>,
     bottom: f32,
     links: Vec<(Destination, Rect)>,
+    /// The tagged items on the page, indexed by their marked-content id.
+    tags: Vec<Tag>,
+    /// The areas of the headings on the page.
+    headings: Vec<Heading>,
 }
 
 /// A simulated graphics state used to deduplicate graphics state changes and
@@ -186,6 +212,9 @@ struct State {
     fill_space: Option<Name<'static>>,
     stroke: Option<Stroke>,
     stroke_space: Option<Name<'static>>,
+    /// The bounding box of the current clip region in the PDF coordinate
+    /// system, used to clip link annotations.
+    clip: Option<(Point, Point)>,
 }
 
 impl PageContext<'_, '_> {
@@ -221,11 +250,19 @@ impl PageContext<'_, '_> {
         }
     }
 
-    fn set_fill(&mut self, fill: &Paint) {
-        if self.state.fill.as_ref() != Some(fill) {
+    /// Set the fill paint for something occupying the box at `pos` with the
+    /// given `size` in the current user space.
+    fn set_fill(&mut self, fill: &Paint, pos: Point, size: Size) {
+        if let Paint::Gradient(gradient) = fill {
+            let name = self.gradient(gradient, pos, size);
+            self.content.set_fill_color_space(ColorSpaceOperand::Pattern);
+            self.content.set_fill_pattern(None, Name(name.as_bytes()));
+            self.reset_fill_color_space();
+            self.state.fill = None;
+        } else if self.state.fill.as_ref() != Some(fill) {
             let f = |c| c as f32 / 255.0;
-            let Paint::Solid(color) = fill;
-            match color {
+            let Paint::Solid(color) = fill else { return };
+            match self.convert_color(*color) {
                 Color::Luma(c) => {
                     self.set_fill_color_space(D65_GRAY);
                     self.content.set_fill_gray(f(c.0));
@@ -243,6 +280,30 @@ impl PageContext<'_, '_> {
         }
     }
 
+    /// Register a shading pattern painting the gradient across the box at
+    /// `pos` with the given `size` and return the pattern's resource name.
+    fn gradient(
+        &mut self,
+        gradient: &LinearGradient,
+        pos: Point,
+        size: Size,
+    ) -> EcoString {
+        let (start, end) = gradient.axis(size);
+        let transform =
+            self.state.transform.pre_concat(Transform::translate(pos.x, pos.y));
+        let pdf_gradient =
+            PdfGradient { gradient: gradient.clone(), start, end, transform };
+        self.parent.gradient_map.insert(pdf_gradient.clone());
+        eco_format!("P{}", self.parent.gradient_map.map(pdf_gradient))
+    }
+
+    fn convert_color(&self, color: Color) -> Color {
+        match self.parent.options.color_space {
+            PdfColorSpace::Preserve => color,
+            PdfColorSpace::Cmyk => Color::Cmyk(color.to_cmyk()),
+        }
+    }
+
     fn set_fill_color_space(&mut self, space: Name<'static>) {
         if self.state.fill_space != Some(space) {
             self.content.set_fill_color_space(ColorSpaceOperand::Named(space));
@@ -254,8 +315,11 @@ impl PageContext<'_, '_> {
         self.state.fill_space = None;
     }
 
-    fn set_stroke(&mut self, stroke: &Stroke) {
-        if self.state.stroke.as_ref() != Some(stroke) {
+    /// Set the stroke for something occupying the box at `pos` with the given
+    /// `size` in the current user space.
+    fn set_stroke(&mut self, stroke: &Stroke, pos: Point, size: Size) {
+        let gradient = matches!(stroke.paint, Paint::Gradient(_));
+        if gradient || self.state.stroke.as_ref() != Some(stroke) {
             let Stroke {
                 paint,
                 thickness,
@@ -266,19 +330,26 @@ impl PageContext<'_, '_> {
             } = stroke;
 
             let f = |c| c as f32 / 255.0;
-            let Paint::Solid(color) = paint;
-            match color {
-                Color::Luma(c) => {
-                    self.set_stroke_color_space(D65_GRAY);
-                    self.content.set_stroke_gray(f(c.0));
-                }
-                Color::Rgba(c) => {
-                    self.set_stroke_color_space(SRGB);
-                    self.content.set_stroke_color([f(c.r), f(c.g), f(c.b)]);
-                }
-                Color::Cmyk(c) => {
+            match paint {
+                Paint::Solid(color) => match self.convert_color(*color) {
+                    Color::Luma(c) => {
+                        self.set_stroke_color_space(D65_GRAY);
+                        self.content.set_stroke_gray(f(c.0));
+                    }
+                    Color::Rgba(c) => {
+                        self.set_stroke_color_space(SRGB);
+                        self.content.set_stroke_color([f(c.r), f(c.g), f(c.b)]);
+                    }
+                    Color::Cmyk(c) => {
+                        self.reset_stroke_color_space();
+                        self.content.set_stroke_cmyk(f(c.c), f(c.m), f(c.y), f(c.k));
+                    }
+                },
+                Paint::Gradient(gradient) => {
+                    let name = self.gradient(gradient, pos, size);
+                    self.content.set_stroke_color_space(ColorSpaceOperand::Pattern);
+                    self.content.set_stroke_pattern(None, Name(name.as_bytes()));
                     self.reset_stroke_color_space();
-                    self.content.set_stroke_cmyk(f(c.c), f(c.m), f(c.y), f(c.k));
                 }
             }
 
@@ -316,6 +387,66 @@ impl PageContext<'_, '_> {
     fn reset_stroke_color_space(&mut self) {
         self.state.stroke_space = None;
     }
+
+    /// Start a marked-content sequence for an item of the structure tree with
+    /// the given width at `pos` in the current user space, if the document is
+    /// tagged.
+    fn begin_tag(&mut self, pos: Point, width: Abs, kind: TagKind) {
+        if !self.parent.options.tagged {
+            return;
+        }
+
+        let name = match kind {
+            TagKind::Text(_) => Name(b"P"),
+            TagKind::Figure(_) => Name(b"Figure"),
+        };
+
+        let mut marked = self.content.begin_marked_content_with_properties(name);
+        let mut properties = marked.properties();
+        properties.pair(Name(b"MCID"), self.tags.len() as i32);
+        properties.finish();
+        marked.finish();
+
+        let bottom = Abs::pt(self.bottom as f64);
+        let (min, max) = bounding_box(self.state.transform, pos, Size::with_x(width));
+        let y = bottom - pos.transform(self.state.transform).y;
+        self.tags.push(Tag {
+            kind,
+            pos: Point::new(min.x, y),
+            width: max.x - min.x,
+        });
+    }
+
+    /// Record the area of a heading at `pos` in the current user space, if
+    /// the document is tagged.
+    fn record_heading(&mut self, pos: Point, elem: &model::Content, size: Size) {
+        if !self.parent.options.tagged || elem.func() != item!(heading_func) {
+            return;
+        }
+
+        let bottom = Abs::pt(self.bottom as f64);
+        let (min, max) = bounding_box(self.state.transform, pos, size);
+        self.headings.push(Heading {
+            level: elem.expect_field::<NonZeroUsize>("level").get().min(6),
+            min: Point::new(min.x, bottom - max.y),
+            max: Point::new(max.x, bottom - min.y),
+        });
+    }
+
+    /// Start a marked-content sequence for decorative content that is not part
+    /// of the structure tree, if the document is tagged.
+    fn begin_artifact(&mut self) {
+        if self.parent.options.tagged {
+            self.content.begin_marked_content(Name(b"Artifact"));
+        }
+    }
+
+    /// End a sequence started with `begin_tag` or `begin_artifact`.
+    fn end_tag(&mut self) {
+        if self.parent.options.tagged {
+            self.content.end_marked_content();
+        }
+    }
 }
 
 /// Encode a frame into the content stream.
@@ -325,12 +456,25 @@ fn write_frame(ctx: &mut PageContext, frame: &Frame) {
         let y = pos.y.to_f32();
         match item {
             FrameItem::Group(group) => write_group(ctx, pos, group),
-            FrameItem::Text(text) => write_text(ctx, x, y, text),
-            FrameItem::Shape(shape, _) => write_shape(ctx, x, y, shape),
-            FrameItem::Image(image, size, _) => write_image(ctx, x, y, image, *size),
+            FrameItem::Text(text) => {
+                ctx.begin_tag(pos, text.width(), TagKind::Text(text.size));
+                write_text(ctx, x, y, text);
+                ctx.end_tag();
+            }
+            FrameItem::Shape(shape, _) => {
+                ctx.begin_artifact();
+                write_shape(ctx, x, y, shape);
+                ctx.end_tag();
+            }
+            FrameItem::Image(image, size, _) => {
+                let alt = image.alt().map(Into::into);
+                ctx.begin_tag(pos, size.x, TagKind::Figure(alt));
+                write_image(ctx, x, y, image, *size);
+                ctx.end_tag();
+            }
             FrameItem::Meta(meta, size) => match meta {
                 Meta::Link(dest) => write_link(ctx, pos, dest, *size),
-                Meta::Elem(_) => {}
+                Meta::Elem(elem) => ctx.record_heading(pos, elem, *size),
                 Meta::Hide => {}
                 Meta::PageNumbering(_) => {}
             },
@@ -347,6 +491,12 @@ fn write_group(ctx: &mut PageContext, pos: Point, group: &GroupItem) {
 
     if group.clips {
         let size = group.frame.size();
+        let (min, max) = bounding_box(ctx.state.transform, Point::zero(), size);
+        ctx.state.clip = Some(match ctx.state.clip {
+            Some((cmin, cmax)) => (cmin.max(min), cmax.min(max)),
+            None => (min, max),
+        });
+
         let w = size.x.to_f32();
         let h = size.y.to_f32();
         ctx.content.move_to(0.0, 0.0);
@@ -371,7 +521,12 @@ fn write_text(ctx: &mut PageContext, x: f32, y: f32, text: &TextItem) {
         glyph_set.entry(g.id).or_insert_with(|| segment.into());
     }
 
-    ctx.set_fill(&text.fill);
+    let metrics = text.font.metrics();
+    let ascender = metrics.ascender.at(text.size);
+    let descender = metrics.descender.at(text.size);
+    let pos = Point::new(Abs::pt(x as f64), Abs::pt(y as f64) - ascender);
+    let size = Size::new(text.width(), ascender - descender);
+    ctx.set_fill(&text.fill, pos, size);
     ctx.set_font(&text.font, text.size);
     ctx.content.begin_text();
 
@@ -430,12 +585,15 @@ fn write_shape(ctx: &mut PageContext, x: f32, y: f32, shape: &Shape) {
         return;
     }
 
+    let (origin, size) = shape.geometry.bbox();
+    let pos = Point::new(Abs::pt(x as f64), Abs::pt(y as f64)) + origin;
+
     if let Some(fill) = &shape.fill {
-        ctx.set_fill(fill);
+        ctx.set_fill(fill, pos, size);
     }
 
     if let Some(stroke) = stroke {
-        ctx.set_stroke(stroke);
+        ctx.set_stroke(stroke, pos, size);
     }
 
     match shape.geometry {
@@ -457,11 +615,13 @@ fn write_shape(ctx: &mut PageContext, x: f32, y: f32, shape: &Shape) {
         }
     }
 
-    match (&shape.fill, stroke) {
-        (None, None) => unreachable!(),
-        (Some(_), None) => ctx.content.fill_nonzero(),
-        (None, Some(_)) => ctx.content.stroke(),
-        (Some(_), Some(_)) => ctx.content.fill_nonzero_and_stroke(),
+    match (&shape.fill, stroke, shape.fill_rule) {
+        (None, None, _) => unreachable!(),
+        (Some(_), None, FillRule::NonZero) => ctx.content.fill_nonzero(),
+        (Some(_), None, FillRule::EvenOdd) => ctx.content.fill_even_odd(),
+        (None, Some(_), _) => ctx.content.stroke(),
+        (Some(_), Some(_), FillRule::NonZero) => ctx.content.fill_nonzero_and_stroke(),
+        (Some(_), Some(_), FillRule::EvenOdd) => ctx.content.fill_even_odd_and_stroke(),
     };
 }
 
@@ -515,33 +675,47 @@ fn write_image(ctx: &mut PageContext, x: f32, y: f32, image: &Image, size: Size)
 }
 
 /// Save a link for later writing in the annotations dictionary.
+///
+/// Links in clipped groups are restricted to the visible area. Links that
+/// are clipped away entirely are dropped.
 fn write_link(ctx: &mut PageContext, pos: Point, dest: &Destination, size: Size) {
-    let mut min_x = Abs::inf();
-    let mut min_y = Abs::inf();
-    let mut max_x = -Abs::inf();
-    let mut max_y = -Abs::inf();
+    let (mut min, mut max) = bounding_box(ctx.state.transform, pos, size);
+    if let Some((cmin, cmax)) = ctx.state.clip {
+        min = min.max(cmin);
+        max = max.min(cmax);
+        if min.x >= max.x || min.y >= max.y {
+            return;
+        }
+    }
+
+    let x1 = min.x.to_f32();
+    let x2 = max.x.to_f32();
+    let y1 = max.y.to_f32();
+    let y2 = min.y.to_f32();
+    let rect = Rect::new(x1, y1, x2, y2);
+
+    ctx.links.push((dest.clone(), rect));
+}
+
+/// Compute the bounding box of a transformed rectangle.
+fn bounding_box(ts: Transform, pos: Point, size: Size) -> (Point, Point) {
+    let mut min = Point::splat(Abs::inf());
+    let mut max = Point::splat(-Abs::inf());
 
-    // Compute the bounding box of the transformed link.
     for point in [
         pos,
         pos + Point::with_x(size.x),
         pos + Point::with_y(size.y),
         pos + size.to_point(),
     ] {
-        let t = point.transform(ctx.state.transform);
-        min_x.set_min(t.x);
-        min_y.set_min(t.y);
-        max_x.set_max(t.x);
-        max_y.set_max(t.y);
+        let t = point.transform(ts);
+        min.x.set_min(t.x);
+        min.y.set_min(t.y);
+        max.x.set_max(t.x);
+        max.y.set_max(t.y);
     }
 
-    let x1 = min_x.to_f32();
-    let x2 = max_x.to_f32();
-    let y1 = max_y.to_f32();
-    let y2 = min_y.to_f32();
-    let rect = Rect::new(x1, y1, x2, y2);
-
-    ctx.links.push((dest.clone(), rect));
+    (min, max)
 }
 
 impl From<&LineCap> for LineCapStyle {
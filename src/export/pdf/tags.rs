@@ -0,0 +1,302 @@
+use ecow::{eco_format, EcoString};
+use pdf_writer::{Finish, Name, Ref, TextStr};
+
+use super::PdfContext;
+use crate::geom::{Abs, Point};
+
+/// An item that was wrapped in a marked-content sequence on a page.
+#[derive(Debug, Clone)]
+pub struct Tag {
+    /// What kind of content was tagged.
+    pub kind: TagKind,
+    /// The position of the item's origin on the page. For text, this is the
+    /// start of the baseline.
+    pub pos: Point,
+    /// The width of the item.
+    pub width: Abs,
+}
+
+/// The kind of a tagged item.
+#[derive(Debug, Clone)]
+pub enum TagKind {
+    /// A text run with the given font size.
+    Text(Abs),
+    /// An image with an optional alternative description.
+    Figure(Option<EcoString>),
+}
+
+/// The area of a heading on a page.
+#[derive(Debug, Clone)]
+pub struct Heading {
+    /// The heading's level, at most six.
+    pub level: usize,
+    /// The top left corner of the area.
+    pub min: Point,
+    /// The bottom right corner of the area.
+    pub max: Point,
+}
+
+/// Write the structure tree for the tagged content of all pages.
+///
+/// The text of each page is split into blocks at larger vertical gaps,
+/// changes of the font size, and lines that don't overlap horizontally, as in
+/// different columns. Blocks that start within a heading's area become `H1`
+/// to `H6` elements, all others become paragraphs. The blocks are listed in
+/// the order in which they were laid out, which is also their reading order.
+#[tracing::instrument(skip_all)]
+pub fn write_structure_tree(ctx: &mut PdfContext) -> Option<Ref> {
+    if !ctx.options.tagged {
+        return None;
+    }
+
+    let root_ref = ctx.alloc.bump();
+    let doc_ref = ctx.alloc.bump();
+    let headings = std::mem::take(&mut ctx.page_headings);
+
+    let mut children = vec![];
+    let mut parent_tree = vec![];
+    for (i, tags) in std::mem::take(&mut ctx.page_tags).into_iter().enumerate() {
+        let page_ref = ctx.page_refs[i];
+        let mut parents = vec![doc_ref; tags.len()];
+        for block in blocks(&tags, &headings[i]) {
+            let elem_ref = ctx.alloc.bump();
+            let role = block.role.name();
+            let mut elem = ctx.writer.indirect(elem_ref).dict();
+            elem.pair(Name(b"Type"), Name(b"StructElem"));
+            elem.pair(Name(b"S"), Name(role.as_bytes()));
+            elem.pair(Name(b"P"), doc_ref);
+            elem.pair(Name(b"Pg"), page_ref);
+            if let Some(alt) = &block.alt {
+                elem.pair(Name(b"Alt"), TextStr(alt));
+            }
+            elem.insert(Name(b"K"))
+                .array()
+                .items(block.mcids.iter().map(|&mcid| mcid as i32));
+            elem.finish();
+
+            for &mcid in &block.mcids {
+                parents[mcid] = elem_ref;
+            }
+            children.push(elem_ref);
+        }
+
+        parent_tree.push(parents);
+    }
+
+    let mut doc = ctx.writer.indirect(doc_ref).dict();
+    doc.pair(Name(b"Type"), Name(b"StructElem"));
+    doc.pair(Name(b"S"), Name(b"Document"));
+    doc.pair(Name(b"P"), root_ref);
+    if let Some(title) = &ctx.document.title {
+        doc.pair(Name(b"T"), TextStr(title));
+    }
+    doc.insert(Name(b"K")).array().items(children);
+    doc.finish();
+
+    let mut root = ctx.writer.indirect(root_ref).dict();
+    root.pair(Name(b"Type"), Name(b"StructTreeRoot"));
+    root.pair(Name(b"K"), doc_ref);
+    root.pair(Name(b"ParentTreeNextKey"), parent_tree.len() as i32);
+    let mut tree = root.insert(Name(b"ParentTree")).dict();
+    let mut nums = tree.insert(Name(b"Nums")).array();
+    for (i, parents) in parent_tree.into_iter().enumerate() {
+        nums.item(i as i32);
+        nums.push().array().items(parents);
+    }
+    nums.finish();
+    tree.finish();
+    root.finish();
+
+    Some(root_ref)
+}
+
+/// A structure element made up of consecutive tagged items.
+#[derive(Debug)]
+struct Block {
+    /// The structure type of the element.
+    role: Role,
+    /// The marked-content ids of the element's items.
+    mcids: Vec<usize>,
+    /// The position of the element's first item.
+    pos: Point,
+    /// The horizontal extent of the element's items.
+    x: (Abs, Abs),
+    /// An alternative description of the element.
+    alt: Option<EcoString>,
+}
+
+/// The structure type of a block.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Role {
+    Paragraph,
+    Heading(usize),
+    Figure,
+}
+
+impl Role {
+    /// The PDF name of the structure type.
+    fn name(self) -> EcoString {
+        match self {
+            Self::Paragraph => "P".into(),
+            Self::Heading(level) => eco_format!("H{level}"),
+            Self::Figure => "Figure".into(),
+        }
+    }
+}
+
+/// Group the tagged items of a page into blocks.
+fn blocks(tags: &[Tag], headings: &[Heading]) -> Vec<Block> {
+    let mut blocks: Vec<Block> = vec![];
+    let mut prev: Option<(Abs, Abs)> = None;
+    for (mcid, tag) in tags.iter().enumerate() {
+        let x = (tag.pos.x, tag.pos.x + tag.width);
+        match &tag.kind {
+            TagKind::Text(size) => {
+                // Runs on the same line or on the next line with the same
+                // font size belong to the same paragraph. Lines in another
+                // column don't overlap the paragraph horizontally.
+                let continues = match (prev, blocks.last()) {
+                    (Some((prev_y, prev_size)), Some(block)) if prev_size == *size => {
+                        let dy = tag.pos.y - prev_y;
+                        let same_line = dy.abs() < *size / 4.0;
+                        let next_line = dy >= *size / 4.0 && dy < *size * 1.6;
+                        same_line || (next_line && x.0 < block.x.1 && block.x.0 < x.1)
+                    }
+                    _ => false,
+                };
+
+                match blocks.last_mut() {
+                    Some(block) if continues => {
+                        block.mcids.push(mcid);
+                        block.x = (block.x.0.min(x.0), block.x.1.max(x.1));
+                    }
+                    _ => blocks.push(Block {
+                        role: Role::Paragraph,
+                        mcids: vec![mcid],
+                        pos: tag.pos,
+                        x,
+                        alt: None,
+                    }),
+                }
+
+                prev = Some((tag.pos.y, *size));
+            }
+            TagKind::Figure(alt) => {
+                blocks.push(Block {
+                    role: Role::Figure,
+                    mcids: vec![mcid],
+                    pos: tag.pos,
+                    x,
+                    alt: alt.clone(),
+                });
+                prev = None;
+            }
+        }
+    }
+
+    // A heading starts the first paragraph that begins within the heading's
+    // area. Since the area is known in both directions, this also works when
+    // the page is split into columns.
+    for heading in headings {
+        if let Some(block) = blocks.iter_mut().find(|block| {
+            block.role == Role::Paragraph
+                && heading.min.x <= block.pos.x
+                && block.pos.x <= heading.max.x
+                && heading.min.y <= block.pos.y
+                && block.pos.y <= heading.max.y
+        }) {
+            block.role = Role::Heading(heading.level);
+        }
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A text run of size 10pt at the given position.
+    fn text(x: f64, y: f64) -> Tag {
+        Tag {
+            kind: TagKind::Text(Abs::pt(10.0)),
+            pos: Point::new(Abs::pt(x), Abs::pt(y)),
+            width: Abs::pt(40.0),
+        }
+    }
+
+    /// The roles and marked-content ids of the blocks for some tags.
+    fn roles(tags: &[Tag], headings: &[Heading]) -> Vec<(Role, Vec<usize>)> {
+        blocks(tags, headings)
+            .into_iter()
+            .map(|block| (block.role, block.mcids))
+            .collect()
+    }
+
+    #[test]
+    fn test_blocks_paragraphs() {
+        let mut larger = text(10.0, 70.0);
+        larger.kind = TagKind::Text(Abs::pt(12.0));
+        let tags = [
+            text(10.0, 10.0),
+            text(50.0, 10.0),
+            text(10.0, 22.0),
+            text(10.0, 50.0),
+            larger,
+        ];
+        assert_eq!(
+            roles(&tags, &[]),
+            [
+                (Role::Paragraph, vec![0, 1, 2]),
+                (Role::Paragraph, vec![3]),
+                (Role::Paragraph, vec![4]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blocks_figure() {
+        let figure = Tag {
+            kind: TagKind::Figure(Some("A cat".into())),
+            pos: Point::new(Abs::pt(10.0), Abs::pt(14.0)),
+            width: Abs::pt(40.0),
+        };
+        let tags = [text(10.0, 10.0), figure, text(10.0, 22.0)];
+        let blocks = blocks(&tags, &[]);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[1].role, Role::Figure);
+        assert_eq!(blocks[1].mcids, [1]);
+        assert_eq!(blocks[1].alt.as_deref(), Some("A cat"));
+        assert_eq!(blocks[2].role, Role::Paragraph);
+    }
+
+    #[test]
+    fn test_blocks_headings_in_columns() {
+        let heading = |level, x: f64, y: f64| Heading {
+            level,
+            min: Point::new(Abs::pt(x), Abs::pt(y)),
+            max: Point::new(Abs::pt(x + 50.0), Abs::pt(y + 15.0)),
+        };
+
+        // The paragraph at the top of the first column lies below the top of
+        // the heading that starts the second column.
+        let tags = [
+            text(10.0, 20.0),
+            text(10.0, 32.0),
+            text(10.0, 70.0),
+            text(70.0, 30.0),
+            text(70.0, 42.0),
+            text(70.0, 70.0),
+        ];
+        let headings = [heading(1, 10.0, 60.0), heading(2, 70.0, 18.0)];
+        assert_eq!(
+            roles(&tags, &headings),
+            [
+                (Role::Paragraph, vec![0, 1]),
+                (Role::Heading(1), vec![2]),
+                (Role::Heading(2), vec![3, 4]),
+                (Role::Paragraph, vec![5]),
+            ]
+        );
+    }
+}
@@ -12,8 +12,8 @@ use usvg::{NodeExt, TreeParsing};
 
 use crate::doc::{Frame, FrameItem, GroupItem, Meta, TextItem};
 use crate::geom::{
-    self, Abs, Color, Geometry, LineCap, LineJoin, Paint, PathItem, Shape, Size, Stroke,
-    Transform,
+    self, Abs, Color, FillRule, Geometry, LineCap, LineJoin, Paint, PathItem, Shape,
+    Size, Stroke, Transform,
 };
 use crate::image::{DecodedImage, Image};
 
@@ -21,6 +21,7 @@ use crate::image::{DecodedImage, Image};
 ///
 /// This renders the frame at the given number of pixels per point and returns
 /// the resulting `tiny-skia` pixel buffer.
+#[tracing::instrument(skip(frame))]
 pub fn render(frame: &Frame, pixel_per_pt: f32, fill: Color) -> sk::Pixmap {
     let size = frame.size();
     let pxw = (pixel_per_pt * size.x.to_f32()).round().max(1.0) as u32;
@@ -35,6 +36,53 @@ pub fn render(frame: &Frame, pixel_per_pt: f32, fill: Color) -> sk::Pixmap {
     canvas
 }
 
+/// Export multiple frames into a single raster image.
+///
+/// The frames are stacked vertically with `padding` around and between them.
+/// The remaining space is filled with the `fill` color. If there are no frames,
+/// the result is a 1×1 pixmap filled with that color.
+#[tracing::instrument(skip(frames))]
+pub fn render_merged(
+    frames: &[Frame],
+    pixel_per_pt: f32,
+    fill: Color,
+    padding: Abs,
+) -> sk::Pixmap {
+    let pixmaps: Vec<_> = frames
+        .iter()
+        .map(|frame| render(frame, pixel_per_pt, Color::WHITE))
+        .collect();
+
+    if pixmaps.is_empty() {
+        let mut canvas = sk::Pixmap::new(1, 1).unwrap();
+        canvas.fill(fill.into());
+        return canvas;
+    }
+
+    let pad = (padding.to_f32() * pixel_per_pt).round() as u32;
+    let pxw = 2 * pad + pixmaps.iter().map(sk::Pixmap::width).max().unwrap_or_default();
+    let pxh = pad + pixmaps.iter().map(|pixmap| pixmap.height() + pad).sum::<u32>();
+
+    let mut canvas = sk::Pixmap::new(pxw, pxh).unwrap();
+    canvas.fill(fill.into());
+
+    let [x, mut y] = [pad; 2];
+    for pixmap in pixmaps {
+        canvas.draw_pixmap(
+            x as i32,
+            y as i32,
+            pixmap.as_ref(),
+            &sk::PixmapPaint::default(),
+            sk::Transform::identity(),
+            None,
+        );
+
+        y += pixmap.height() + pad;
+    }
+
+    canvas
+}
+
 /// Render a frame into the canvas.
 fn render_frame(
     canvas: &mut sk::Pixmap,
@@ -130,6 +178,11 @@ fn render_text(
     mask: Option<&sk::Mask>,
     text: &TextItem,
 ) {
+    if let Paint::Gradient(_) = text.fill {
+        render_gradient_text(canvas, ts, mask, text);
+        return;
+    }
+
     let mut x = 0.0;
     for glyph in &text.glyphs {
         let id = GlyphId(glyph.id);
@@ -144,6 +197,39 @@ fn render_text(
     }
 }
 
+/// Render a text run whose fill is a gradient into the canvas.
+///
+/// The outlines of all glyphs are combined into one path so that the gradient
+/// spans the whole run instead of restarting at every glyph.
+fn render_gradient_text(
+    canvas: &mut sk::Pixmap,
+    ts: sk::Transform,
+    mask: Option<&sk::Mask>,
+    text: &TextItem,
+) -> Option<()> {
+    let mut builder = GlyphRunBuilder {
+        builder: sk::PathBuilder::new(),
+        scale: text.size.to_f32() / text.font.units_per_em() as f32,
+        offset: 0.0,
+    };
+
+    let mut x = 0.0;
+    for glyph in &text.glyphs {
+        builder.offset = x + glyph.x_offset.at(text.size).to_f32();
+        text.font.ttf().outline_glyph(GlyphId(glyph.id), &mut builder);
+        x += glyph.x_advance.at(text.size).to_f32();
+    }
+
+    let path = builder.builder.finish()?;
+    let metrics = text.font.metrics();
+    let top = -metrics.ascender.at(text.size).to_f32();
+    let bottom = -metrics.descender.at(text.size).to_f32();
+    let bbox = sk::Rect::from_ltrb(0.0, top, x, bottom)?;
+    let paint = convert_paint(&text.fill, bbox);
+    canvas.fill_path(&path, &paint, sk::FillRule::default(), ts, mask);
+    Some(())
+}
+
 /// Render an SVG glyph into the canvas.
 fn render_svg_glyph(
     canvas: &mut sk::Pixmap,
@@ -285,7 +371,7 @@ fn render_outline_glyph(
             builder.0.finish()?
         };
 
-        let paint = (&text.fill).into();
+        let paint = convert_paint(&text.fill, path.bounds());
         let rule = sk::FillRule::default();
 
         // Flip vertically because font design coordinate
@@ -308,7 +394,7 @@ fn render_outline_glyph(
         let mw = bitmap.width;
         let mh = bitmap.height;
 
-        let Paint::Solid(color) = text.fill;
+        let Paint::Solid(color) = text.fill else { return None };
         let c = color.to_rgba();
 
         // Pad the pixmap with 1 pixel in each dimension so that we do
@@ -348,7 +434,7 @@ fn render_outline_glyph(
         let bottom = top + mh;
 
         // Premultiply the text color.
-        let Paint::Solid(color) = text.fill;
+        let Paint::Solid(color) = text.fill else { return None };
         let c = color.to_rgba();
         let color = sk::ColorU8::from_rgba(c.r, c.g, c.b, 255).premultiply().get();
 
@@ -400,12 +486,15 @@ fn render_shape(
     };
 
     if let Some(fill) = &shape.fill {
-        let mut paint: sk::Paint = fill.into();
+        let mut paint = convert_paint(fill, path.bounds());
         if matches!(shape.geometry, Geometry::Rect(_)) {
             paint.anti_alias = false;
         }
 
-        let rule = sk::FillRule::default();
+        let rule = match shape.fill_rule {
+            FillRule::NonZero => sk::FillRule::Winding,
+            FillRule::EvenOdd => sk::FillRule::EvenOdd,
+        };
         canvas.fill_path(&path, &paint, rule, ts, mask);
     }
 
@@ -433,7 +522,7 @@ fn render_shape(
 
                 sk::StrokeDash::new(dash_array, pattern.phase.to_f32())
             });
-            let paint = paint.into();
+            let paint = convert_paint(paint, path.bounds());
             let stroke = sk::Stroke {
                 width,
                 line_cap: line_cap.into(),
@@ -555,14 +644,47 @@ impl From<Transform> for sk::Transform {
     }
 }
 
-impl From<&Paint> for sk::Paint<'static> {
-    fn from(paint: &Paint) -> Self {
-        let mut sk_paint = sk::Paint::default();
-        let Paint::Solid(color) = *paint;
-        sk_paint.set_color(color.into());
-        sk_paint.anti_alias = true;
-        sk_paint
+/// Convert a Typst paint into a tiny-skia paint for filling or stroking
+/// something with the given bounding box.
+fn convert_paint(paint: &Paint, bbox: sk::Rect) -> sk::Paint<'static> {
+    let mut sk_paint = sk::Paint::default();
+    match paint {
+        Paint::Solid(color) => sk_paint.set_color((*color).into()),
+        Paint::Gradient(gradient) => {
+            let size =
+                Size::new(Abs::pt(bbox.width() as f64), Abs::pt(bbox.height() as f64));
+
+            let (start, end) = gradient.axis(size);
+            let point = |p: geom::Point| {
+                sk::Point::from_xy(bbox.left() + p.x.to_f32(), bbox.top() + p.y.to_f32())
+            };
+
+            let stops = gradient
+                .stops
+                .iter()
+                .map(|&(offset, color)| {
+                    sk::GradientStop::new(offset.get() as f32, color.into())
+                })
+                .collect();
+
+            match sk::LinearGradient::new(
+                point(start),
+                point(end),
+                stops,
+                sk::SpreadMode::Pad,
+                sk::Transform::identity(),
+            ) {
+                Some(shader) => sk_paint.shader = shader,
+                None => {
+                    if let Some(&(_, color)) = gradient.stops.last() {
+                        sk_paint.set_color(color.into());
+                    }
+                }
+            }
+        }
     }
+    sk_paint.anti_alias = true;
+    sk_paint
 }
 
 impl From<Color> for sk::Color {
@@ -617,6 +739,49 @@ impl OutlineBuilder for WrappedPathBuilder {
     }
 }
 
+/// Collects the outlines of a text run's glyphs into a single path, scaling
+/// them from font units and moving each glyph to its offset in the run.
+struct GlyphRunBuilder {
+    builder: sk::PathBuilder,
+    scale: f32,
+    offset: f32,
+}
+
+impl GlyphRunBuilder {
+    fn map(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.offset + x * self.scale, -y * self.scale)
+    }
+}
+
+impl OutlineBuilder for GlyphRunBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.map(x, y);
+        self.builder.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.map(x, y);
+        self.builder.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x1, y1) = self.map(x1, y1);
+        let (x, y) = self.map(x, y);
+        self.builder.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x1, y1) = self.map(x1, y1);
+        let (x2, y2) = self.map(x2, y2);
+        let (x, y) = self.map(x, y);
+        self.builder.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
+
 /// Additional methods for [`Length`].
 trait AbsExt {
     /// Convert to a number of points as f32.
@@ -0,0 +1,577 @@
+//! Rendering into SVG vector graphics.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use base64::Engine;
+use ecow::{eco_format, EcoString};
+use ttf_parser::{GlyphId, OutlineBuilder};
+use xmlwriter::XmlWriter;
+
+use crate::doc::{Destination, Frame, FrameItem, GroupItem, Meta, TextItem};
+use crate::font::Font;
+use crate::geom::{
+    Abs, Color, FillRule, Geometry, LineCap, LineJoin, LinearGradient, Paint, PathItem,
+    Point, Shape, Size, Stroke, Transform,
+};
+use crate::image::{Image, ImageFormat, RasterFormat, VectorFormat};
+
+/// Export a frame into an SVG document.
+///
+/// Glyphs are converted into paths and deduplicated through `<use>`
+/// references, so the resulting file does not depend on any installed fonts.
+/// Images are embedded as base64-encoded data URLs.
+#[tracing::instrument(skip_all)]
+pub fn svg(frame: &Frame) -> String {
+    let mut renderer = SvgRenderer::new();
+    renderer.write_header(frame.size());
+    renderer.render_frame(frame);
+    renderer.finalize()
+}
+
+/// Holds the state of an in-progress SVG export.
+struct SvgRenderer {
+    /// The XML writer for the document.
+    xml: XmlWriter,
+    /// Maps glyphs to the ids of their outline definitions. `None` if the glyph
+    /// has no outline.
+    glyph_ids: HashMap<(Font, u16), Option<EcoString>>,
+    /// The outline definitions in the order in which they were first used.
+    glyphs: Vec<(EcoString, String)>,
+    /// The number of clip paths emitted so far.
+    clips: usize,
+    /// The number of gradients emitted so far.
+    gradients: usize,
+}
+
+impl SvgRenderer {
+    /// Create a new, empty renderer.
+    fn new() -> Self {
+        Self {
+            xml: XmlWriter::new(xmlwriter::Options::default()),
+            glyph_ids: HashMap::new(),
+            glyphs: vec![],
+            clips: 0,
+            gradients: 0,
+        }
+    }
+
+    /// Write the root element with its namespaces and dimensions.
+    fn write_header(&mut self, size: Size) {
+        self.xml.start_element("svg");
+        self.xml.write_attribute("class", "typst-doc");
+        self.xml.write_attribute_fmt(
+            "viewBox",
+            format_args!("0 0 {} {}", size.x.to_pt(), size.y.to_pt()),
+        );
+        self.xml
+            .write_attribute_fmt("width", format_args!("{}pt", size.x.to_pt()));
+        self.xml
+            .write_attribute_fmt("height", format_args!("{}pt", size.y.to_pt()));
+        self.xml.write_attribute("xmlns", "http://www.w3.org/2000/svg");
+        self.xml
+            .write_attribute("xmlns:xlink", "http://www.w3.org/1999/xlink");
+    }
+
+    /// Write the collected glyph definitions and finish the document.
+    fn finalize(mut self) -> String {
+        if !self.glyphs.is_empty() {
+            self.xml.start_element("defs");
+            self.xml.write_attribute("id", "glyph");
+            for (id, path) in &self.glyphs {
+                self.xml.start_element("path");
+                self.xml.write_attribute("id", id);
+                self.xml.write_attribute("d", path);
+                self.xml.end_element();
+            }
+            self.xml.end_element();
+        }
+        self.xml.end_document()
+    }
+
+    /// Render a frame with all its items.
+    fn render_frame(&mut self, frame: &Frame) {
+        for (pos, item) in frame.items() {
+            let x = pos.x.to_pt();
+            let y = pos.y.to_pt();
+            self.xml.start_element("g");
+            if x != 0.0 || y != 0.0 {
+                self.xml
+                    .write_attribute_fmt("transform", format_args!("translate({x} {y})"));
+            }
+
+            match item {
+                FrameItem::Group(group) => self.render_group(group),
+                FrameItem::Text(text) => self.render_text(text),
+                FrameItem::Shape(shape, _) => self.render_shape(shape),
+                FrameItem::Image(image, size, _) => self.render_image(image, *size),
+                FrameItem::Meta(meta, size) => match meta {
+                    Meta::Link(dest) => self.render_link(dest, *size),
+                    Meta::Elem(_) => {}
+                    Meta::PageNumbering(_) => {}
+                    Meta::Hide => {}
+                },
+            }
+
+            self.xml.end_element();
+        }
+    }
+
+    /// Render a group with optional transform and clipping.
+    fn render_group(&mut self, group: &GroupItem) {
+        self.xml.start_element("g");
+        self.xml.write_attribute("class", "typst-group");
+        if !group.transform.is_identity() {
+            self.xml.write_attribute("transform", &svg_matrix(group.transform));
+        }
+
+        if group.clips {
+            let id = eco_format!("c{}", self.clips);
+            self.clips += 1;
+
+            let size = group.frame.size();
+            self.xml.start_element("clipPath");
+            self.xml.write_attribute("id", &id);
+            self.xml.start_element("rect");
+            self.xml.write_attribute("width", &size.x.to_pt());
+            self.xml.write_attribute("height", &size.y.to_pt());
+            self.xml.end_element();
+            self.xml.end_element();
+
+            self.xml.start_element("g");
+            self.xml.write_attribute_fmt("clip-path", format_args!("url(#{id})"));
+            self.render_frame(&group.frame);
+            self.xml.end_element();
+        } else {
+            self.render_frame(&group.frame);
+        }
+
+        self.xml.end_element();
+    }
+
+    /// Render a text run by referencing its glyph outlines.
+    fn render_text(&mut self, text: &TextItem) {
+        let Paint::Solid(color) = &text.fill else {
+            self.render_gradient_text(text);
+            return;
+        };
+
+        let scale = text.size.to_pt() / text.font.units_per_em();
+
+        self.xml.start_element("g");
+        self.xml.write_attribute("class", "typst-text");
+        write_fill(&mut self.xml, &svg_color(*color), color.to_rgba().a);
+
+        let mut x = 0.0;
+        for glyph in &text.glyphs {
+            let offset = x + glyph.x_offset.at(text.size).to_pt();
+            match self.glyph(&text.font, glyph.id) {
+                Some(id) => self.render_glyph_use(&id, offset, scale),
+                None => self.render_bitmap_glyph(text, glyph.id, offset),
+            }
+            x += glyph.x_advance.at(text.size).to_pt();
+        }
+
+        self.xml.end_element();
+    }
+
+    /// Render a text run whose fill is a gradient.
+    ///
+    /// The glyph outlines become the clip path of a rectangle filled with the
+    /// gradient, so that the gradient spans the whole run instead of being
+    /// restarted in every glyph's coordinate system.
+    fn render_gradient_text(&mut self, text: &TextItem) {
+        let scale = text.size.to_pt() / text.font.units_per_em();
+        let metrics = text.font.metrics();
+        let ascender = metrics.ascender.at(text.size);
+        let descender = metrics.descender.at(text.size);
+        let pos = Point::with_y(-ascender);
+        let size = Size::new(text.width(), ascender - descender);
+        let (fill, alpha) = self.paint(&text.fill, pos, size);
+
+        let id = eco_format!("c{}", self.clips);
+        self.clips += 1;
+
+        self.xml.start_element("g");
+        self.xml.write_attribute("class", "typst-text");
+        self.xml.start_element("clipPath");
+        self.xml.write_attribute("id", &id);
+
+        let mut x = 0.0;
+        let mut bitmaps = vec![];
+        for glyph in &text.glyphs {
+            let offset = x + glyph.x_offset.at(text.size).to_pt();
+            match self.glyph(&text.font, glyph.id) {
+                Some(id) => self.render_glyph_use(&id, offset, scale),
+                None => bitmaps.push((glyph.id, offset)),
+            }
+            x += glyph.x_advance.at(text.size).to_pt();
+        }
+
+        self.xml.end_element();
+        self.xml.start_element("rect");
+        self.xml.write_attribute("y", &pos.y.to_pt());
+        self.xml.write_attribute("width", &size.x.to_pt());
+        self.xml.write_attribute("height", &size.y.to_pt());
+        write_fill(&mut self.xml, &fill, alpha);
+        self.xml.write_attribute_fmt("clip-path", format_args!("url(#{id})"));
+        self.xml.end_element();
+
+        // Images can't take part in a clip path, so bitmap glyphs are rendered
+        // as they are.
+        for (id, offset) in bitmaps {
+            self.render_bitmap_glyph(text, id, offset);
+        }
+
+        self.xml.end_element();
+    }
+
+    /// Reference a glyph's outline definition, placed at `offset` in its run.
+    fn render_glyph_use(&mut self, id: &str, offset: f64, scale: f64) {
+        self.xml.start_element("use");
+        self.xml.write_attribute_fmt("xlink:href", format_args!("#{id}"));
+        self.xml.write_attribute_fmt(
+            "transform",
+            format_args!("translate({offset} 0) scale({scale} -{scale})"),
+        );
+        self.xml.end_element();
+    }
+
+    /// Look up or create the outline definition for a glyph.
+    fn glyph(&mut self, font: &Font, id: u16) -> Option<EcoString> {
+        let glyphs = &mut self.glyphs;
+        self.glyph_ids
+            .entry((font.clone(), id))
+            .or_insert_with(|| {
+                let mut builder = SvgPathBuilder(String::new());
+                font.ttf().outline_glyph(GlyphId(id), &mut builder)?;
+                let name = eco_format!("g{}", glyphs.len());
+                glyphs.push((name.clone(), builder.0));
+                Some(name)
+            })
+            .clone()
+    }
+
+    /// Render a glyph without an outline from the font's bitmap table.
+    fn render_bitmap_glyph(&mut self, text: &TextItem, id: u16, offset: f64) {
+        let Some(raster) = text.font.ttf().glyph_raster_image(GlyphId(id), u16::MAX)
+        else {
+            return;
+        };
+
+        let Ok(image) = Image::new(raster.data.into(), raster.format.into(), None) else {
+            return;
+        };
+
+        let size = text.size.to_pt();
+        let h = size;
+        let w = (image.width() as f64 / image.height() as f64) * h;
+        let dx = (raster.x as f64) / (image.width() as f64) * size;
+        let dy = (raster.y as f64) / (image.height() as f64) * size;
+
+        self.xml.start_element("g");
+        self.xml.write_attribute_fmt(
+            "transform",
+            format_args!("translate({} {})", offset + dx, -size - dy),
+        );
+        self.render_image(&image, Size::new(Abs::pt(w), Abs::pt(h)));
+        self.xml.end_element();
+    }
+
+    /// Render a geometrical shape.
+    fn render_shape(&mut self, shape: &Shape) {
+        let (pos, size) = shape.geometry.bbox();
+        let fill = shape.fill.as_ref().map(|fill| self.paint(fill, pos, size));
+        let stroke = shape
+            .stroke
+            .as_ref()
+            .map(|stroke| (stroke, self.paint(&stroke.paint, pos, size)));
+
+        self.xml.start_element("path");
+        self.xml.write_attribute("class", "typst-shape");
+
+        match fill {
+            Some((fill, alpha)) => {
+                write_fill(&mut self.xml, &fill, alpha);
+                if shape.fill_rule == FillRule::EvenOdd {
+                    self.xml.write_attribute("fill-rule", "evenodd");
+                }
+            }
+            None => self.xml.write_attribute("fill", "none"),
+        }
+
+        if let Some((stroke, (paint, alpha))) = stroke {
+            write_stroke(&mut self.xml, stroke, &paint, alpha);
+        }
+
+        self.xml.write_attribute("d", &convert_geometry(&shape.geometry));
+        self.xml.end_element();
+    }
+
+    /// Convert a paint for something occupying the box at `pos` with the given
+    /// `size` into an SVG paint and its alpha value.
+    ///
+    /// Gradients are written as definitions right away and referenced by id.
+    fn paint(&mut self, paint: &Paint, pos: Point, size: Size) -> (EcoString, u8) {
+        match paint {
+            Paint::Solid(color) => (svg_color(*color), color.to_rgba().a),
+            Paint::Gradient(gradient) => {
+                let id = self.gradient(gradient, pos, size);
+                (eco_format!("url(#{id})"), u8::MAX)
+            }
+        }
+    }
+
+    /// Write the definition of a gradient spanning the box at `pos` with the
+    /// given `size` and return its id.
+    fn gradient(
+        &mut self,
+        gradient: &LinearGradient,
+        pos: Point,
+        size: Size,
+    ) -> EcoString {
+        let id = eco_format!("gr{}", self.gradients);
+        self.gradients += 1;
+
+        let (start, end) = gradient.axis(size);
+        self.xml.start_element("linearGradient");
+        self.xml.write_attribute("id", &id);
+        self.xml.write_attribute("gradientUnits", "userSpaceOnUse");
+        self.xml.write_attribute("x1", &(pos.x + start.x).to_pt());
+        self.xml.write_attribute("y1", &(pos.y + start.y).to_pt());
+        self.xml.write_attribute("x2", &(pos.x + end.x).to_pt());
+        self.xml.write_attribute("y2", &(pos.y + end.y).to_pt());
+        for &(offset, color) in &gradient.stops {
+            self.xml.start_element("stop");
+            self.xml.write_attribute("offset", &offset.get());
+            self.xml.write_attribute("stop-color", &svg_color(color));
+            let alpha = color.to_rgba().a;
+            if alpha != u8::MAX {
+                self.xml.write_attribute("stop-opacity", &(alpha as f64 / 255.0));
+            }
+            self.xml.end_element();
+        }
+        self.xml.end_element();
+
+        id
+    }
+
+    /// Render a raster or vector image as an embedded data URL.
+    fn render_image(&mut self, image: &Image, size: Size) {
+        let mime = match image.format() {
+            ImageFormat::Raster(RasterFormat::Png) => "image/png",
+            ImageFormat::Raster(RasterFormat::Jpg) => "image/jpeg",
+            ImageFormat::Raster(RasterFormat::Gif) => "image/gif",
+            ImageFormat::Raster(RasterFormat::Webp) => "image/webp",
+            ImageFormat::Vector(VectorFormat::Svg) => "image/svg+xml",
+        };
+
+        let data = base64::engine::general_purpose::STANDARD.encode(image.data());
+        self.xml.start_element("image");
+        self.xml.write_attribute("width", &size.x.to_pt());
+        self.xml.write_attribute("height", &size.y.to_pt());
+        self.xml.write_attribute("preserveAspectRatio", "none");
+        self.xml
+            .write_attribute_fmt("xlink:href", format_args!("data:{mime};base64,{data}"));
+        self.xml.end_element();
+    }
+
+    /// Render a link as a transparent, clickable area.
+    ///
+    /// Only links to URLs are supported as the other destinations point into
+    /// other pages of the document.
+    fn render_link(&mut self, dest: &Destination, size: Size) {
+        let Destination::Url(url) = dest else { return };
+        self.xml.start_element("a");
+        self.xml.write_attribute("xlink:href", url);
+        self.xml.start_element("rect");
+        self.xml.write_attribute("width", &size.x.to_pt());
+        self.xml.write_attribute("height", &size.y.to_pt());
+        self.xml.write_attribute("fill", "transparent");
+        self.xml.end_element();
+        self.xml.end_element();
+    }
+}
+
+/// Write the fill attributes for an SVG paint with the given alpha value.
+fn write_fill(xml: &mut XmlWriter, paint: &str, alpha: u8) {
+    xml.write_attribute("fill", paint);
+    if alpha != u8::MAX {
+        xml.write_attribute("fill-opacity", &(alpha as f64 / 255.0));
+    }
+}
+
+/// Write the stroke attributes for a stroke, painted with the given SVG paint
+/// and alpha value.
+fn write_stroke(xml: &mut XmlWriter, stroke: &Stroke, paint: &str, alpha: u8) {
+    xml.write_attribute("stroke", paint);
+    if alpha != u8::MAX {
+        xml.write_attribute("stroke-opacity", &(alpha as f64 / 255.0));
+    }
+
+    xml.write_attribute("stroke-width", &stroke.thickness.to_pt());
+    xml.write_attribute(
+        "stroke-linecap",
+        match stroke.line_cap {
+            LineCap::Butt => "butt",
+            LineCap::Round => "round",
+            LineCap::Square => "square",
+        },
+    );
+    xml.write_attribute(
+        "stroke-linejoin",
+        match stroke.line_join {
+            LineJoin::Miter => "miter",
+            LineJoin::Round => "round",
+            LineJoin::Bevel => "bevel",
+        },
+    );
+    xml.write_attribute("stroke-miterlimit", &stroke.miter_limit.0);
+
+    if let Some(pattern) = &stroke.dash_pattern {
+        let mut array = String::new();
+        for (i, length) in pattern.array.iter().enumerate() {
+            if i > 0 {
+                array.push(' ');
+            }
+            write!(array, "{}", length.to_pt()).unwrap();
+        }
+        xml.write_attribute("stroke-dasharray", &array);
+        xml.write_attribute("stroke-dashoffset", &pattern.phase.to_pt());
+    }
+}
+
+/// Convert a color into its SVG hex notation, ignoring the alpha channel.
+fn svg_color(color: Color) -> EcoString {
+    let c = color.to_rgba();
+    eco_format!("#{:02x}{:02x}{:02x}", c.r, c.g, c.b)
+}
+
+/// Convert a transform into an SVG matrix.
+fn svg_matrix(transform: Transform) -> EcoString {
+    let Transform { sx, ky, kx, sy, tx, ty } = transform;
+    eco_format!(
+        "matrix({} {} {} {} {} {})",
+        sx.get(),
+        ky.get(),
+        kx.get(),
+        sy.get(),
+        tx.to_pt(),
+        ty.to_pt()
+    )
+}
+
+/// Convert a shape's geometry into SVG path data.
+fn convert_geometry(geometry: &Geometry) -> String {
+    let mut d = String::new();
+    match geometry {
+        Geometry::Line(target) => {
+            write!(d, "M 0 0 L {} {}", target.x.to_pt(), target.y.to_pt()).unwrap();
+        }
+        Geometry::Rect(size) => {
+            let w = size.x.to_pt();
+            let h = size.y.to_pt();
+            write!(d, "M 0 0 L {w} 0 L {w} {h} L 0 {h} Z").unwrap();
+        }
+        Geometry::Path(path) => {
+            for item in &path.0 {
+                match item {
+                    PathItem::MoveTo(p) => {
+                        write!(d, "M {} {} ", p.x.to_pt(), p.y.to_pt()).unwrap();
+                    }
+                    PathItem::LineTo(p) => {
+                        write!(d, "L {} {} ", p.x.to_pt(), p.y.to_pt()).unwrap();
+                    }
+                    PathItem::CubicTo(p1, p2, p3) => {
+                        write!(
+                            d,
+                            "C {} {} {} {} {} {} ",
+                            p1.x.to_pt(),
+                            p1.y.to_pt(),
+                            p2.x.to_pt(),
+                            p2.y.to_pt(),
+                            p3.x.to_pt(),
+                            p3.y.to_pt()
+                        )
+                        .unwrap();
+                    }
+                    PathItem::ClosePath => d.push_str("Z "),
+                }
+            }
+        }
+    }
+    d
+}
+
+/// Allows to build SVG path data from glyph outlines.
+struct SvgPathBuilder(String);
+
+impl OutlineBuilder for SvgPathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        write!(self.0, "M {x} {y} ").unwrap();
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        write!(self.0, "L {x} {y} ").unwrap();
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        write!(self.0, "Q {x1} {y1} {x} {y} ").unwrap();
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        write!(self.0, "C {x1} {y1} {x2} {y2} {x} {y} ").unwrap();
+    }
+
+    fn close(&mut self) {
+        self.0.push_str("Z ");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::Ratio;
+    use crate::syntax::Span;
+
+    #[test]
+    fn test_svg_export() {
+        let size = |w, h| Size::new(Abs::pt(w), Abs::pt(h));
+        let rect = |w, h| Geometry::Rect(size(w, h));
+
+        let mut inner = Frame::new(size(10.0, 10.0));
+        inner.push(
+            Point::zero(),
+            FrameItem::Shape(
+                rect(10.0, 10.0).filled(Color::RED.into()),
+                Span::detached(),
+            ),
+        );
+
+        let mut group = GroupItem::new(inner);
+        group.transform = Transform::scale(Ratio::new(2.0), Ratio::new(2.0));
+        group.clips = true;
+
+        let mut frame = Frame::new(size(40.0, 20.0));
+        frame.push(Point::new(Abs::pt(5.0), Abs::zero()), FrameItem::Group(group));
+        frame.push(
+            Point::zero(),
+            FrameItem::Meta(
+                Meta::Link(Destination::Url("https://typst.app".into())),
+                size(40.0, 20.0),
+            ),
+        );
+
+        let svg = svg(&frame);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r#"viewBox="0 0 40 20""#));
+        assert!(svg.contains(r#"width="40pt""#));
+        assert!(svg.contains(r#"transform="translate(5 0)""#));
+        assert!(svg.contains(r#"transform="matrix(2 0 0 2 0 0)""#));
+        assert!(svg.contains(r#"clip-path="url(#c0)""#));
+        assert!(svg.contains(r##"fill="#ff4136""##));
+        assert!(svg.contains(r#"d="M 0 0 L 10 0 L 10 10 L 0 10 Z""#));
+        assert!(svg.contains(r#"<a xlink:href="https://typst.app">"#));
+        assert!(!svg.contains("<defs"));
+    }
+}
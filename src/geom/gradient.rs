@@ -0,0 +1,69 @@
+use ecow::{eco_format, EcoVec};
+
+use super::*;
+use crate::util::pretty_array_like;
+
+/// A linear color gradient.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct LinearGradient {
+    /// The color stops as pairs of offsets along the gradient's axis and
+    /// colors. There are at least two stops and their offsets are ascending
+    /// and between `0%` and `100%`.
+    pub stops: Vec<(Ratio, Color)>,
+    /// The direction of the gradient's axis. Zero degrees means left-to-right
+    /// and positive angles rotate clockwise.
+    pub angle: Angle,
+}
+
+impl LinearGradient {
+    /// The start and end point of the gradient's axis when filling a box of
+    /// the given size whose top-left corner is at the origin.
+    ///
+    /// The axis passes through the box's center and is just long enough for
+    /// the first and last stop to touch opposite corners of the box.
+    pub fn axis(&self, size: Size) -> (Point, Point) {
+        let (sin, cos) = self.angle.to_rad().sin_cos();
+        let half = (size.x * cos.abs() + size.y * sin.abs()) / 2.0;
+        let center = size.to_point() / 2.0;
+        let delta = Point::new(half * cos, half * sin);
+        (center - delta, center + delta)
+    }
+}
+
+impl Debug for LinearGradient {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut parts: EcoVec<_> = self
+            .stops
+            .iter()
+            .map(|(offset, color)| eco_format!("({offset:?}, {color:?})"))
+            .collect();
+
+        if !self.angle.is_zero() {
+            parts.push(eco_format!("angle: {:?}", self.angle));
+        }
+
+        write!(f, "gradient.linear{}", pretty_array_like(&parts, false))
+    }
+}
+
+cast_from_value! {
+    LinearGradient: "gradient",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_gradient_axis() {
+        let gradient = LinearGradient {
+            stops: vec![(Ratio::zero(), Color::RED), (Ratio::one(), Color::BLUE)],
+            angle: Angle::zero(),
+        };
+
+        let size = Size::new(Abs::pt(100.0), Abs::pt(50.0));
+        let (start, end) = gradient.axis(size);
+        assert_eq!(start, Point::new(Abs::zero(), Abs::pt(25.0)));
+        assert_eq!(end, Point::new(Abs::pt(100.0), Abs::pt(25.0)));
+    }
+}
@@ -11,6 +11,7 @@ mod dir;
 mod ellipse;
 mod em;
 mod fr;
+mod gradient;
 mod length;
 mod paint;
 mod path;
@@ -35,6 +36,7 @@ pub use self::dir::*;
 pub use self::ellipse::*;
 pub use self::em::*;
 pub use self::fr::*;
+pub use self::gradient::*;
 pub use self::length::*;
 pub use self::paint::*;
 pub use self::path::*;
@@ -7,6 +7,8 @@ use super::*;
 pub enum Paint {
     /// A solid color.
     Solid(Color),
+    /// A linear gradient.
+    Gradient(LinearGradient),
 }
 
 impl<T: Into<Color>> From<T> for Paint {
@@ -19,6 +21,7 @@ impl Debug for Paint {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
             Self::Solid(color) => color.fmt(f),
+            Self::Gradient(gradient) => gradient.fmt(f),
         }
     }
 }
@@ -26,10 +29,14 @@ impl Debug for Paint {
 cast_from_value! {
     Paint,
     color: Color => Self::Solid(color),
+    gradient: LinearGradient => Self::Gradient(gradient),
 }
 
 cast_to_value! {
-    Paint::Solid(color): Paint => Value::Color(color)
+    v: Paint => match v {
+        Paint::Solid(color) => Value::Color(color),
+        Paint::Gradient(gradient) => Value::dynamic(gradient),
+    }
 }
 
 /// A color in a dynamic format.
@@ -72,6 +79,15 @@ impl Color {
         }
     }
 
+    /// Convert this color to CMYK.
+    pub fn to_cmyk(self) -> CmykColor {
+        match self {
+            Self::Luma(luma) => luma.to_cmyk(),
+            Self::Rgba(rgba) => rgba.to_cmyk(),
+            Self::Cmyk(cmyk) => cmyk,
+        }
+    }
+
     /// Lighten this color by the given factor.
     pub fn lighten(self, factor: Ratio) -> Self {
         match self {
@@ -98,6 +114,77 @@ impl Color {
             Self::Cmyk(cmyk) => Self::Cmyk(cmyk.negate()),
         }
     }
+
+    /// Mix colors with the given weights in a color space.
+    ///
+    /// The weights are normalized, so they don't need to sum to one. Returns
+    /// `None` if there are no colors or all weights are zero.
+    pub fn mix(colors: &[(Self, f64)], space: MixSpace) -> Option<Self> {
+        let total: f64 = colors.iter().map(|&(_, weight)| weight).sum();
+        if !(total > 0.0) {
+            return None;
+        }
+
+        let mut acc = [0.0; 4];
+        for &(color, weight) in colors {
+            let RgbaColor { r, g, b, a } = color.to_rgba();
+            let factor = weight / total;
+            acc[0] += factor * space.decode(r);
+            acc[1] += factor * space.decode(g);
+            acc[2] += factor * space.decode(b);
+            acc[3] += factor * a as f64 / 255.0;
+        }
+
+        Some(Self::Rgba(RgbaColor::new(
+            space.encode(acc[0]),
+            space.encode(acc[1]),
+            space.encode(acc[2]),
+            round_u8(255.0 * acc[3].clamp(0.0, 1.0)),
+        )))
+    }
+}
+
+/// The color space in which colors are mixed.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MixSpace {
+    /// Mix in linear RGB, which is perceptually more even.
+    #[default]
+    LinearRgb,
+    /// Mix the gamma-encoded sRGB channels directly.
+    Srgb,
+}
+
+impl MixSpace {
+    /// Convert an 8-bit sRGB channel into this space.
+    fn decode(self, channel: u8) -> f64 {
+        let c = channel as f64 / 255.0;
+        match self {
+            Self::LinearRgb if c <= 0.04045 => c / 12.92,
+            Self::LinearRgb => ((c + 0.055) / 1.055).powf(2.4),
+            Self::Srgb => c,
+        }
+    }
+
+    /// Convert a channel in this space back into an 8-bit sRGB channel.
+    fn encode(self, c: f64) -> u8 {
+        let c = c.clamp(0.0, 1.0);
+        round_u8(
+            255.0
+                * match self {
+                    Self::LinearRgb if c <= 0.0031308 => 12.92 * c,
+                    Self::LinearRgb => 1.055 * c.powf(1.0 / 2.4) - 0.055,
+                    Self::Srgb => c,
+                },
+        )
+    }
+}
+
+cast_from_value! {
+    MixSpace,
+    /// Mix in linear RGB.
+    "linear-rgb" => Self::LinearRgb,
+    /// Mix the sRGB channels directly.
+    "srgb" => Self::Srgb,
 }
 
 impl Debug for Color {
@@ -184,6 +271,45 @@ impl RgbaColor {
         Self { r, g, b, a }
     }
 
+    /// Construct an opaque RGBA color from hue, saturation, and lightness.
+    ///
+    /// Saturation and lightness are clamped to the range from zero to one.
+    pub fn from_hsl(hue: Angle, saturation: f64, lightness: f64) -> Self {
+        let h = hue.to_deg().rem_euclid(360.0) / 60.0;
+        let s = saturation.clamp(0.0, 1.0);
+        let l = lightness.clamp(0.0, 1.0);
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c / 2.0;
+        let f = |v: f64| round_u8(255.0 * (v + m));
+        Self::new(f(r), f(g), f(b), u8::MAX)
+    }
+
+    /// Convert this color to CMYK.
+    ///
+    /// This is a naive conversion that doesn't take any color profile into
+    /// account. The alpha channel is dropped.
+    pub fn to_cmyk(self) -> CmykColor {
+        let r = self.r as f64 / 255.0;
+        let g = self.g as f64 / 255.0;
+        let b = self.b as f64 / 255.0;
+        let k = 1.0 - r.max(g).max(b);
+        if k >= 1.0 {
+            return CmykColor::new(0, 0, 0, u8::MAX);
+        }
+
+        let f = |c: f64| round_u8(255.0 * (1.0 - c - k) / (1.0 - k));
+        CmykColor::new(f(r), f(g), f(b), round_u8(255.0 * k))
+    }
+
     /// Lighten this color by a factor.
     ///
     /// The alpha channel is not affected.
@@ -399,6 +525,46 @@ mod tests {
         test("111b", 0x11, 0x11, 0x11, 0xbb);
     }
 
+    #[test]
+    fn test_rgba_to_cmyk() {
+        #[track_caller]
+        fn test(rgba: RgbaColor, c: u8, m: u8, y: u8, k: u8) {
+            assert_eq!(rgba.to_cmyk(), CmykColor::new(c, m, y, k));
+        }
+
+        test(RgbaColor::new(0, 0, 0, 255), 0, 0, 0, 255);
+        test(RgbaColor::new(255, 255, 255, 255), 0, 0, 0, 0);
+        test(RgbaColor::new(255, 0, 0, 255), 0, 255, 255, 0);
+        test(RgbaColor::new(0, 128, 128, 255), 255, 0, 0, 127);
+    }
+
+    #[test]
+    fn test_hsl_to_rgba() {
+        let test = |h: f64, s: f64, l: f64, rgba: RgbaColor| {
+            assert_eq!(RgbaColor::from_hsl(Angle::deg(h), s, l), rgba);
+        };
+
+        test(0.0, 1.0, 0.5, RgbaColor::new(255, 0, 0, 255));
+        test(120.0, 1.0, 0.25, RgbaColor::new(0, 128, 0, 255));
+        test(-120.0, 1.0, 0.5, RgbaColor::new(0, 0, 255, 255));
+        test(210.0, 0.5, 0.6, RgbaColor::new(102, 153, 204, 255));
+        test(0.0, 0.0, 1.0, RgbaColor::new(255, 255, 255, 255));
+    }
+
+    #[test]
+    fn test_mix_colors() {
+        let black = Color::BLACK;
+        let white = Color::WHITE;
+        let mix = |space| Color::mix(&[(black, 1.0), (white, 1.0)], space).unwrap();
+        assert_eq!(mix(MixSpace::Srgb), Color::Rgba(RgbaColor::new(128, 128, 128, 255)));
+        assert_eq!(
+            mix(MixSpace::LinearRgb),
+            Color::Rgba(RgbaColor::new(188, 188, 188, 255))
+        );
+        assert_eq!(Color::mix(&[(black, 2.0)], MixSpace::LinearRgb), Some(black));
+        assert_eq!(Color::mix(&[(black, 0.0)], MixSpace::LinearRgb), None);
+    }
+
     #[test]
     fn test_parse_invalid_colors() {
         #[track_caller]
@@ -15,13 +15,19 @@ pub fn rounded_rect(
             geometry: fill_geometry(size, radius),
             fill,
             stroke: if stroke.is_uniform() { stroke.top.clone() } else { None },
+            fill_rule: FillRule::default(),
         });
     }
 
     if !stroke.is_uniform() {
         for (path, stroke) in stroke_segments(size, radius, stroke) {
             if stroke.is_some() {
-                res.push(Shape { geometry: Geometry::Path(path), fill: None, stroke });
+                res.push(Shape {
+                    geometry: Geometry::Path(path),
+                    fill: None,
+                    stroke,
+                    fill_rule: FillRule::default(),
+                });
             }
         }
     }
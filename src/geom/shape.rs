@@ -9,6 +9,20 @@ pub struct Shape {
     pub fill: Option<Paint>,
     /// The shape's border stroke.
     pub stroke: Option<Stroke>,
+    /// How to decide which parts of a self-intersecting shape are filled.
+    pub fill_rule: FillRule,
+}
+
+/// How to decide which points lie inside of a shape's fill.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum FillRule {
+    /// A point is inside if a ray from it crosses the outline a different
+    /// number of times from left to right than from right to left.
+    #[default]
+    NonZero,
+    /// A point is inside if a ray from it crosses the outline an odd number of
+    /// times.
+    EvenOdd,
 }
 
 /// A shape's geometry.
@@ -25,11 +39,50 @@ pub enum Geometry {
 impl Geometry {
     /// Fill the geometry without a stroke.
     pub fn filled(self, fill: Paint) -> Shape {
-        Shape { geometry: self, fill: Some(fill), stroke: None }
+        Shape {
+            geometry: self,
+            fill: Some(fill),
+            stroke: None,
+            fill_rule: FillRule::default(),
+        }
     }
 
     /// Stroke the geometry without a fill.
     pub fn stroked(self, stroke: Stroke) -> Shape {
-        Shape { geometry: self, fill: None, stroke: Some(stroke) }
+        Shape {
+            geometry: self,
+            fill: None,
+            stroke: Some(stroke),
+            fill_rule: FillRule::default(),
+        }
+    }
+
+    /// The smallest box containing the geometry, as its top-left corner
+    /// relative to the shape's origin and its size.
+    ///
+    /// For paths, this includes the control points of bezier curves.
+    pub fn bbox(&self) -> (Point, Size) {
+        let points: Vec<Point> = match self {
+            Self::Line(target) => vec![Point::zero(), *target],
+            Self::Rect(size) => vec![Point::zero(), size.to_point()],
+            Self::Path(path) => path
+                .0
+                .iter()
+                .flat_map(|item| match *item {
+                    PathItem::MoveTo(p) | PathItem::LineTo(p) => vec![p],
+                    PathItem::CubicTo(p1, p2, p3) => vec![p1, p2, p3],
+                    PathItem::ClosePath => vec![],
+                })
+                .collect(),
+        };
+
+        let Some(&first) = points.first() else {
+            return (Point::zero(), Size::zero());
+        };
+
+        let (min, max) = points
+            .iter()
+            .fold((first, first), |(min, max), &p| (min.min(p), max.max(p)));
+        (min, (max - min).to_size())
     }
 }
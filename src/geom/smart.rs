@@ -11,16 +11,32 @@ pub enum Smart<T> {
 
 impl<T> Smart<T> {
     /// Whether the value is `Auto`.
-    pub fn is_auto(&self) -> bool {
+    pub const fn is_auto(&self) -> bool {
         matches!(self, Self::Auto)
     }
 
     /// Whether this holds a custom value.
-    pub fn is_custom(&self) -> bool {
+    pub const fn is_custom(&self) -> bool {
         matches!(self, Self::Custom(_))
     }
 
-    /// Returns a reference the contained custom value.
+    /// Converts from `&Smart<T>` to `Smart<&T>`.
+    pub const fn as_ref(&self) -> Smart<&T> {
+        match self {
+            Self::Auto => Smart::Auto,
+            Self::Custom(x) => Smart::Custom(x),
+        }
+    }
+
+    /// Converts from `&mut Smart<T>` to `Smart<&mut T>`.
+    pub fn as_mut(&mut self) -> Smart<&mut T> {
+        match self {
+            Self::Auto => Smart::Auto,
+            Self::Custom(x) => Smart::Custom(x),
+        }
+    }
+
+    /// Returns the contained custom value.
     /// If the value is [`Smart::Auto`], `None` is returned.
     pub fn as_custom(self) -> Option<T> {
         match self {
@@ -52,6 +68,18 @@ impl<T> Smart<T> {
         }
     }
 
+    /// Returns `Auto` if `self` is `Auto`, otherwise calls `f` with the
+    /// contained custom value and returns the result.
+    pub fn and_then<F, U>(self, f: F) -> Smart<U>
+    where
+        F: FnOnce(T) -> Smart<U>,
+    {
+        match self {
+            Self::Auto => Smart::Auto,
+            Self::Custom(x) => f(x),
+        }
+    }
+
     /// Keeps `self` if it contains a custom value, otherwise returns `other`.
     pub fn or(self, other: Smart<T>) -> Self {
         match self {
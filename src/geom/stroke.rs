@@ -289,6 +289,7 @@ cast_from_value! {
     "densely-dash-dotted" => vec![Abs::pt(3.0).into(), Abs::pt(1.0).into(), DashLength::LineWidth, Abs::pt(1.0).into()].into(),
     "loosely-dash-dotted" => vec![Abs::pt(3.0).into(), Abs::pt(4.0).into(), DashLength::LineWidth, Abs::pt(4.0).into()].into(),
     array: Vec<DashLength> => {
+        check_dash_array(&array)?;
         Self {
             array,
             phase: Length::zero(),
@@ -296,6 +297,7 @@ cast_from_value! {
     },
     mut dict: Dict => {
         let array: Vec<DashLength> = dict.take("array")?.cast()?;
+        check_dash_array(&array)?;
         let phase = dict.take("phase").ok().map(Length::cast)
             .transpose()?.unwrap_or(Length::zero());
 
@@ -308,6 +310,19 @@ cast_from_value! {
     },
 }
 
+/// Ensure that a non-empty dash array doesn't consist only of zero lengths, as
+/// such a pattern would neither draw a dash nor advance along the line.
+fn check_dash_array(array: &[DashLength]) -> StrResult<()> {
+    if !array.is_empty()
+        && array
+            .iter()
+            .all(|l| matches!(l, DashLength::Length(l) if l.is_zero()))
+    {
+        return Err("dash array must contain at least one non-zero length".into());
+    }
+    Ok(())
+}
+
 impl Resolve for DashPattern {
     type Output = DashPattern<Abs>;
 
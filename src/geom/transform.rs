@@ -68,6 +68,28 @@ impl Transform {
     pub fn post_concat(self, next: Self) -> Self {
         next.pre_concat(self)
     }
+
+    /// The inverse transformation, if there is one.
+    ///
+    /// Transformations that collapse the plane onto a line or point, like a
+    /// scale by zero, have no inverse.
+    pub fn invert(self) -> Option<Self> {
+        let (sx, ky, kx, sy) =
+            (self.sx.get(), self.ky.get(), self.kx.get(), self.sy.get());
+        let det = sx * sy - kx * ky;
+        if det.abs() < 1e-12 {
+            return None;
+        }
+
+        Some(Self {
+            sx: Ratio::new(sy / det),
+            ky: Ratio::new(-ky / det),
+            kx: Ratio::new(-kx / det),
+            sy: Ratio::new(sx / det),
+            tx: (self.ty * kx - self.tx * sy) / det,
+            ty: (self.tx * ky - self.ty * sx) / det,
+        })
+    }
 }
 
 impl Default for Transform {
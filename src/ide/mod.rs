@@ -2,15 +2,14 @@
 
 mod analyze;
 mod complete;
-mod highlight;
 mod jump;
 mod tooltip;
 
 pub use self::analyze::analyze_labels;
 pub use self::complete::*;
-pub use self::highlight::*;
 pub use self::jump::*;
 pub use self::tooltip::*;
+pub use crate::syntax::{highlight_html, highlight_node as highlight, Tag};
 
 use std::fmt::Write;
 
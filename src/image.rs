@@ -11,6 +11,7 @@ use ecow::{EcoString, EcoVec};
 use image::codecs::gif::GifDecoder;
 use image::codecs::jpeg::JpegDecoder;
 use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
 use image::io::Limits;
 use image::{ImageDecoder, ImageResult};
 use usvg::{TreeParsing, TreeTextToPath};
@@ -156,6 +157,28 @@ pub enum ImageFormat {
     Vector(VectorFormat),
 }
 
+impl ImageFormat {
+    /// Detect the format of an image from the signature at the start of its
+    /// data.
+    ///
+    /// Returns `None` for SVG images as they have no reliable signature.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        let raster = if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            RasterFormat::Png
+        } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            RasterFormat::Jpg
+        } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            RasterFormat::Gif
+        } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP".as_slice())
+        {
+            RasterFormat::Webp
+        } else {
+            return None;
+        };
+        Some(Self::Raster(raster))
+    }
+}
+
 /// A raster graphics format.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum RasterFormat {
@@ -165,6 +188,9 @@ pub enum RasterFormat {
     Jpg,
     /// Raster format that is typically used for short animated clips.
     Gif,
+    /// Raster format with lossy or lossless compression that is common on the
+    /// web.
+    Webp,
 }
 
 /// A vector graphics format.
@@ -180,6 +206,7 @@ impl From<RasterFormat> for image::ImageFormat {
             RasterFormat::Png => image::ImageFormat::Png,
             RasterFormat::Jpg => image::ImageFormat::Jpeg,
             RasterFormat::Gif => image::ImageFormat::Gif,
+            RasterFormat::Webp => image::ImageFormat::WebP,
         }
     }
 }
@@ -250,6 +277,7 @@ fn decode_raster(data: &Buffer, format: RasterFormat) -> StrResult<Arc<DecodedIm
         RasterFormat::Jpg => decode_with(JpegDecoder::new(cursor)),
         RasterFormat::Png => decode_with(PngDecoder::new(cursor)),
         RasterFormat::Gif => decode_with(GifDecoder::new(cursor)),
+        RasterFormat::Webp => decode_with(WebPDecoder::new(cursor)),
     }
     .map_err(format_image_error)?;
 
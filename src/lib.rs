@@ -17,7 +17,7 @@
 //!   per page with items at fixed positions.
 //! - **Exporting:**
 //!   These frames can finally be exported into an output format (currently
-//!   supported are [PDF] and [raster images]).
+//!   supported are [PDF], [raster images], and [SVG]).
 //!
 //! [tokens]: syntax::SyntaxKind
 //! [parsed]: syntax::parse
@@ -31,6 +31,7 @@
 //! [frame]: doc::Frame
 //! [PDF]: export::pdf
 //! [raster images]: export::render
+//! [SVG]: export::svg
 
 #![recursion_limit = "1000"]
 #![allow(clippy::comparison_chain)]
@@ -52,22 +53,24 @@ pub mod image;
 pub mod model;
 pub mod syntax;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use comemo::{Prehashed, Track, TrackedMut};
 
-use crate::diag::{FileResult, SourceResult};
-use crate::doc::Document;
-use crate::eval::{Datetime, Library, Route, Tracer};
+use crate::diag::{FileResult, PackageError, SourceResult};
+use crate::doc::{Document, Frame};
+use crate::eval::{Datetime, Library, PackageSpec, Route, Tracer};
 use crate::font::{Font, FontBook};
 use crate::syntax::{Source, SourceId};
-use crate::util::Buffer;
+use crate::util::{Buffer, PathExt};
 
 /// Compile a source file into a fully layouted document.
-#[tracing::instrument(skip(world))]
-pub fn compile(world: &dyn World) -> SourceResult<Document> {
+///
+/// Errors are returned while warnings are collected in the `tracer`, even if
+/// compilation fails.
+#[tracing::instrument(skip(world, tracer))]
+pub fn compile(world: &dyn World, tracer: &mut Tracer) -> SourceResult<Document> {
     let route = Route::default();
-    let mut tracer = Tracer::default();
 
     // Call `track` just once to keep comemo's ID stable.
     let world = world.track();
@@ -86,6 +89,42 @@ pub fn compile(world: &dyn World) -> SourceResult<Document> {
     model::typeset(world, tracer, &module.content())
 }
 
+/// Compile a source file and return only the page with the given index.
+///
+/// This is meant for previews, which only need to render the visible page.
+/// Note that the whole document is still laid out: Page breaking is
+/// sequential and introspection (counters, references, outlines, ...) may
+/// depend on any page, so a page can't be laid out without the others. What
+/// is saved is the export of all other pages. Across compilations, unchanged
+/// parts of the document are not laid out again thanks to memoization, which
+/// keeps previews of large documents responsive.
+///
+/// Returns `None` if the document has fewer pages.
+#[tracing::instrument(skip(world, tracer))]
+pub fn compile_page(
+    world: &dyn World,
+    tracer: &mut Tracer,
+    index: usize,
+) -> SourceResult<Option<Frame>> {
+    let mut document = compile(world, tracer)?;
+    Ok((index < document.pages.len()).then(|| document.pages.swap_remove(index)))
+}
+
+/// Evict memoized results that haven't been used in the last `max_age`
+/// compilations.
+///
+/// Compilation memoizes parsing, evaluation, and layout in a global cache so
+/// that recompiling a changed document is fast. Long-running embedders (like
+/// a watcher or a language server) should call this after each compilation
+/// to keep the cache from growing without bounds. Passing `0` clears the
+/// whole cache.
+///
+/// This must only be called between compilations and not while a compilation
+/// is running on another thread.
+pub fn evict(max_age: usize) {
+    comemo::evict(max_age);
+}
+
 /// The environment in which typesetting occurs.
 #[comemo::track]
 pub trait World {
@@ -117,9 +156,308 @@ pub trait World {
     /// Try to access a file at a path.
     fn file(&self, path: &Path) -> FileResult<Buffer>;
 
+    /// Try to compute a hash of the contents of the file at a path.
+    ///
+    /// Incremental tooling can compare this across compilations to detect
+    /// changed files. Implementations that can determine it without reading
+    /// the whole file (e.g. from a stored digest) should override this.
+    ///
+    /// Defaults to hashing the file's contents.
+    fn file_hash(&self, path: &Path) -> FileResult<u128> {
+        self.file(path).map(|buffer| util::hash128(&buffer))
+    }
+
+    /// Try to resolve the root directory of a package.
+    ///
+    /// An import like `#import "@preview/example:0.1.0"` calls this to find
+    /// out where the package lives and then evaluates the package's `lib.typ`
+    /// in that directory. Implementations are free to back this with a cache
+    /// directory, a network download, or both.
+    ///
+    /// Defaults to reporting the package as not found.
+    fn package(&self, spec: &PackageSpec) -> FileResult<PathBuf> {
+        Err(PackageError::NotFound(spec.clone()).into())
+    }
+
     /// Get the current date.
     ///
     /// If no offset is specified, the local date should be chosen. Otherwise,
     /// the UTC date should be chosen with the corresponding offset in hours.
     fn today(&self, offset: Option<i64>) -> Option<Datetime>;
 }
+
+/// A world that serves in-memory contents for some paths and delegates
+/// everything else to another world.
+///
+/// This is useful for editor integrations, where the contents of unsaved
+/// buffers differ from those on disk. An overlaid path shadows the base
+/// world's [`file`](World::file) as well as its [`resolve`](World::resolve)
+/// and [`source`](World::source). If the main file is overlaid, the overlay
+/// also replaces [`main`](World::main).
+pub struct OverlayWorld<'a> {
+    /// The world to delegate to.
+    base: &'a dyn World,
+    /// The overlaid main source, if any.
+    main: Option<Source>,
+    /// The other overlaid sources.
+    sources: Vec<Source>,
+}
+
+impl<'a> OverlayWorld<'a> {
+    /// Create an overlay without any overlaid files.
+    pub fn new(base: &'a dyn World) -> Self {
+        Self { base, main: None, sources: vec![] }
+    }
+
+    /// Serve the given text for a path instead of the base world's contents.
+    ///
+    /// Overlaid sources other than the main file get ids counting down from
+    /// just below the detached id, so they don't collide with the ids of the
+    /// base world.
+    pub fn set(&mut self, path: &Path, text: String) {
+        let path = path.normalize();
+        if path == self.base.main().path() {
+            self.main = Some(Source::new(self.base.main().id(), &path, text));
+        } else if let Some(source) = self.sources.iter_mut().find(|s| s.path() == path) {
+            source.replace(text);
+        } else {
+            let id = SourceId::from_u16(u16::MAX - 1 - self.sources.len() as u16);
+            self.sources.push(Source::new(id, &path, text));
+        }
+    }
+
+    /// Builder-style variant of [`set`](Self::set).
+    pub fn with(mut self, path: &Path, text: String) -> Self {
+        self.set(path, text);
+        self
+    }
+
+    /// Stop serving the overlaid contents for a path.
+    pub fn remove(&mut self, path: &Path) {
+        let path = path.normalize();
+        if self.main.as_ref().map_or(false, |main| main.path() == path) {
+            self.main = None;
+        }
+
+        // Keep the positions of the remaining sources stable since they
+        // determine the ids of those added later.
+        if let Some(source) = self.sources.iter_mut().find(|s| s.path() == path) {
+            *source = Source::new(source.id(), Path::new(""), String::new());
+        }
+    }
+
+    /// The overlaid source for a path, if any.
+    fn overlaid(&self, path: &Path) -> Option<&Source> {
+        let path = path.normalize();
+        self.main
+            .iter()
+            .chain(&self.sources)
+            .find(|source| source.path() == path && !path.as_os_str().is_empty())
+    }
+}
+
+impl World for OverlayWorld<'_> {
+    fn root(&self) -> &Path {
+        self.base.root()
+    }
+
+    fn library(&self) -> &Prehashed<Library> {
+        self.base.library()
+    }
+
+    fn main(&self) -> &Source {
+        self.main.as_ref().unwrap_or_else(|| self.base.main())
+    }
+
+    fn resolve(&self, path: &Path) -> FileResult<SourceId> {
+        match self.overlaid(path) {
+            Some(source) => Ok(source.id()),
+            None => self.base.resolve(path),
+        }
+    }
+
+    fn source(&self, id: SourceId) -> &Source {
+        self.main
+            .iter()
+            .chain(&self.sources)
+            .find(|source| source.id() == id)
+            .unwrap_or_else(|| self.base.source(id))
+    }
+
+    fn book(&self) -> &Prehashed<FontBook> {
+        self.base.book()
+    }
+
+    fn font(&self, id: usize) -> Option<Font> {
+        self.base.font(id)
+    }
+
+    fn file(&self, path: &Path) -> FileResult<Buffer> {
+        match self.overlaid(path) {
+            Some(source) => Ok(source.text().as_bytes().into()),
+            None => self.base.file(path),
+        }
+    }
+
+    fn file_hash(&self, path: &Path) -> FileResult<u128> {
+        match self.overlaid(path) {
+            Some(_) => self.file(path).map(|buffer| util::hash128(&buffer)),
+            None => self.base.file_hash(path),
+        }
+    }
+
+    fn package(&self, spec: &PackageSpec) -> FileResult<PathBuf> {
+        self.base.package(spec)
+    }
+
+    fn today(&self, offset: Option<i64>) -> Option<Datetime> {
+        self.base.today(offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diag::{bail, FileError};
+    use crate::eval::{LangItems, Module};
+    use crate::geom::{Abs, Dir};
+    use crate::model::{Content, Styles};
+
+    /// A world with two source files and a hash that marks delegation.
+    struct BaseWorld {
+        library: Prehashed<Library>,
+        book: Prehashed<FontBook>,
+        sources: Vec<Source>,
+    }
+
+    impl BaseWorld {
+        fn new() -> Self {
+            let source = |id, path, text: &str| {
+                Source::new(SourceId::from_u16(id), Path::new(path), text.into())
+            };
+
+            Self {
+                library: Prehashed::new(library()),
+                book: Prehashed::new(FontBook::new()),
+                sources: vec![source(0, "main.typ", "Main"), source(1, "lib.typ", "Lib")],
+            }
+        }
+    }
+
+    impl World for BaseWorld {
+        fn library(&self) -> &Prehashed<Library> {
+            &self.library
+        }
+
+        fn main(&self) -> &Source {
+            &self.sources[0]
+        }
+
+        fn resolve(&self, path: &Path) -> FileResult<SourceId> {
+            self.sources
+                .iter()
+                .find(|source| source.path() == path)
+                .map(Source::id)
+                .ok_or_else(|| FileError::NotFound(path.into()))
+        }
+
+        fn source(&self, id: SourceId) -> &Source {
+            &self.sources[usize::from(id.into_u16())]
+        }
+
+        fn book(&self) -> &Prehashed<FontBook> {
+            &self.book
+        }
+
+        fn font(&self, _: usize) -> Option<Font> {
+            None
+        }
+
+        fn file(&self, path: &Path) -> FileResult<Buffer> {
+            let id = self.resolve(path)?;
+            Ok(self.source(id).text().as_bytes().into())
+        }
+
+        fn file_hash(&self, path: &Path) -> FileResult<u128> {
+            self.resolve(path).map(|_| 7)
+        }
+
+        fn today(&self, _: Option<i64>) -> Option<Datetime> {
+            None
+        }
+    }
+
+    /// A library without definitions whose items produce empty content.
+    fn library() -> Library {
+        let func = Content::empty().func();
+        Library {
+            global: Module::new("global"),
+            math: Module::new("math"),
+            styles: Styles::new(),
+            items: LangItems {
+                layout: |_, _, _| Ok(Document::default()),
+                em: |_| Abs::zero(),
+                dir: |_| Dir::LTR,
+                space: Content::empty,
+                linebreak: Content::empty,
+                text: |_| Content::empty(),
+                text_func: func,
+                text_str: |_| None,
+                smart_quote: |_| Content::empty(),
+                parbreak: Content::empty,
+                strong: |body| body,
+                emph: |body| body,
+                raw: |_, _, _| Content::empty(),
+                raw_languages: Vec::new,
+                link: |_| Content::empty(),
+                reference: |_, _| Content::empty(),
+                bibliography_keys: |_, _| vec![],
+                heading: |_, body| body,
+                heading_func: func,
+                list_item: |body| body,
+                enum_item: |_, body| body,
+                term_item: |_, body| body,
+                equation: |body, _| body,
+                math_align_point: Content::empty,
+                math_delimited: |_, body, _| body,
+                math_attach: |base, _, _, _, _, _, _| base,
+                math_accent: |base, _| base,
+                math_frac: |num, _| num,
+                math_root: |_, radicand| radicand,
+                mathml: |_| None,
+                library_method: |_, _, method, _, span| {
+                    bail!(span, "unknown method: {method}")
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_overlay_world() {
+        let base = BaseWorld::new();
+        let world = OverlayWorld::new(&base)
+            .with(Path::new("main.typ"), "New main".into())
+            .with(Path::new("other.typ"), "Other".into());
+
+        // The overlaid main file keeps its id.
+        let main = world.resolve(Path::new("main.typ")).unwrap();
+        assert_eq!(main, base.main().id());
+        assert_eq!(world.main().text(), "New main");
+        assert_eq!(world.source(main).text(), "New main");
+        assert_eq!(world.file(Path::new("main.typ")).unwrap().as_slice(), b"New main");
+
+        // Other overlaid files get a fresh id.
+        let other = world.resolve(Path::new("other.typ")).unwrap();
+        assert_ne!(other, main);
+        assert_eq!(world.source(other).text(), "Other");
+        assert_eq!(world.file(Path::new("other.typ")).unwrap().as_slice(), b"Other");
+        assert_ne!(world.file_hash(Path::new("other.typ")).unwrap(), 7);
+
+        // Everything else comes from the base world.
+        let lib = world.resolve(Path::new("lib.typ")).unwrap();
+        assert_eq!(world.source(lib).text(), "Lib");
+        assert_eq!(world.file(Path::new("lib.typ")).unwrap().as_slice(), b"Lib");
+        assert_eq!(world.file_hash(Path::new("lib.typ")).unwrap(), 7);
+        assert!(world.resolve(Path::new("missing.typ")).is_err());
+    }
+}
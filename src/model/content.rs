@@ -399,28 +399,49 @@ impl Content {
         text
     }
 
-    /// Traverse this content.
-    fn traverse<'a, F>(&'a self, f: &mut F)
+    /// The content directly nested in this content.
+    ///
+    /// These are the children of a sequence, the child of styled content, or
+    /// the content in an element's fields, also within arrays, in field order.
+    /// Use [`to_sequence`](Self::to_sequence) and
+    /// [`to_styled`](Self::to_styled) to tell the structural wrappers apart
+    /// from other elements.
+    pub fn children(&self) -> Vec<&Content> {
+        let mut children = vec![];
+        self.for_each_child(&mut |child| children.push(child));
+        children
+    }
+
+    /// Traverse this content and all content nested in it in pre-order,
+    /// calling `f` for each element.
+    pub fn traverse<'a, F>(&'a self, f: &mut F)
     where
         F: FnMut(&'a Content),
     {
         f(self);
+        self.for_each_child(&mut |child| child.traverse(f));
+    }
 
+    /// Call `f` for each of the [children](Self::children).
+    fn for_each_child<'a, F>(&'a self, f: &mut F)
+    where
+        F: FnMut(&'a Content),
+    {
         for attr in &self.attrs {
             match attr {
-                Attr::Child(child) => child.traverse(f),
+                Attr::Child(child) => f(child),
                 Attr::Value(value) => walk_value(value, f),
                 _ => {}
             }
         }
 
-        /// Walks a given value to find any content that matches the selector.
+        /// Calls `f` for the content in a value without descending into it.
         fn walk_value<'a, F>(value: &'a Value, f: &mut F)
         where
             F: FnMut(&'a Content),
         {
             match value {
-                Value::Content(content) => content.traverse(f),
+                Value::Content(content) => f(content),
                 Value::Array(array) => {
                     for value in array {
                         walk_value(value, f);
@@ -601,3 +622,33 @@ fn missing_field_no_default(key: &str) -> EcoString {
         Str::from(key)
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::Array;
+
+    #[test]
+    fn test_content_children() {
+        let leaf = |n: i64| Content::new(MetaElem::func()).with_field("n", n);
+        let (a, b, c) = (leaf(1), leaf(2), leaf(3));
+        assert!(a.children().is_empty());
+
+        let sequence = Content::sequence([a.clone(), b.clone()]);
+        assert!(sequence.children() == [&a, &b]);
+
+        let styled = a.clone().styled(MetaElem::set_data(vec![Meta::Hide]));
+        assert!(styled.children() == [&a]);
+
+        let items: Array =
+            [Value::Content(b.clone()), Value::Int(0), Value::Content(c.clone())]
+                .into_iter()
+                .collect();
+        let elem = leaf(0).with_field("body", a.clone()).with_field("items", items);
+        assert!(elem.children() == [&a, &b, &c]);
+
+        let mut count = 0;
+        Content::sequence([elem, styled]).traverse(&mut |_| count += 1);
+        assert_eq!(count, 7);
+    }
+}
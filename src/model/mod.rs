@@ -18,9 +18,10 @@ use std::mem::ManuallyDrop;
 
 use comemo::{Track, Tracked, TrackedMut, Validate};
 
-use crate::diag::SourceResult;
+use crate::diag::{warning, SourceResult};
 use crate::doc::Document;
 use crate::eval::Tracer;
+use crate::syntax::Span;
 use crate::World;
 
 /// Typeset content into a fully layouted document.
@@ -44,7 +45,7 @@ pub fn typeset(
     let mut introspector = ManuallyDrop::new(Introspector::new(&[]));
 
     // Relayout until all introspections stabilize.
-    // If that doesn't happen within five attempts, we give up.
+    // If that doesn't happen within five attempts, we give up and warn.
     loop {
         tracing::info!("Layout iteration {iter}");
 
@@ -68,7 +69,15 @@ pub fn typeset(
         introspector = ManuallyDrop::new(Introspector::new(&document.pages));
         iter += 1;
 
-        if iter >= 5 || introspector.validate(&constraint) {
+        if introspector.validate(&constraint) {
+            break;
+        }
+
+        if iter >= 5 {
+            tracer.warn(warning!(
+                Span::detached(),
+                "layout did not converge within 5 attempts",
+            ));
             break;
         }
     }
@@ -1992,3 +1992,380 @@ impl FuncReturn {
         self.0.cast_last_match()
     }
 }
+
+/// A visitor over the typed nodes of a syntax tree.
+///
+/// Each method receives a typed view of a node and, by default, walks into its
+/// children. Override the methods for the nodes you are interested in and call
+/// [`walk`] from them to continue into the node's children. Typed nodes are
+/// thin wrappers around untyped nodes, so visiting doesn't copy the tree.
+///
+/// ```
+/// # use typst::syntax::{ast, parse};
+/// # use ast::{AstNode, Visitor};
+/// struct Headings(Vec<usize>);
+///
+/// impl Visitor for Headings {
+///     fn visit_heading(&mut self, heading: &ast::Heading) {
+///         self.0.push(heading.level().get());
+///         ast::walk(self, heading.as_untyped());
+///     }
+/// }
+///
+/// let mut headings = Headings(vec![]);
+/// headings.visit_node(&parse("= A\n== B"));
+/// assert_eq!(headings.0, [1, 2]);
+/// ```
+pub trait Visitor {
+    /// Visit any node, dispatching to the typed method if it is an
+    /// expression.
+    fn visit_node(&mut self, node: &SyntaxNode) {
+        match Expr::cast_with_space(node) {
+            Some(expr) => self.visit_expr(&expr),
+            Option::None => walk(self, node),
+        }
+    }
+
+    /// Visit an expression by dispatching to the method for its kind.
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Text(v) => self.visit_text(v),
+            Expr::Space(v) => self.visit_space(v),
+            Expr::Linebreak(v) => self.visit_linebreak(v),
+            Expr::Parbreak(v) => self.visit_parbreak(v),
+            Expr::Escape(v) => self.visit_escape(v),
+            Expr::Shorthand(v) => self.visit_shorthand(v),
+            Expr::SmartQuote(v) => self.visit_smart_quote(v),
+            Expr::Strong(v) => self.visit_strong(v),
+            Expr::Emph(v) => self.visit_emph(v),
+            Expr::Raw(v) => self.visit_raw(v),
+            Expr::Link(v) => self.visit_link(v),
+            Expr::Label(v) => self.visit_label(v),
+            Expr::Ref(v) => self.visit_ref(v),
+            Expr::Heading(v) => self.visit_heading(v),
+            Expr::List(v) => self.visit_list_item(v),
+            Expr::Enum(v) => self.visit_enum_item(v),
+            Expr::Term(v) => self.visit_term_item(v),
+            Expr::Equation(v) => self.visit_equation(v),
+            Expr::Math(v) => self.visit_math(v),
+            Expr::MathIdent(v) => self.visit_math_ident(v),
+            Expr::MathAlignPoint(v) => self.visit_math_align_point(v),
+            Expr::MathDelimited(v) => self.visit_math_delimited(v),
+            Expr::MathAttach(v) => self.visit_math_attach(v),
+            Expr::MathFrac(v) => self.visit_math_frac(v),
+            Expr::MathRoot(v) => self.visit_math_root(v),
+            Expr::Ident(v) => self.visit_ident(v),
+            Expr::None(v) => self.visit_none(v),
+            Expr::Auto(v) => self.visit_auto(v),
+            Expr::Bool(v) => self.visit_bool(v),
+            Expr::Int(v) => self.visit_int(v),
+            Expr::Float(v) => self.visit_float(v),
+            Expr::Numeric(v) => self.visit_numeric(v),
+            Expr::Str(v) => self.visit_str(v),
+            Expr::Code(v) => self.visit_code_block(v),
+            Expr::Content(v) => self.visit_content_block(v),
+            Expr::Parenthesized(v) => self.visit_parenthesized(v),
+            Expr::Array(v) => self.visit_array(v),
+            Expr::Dict(v) => self.visit_dict(v),
+            Expr::Unary(v) => self.visit_unary(v),
+            Expr::Binary(v) => self.visit_binary(v),
+            Expr::FieldAccess(v) => self.visit_field_access(v),
+            Expr::FuncCall(v) => self.visit_func_call(v),
+            Expr::Closure(v) => self.visit_closure(v),
+            Expr::Let(v) => self.visit_let_binding(v),
+            Expr::DestructAssign(v) => self.visit_destruct_assignment(v),
+            Expr::Set(v) => self.visit_set_rule(v),
+            Expr::Show(v) => self.visit_show_rule(v),
+            Expr::Conditional(v) => self.visit_conditional(v),
+            Expr::While(v) => self.visit_while_loop(v),
+            Expr::For(v) => self.visit_for_loop(v),
+            Expr::Import(v) => self.visit_module_import(v),
+            Expr::Include(v) => self.visit_module_include(v),
+            Expr::Break(v) => self.visit_loop_break(v),
+            Expr::Continue(v) => self.visit_loop_continue(v),
+            Expr::Return(v) => self.visit_func_return(v),
+        }
+    }
+
+    /// Visit a [`Text`] node.
+    fn visit_text(&mut self, node: &Text) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Space`] node.
+    fn visit_space(&mut self, node: &Space) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Linebreak`] node.
+    fn visit_linebreak(&mut self, node: &Linebreak) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Parbreak`] node.
+    fn visit_parbreak(&mut self, node: &Parbreak) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit an [`Escape`] node.
+    fn visit_escape(&mut self, node: &Escape) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Shorthand`] node.
+    fn visit_shorthand(&mut self, node: &Shorthand) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`SmartQuote`] node.
+    fn visit_smart_quote(&mut self, node: &SmartQuote) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Strong`] node.
+    fn visit_strong(&mut self, node: &Strong) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit an [`Emph`] node.
+    fn visit_emph(&mut self, node: &Emph) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Raw`] node.
+    fn visit_raw(&mut self, node: &Raw) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Link`] node.
+    fn visit_link(&mut self, node: &Link) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Label`] node.
+    fn visit_label(&mut self, node: &Label) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Ref`] node.
+    fn visit_ref(&mut self, node: &Ref) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Heading`] node.
+    fn visit_heading(&mut self, node: &Heading) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`ListItem`] node.
+    fn visit_list_item(&mut self, node: &ListItem) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit an [`EnumItem`] node.
+    fn visit_enum_item(&mut self, node: &EnumItem) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`TermItem`] node.
+    fn visit_term_item(&mut self, node: &TermItem) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit an [`Equation`] node.
+    fn visit_equation(&mut self, node: &Equation) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Math`] node.
+    fn visit_math(&mut self, node: &Math) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`MathIdent`] node.
+    fn visit_math_ident(&mut self, node: &MathIdent) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`MathAlignPoint`] node.
+    fn visit_math_align_point(&mut self, node: &MathAlignPoint) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`MathDelimited`] node.
+    fn visit_math_delimited(&mut self, node: &MathDelimited) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`MathAttach`] node.
+    fn visit_math_attach(&mut self, node: &MathAttach) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`MathFrac`] node.
+    fn visit_math_frac(&mut self, node: &MathFrac) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`MathRoot`] node.
+    fn visit_math_root(&mut self, node: &MathRoot) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit an [`Ident`] node.
+    fn visit_ident(&mut self, node: &Ident) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`None`] node.
+    fn visit_none(&mut self, node: &None) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit an [`Auto`] node.
+    fn visit_auto(&mut self, node: &Auto) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Bool`] node.
+    fn visit_bool(&mut self, node: &Bool) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit an [`Int`] node.
+    fn visit_int(&mut self, node: &Int) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Float`] node.
+    fn visit_float(&mut self, node: &Float) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Numeric`] node.
+    fn visit_numeric(&mut self, node: &Numeric) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Str`] node.
+    fn visit_str(&mut self, node: &Str) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`CodeBlock`] node.
+    fn visit_code_block(&mut self, node: &CodeBlock) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`ContentBlock`] node.
+    fn visit_content_block(&mut self, node: &ContentBlock) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Parenthesized`] node.
+    fn visit_parenthesized(&mut self, node: &Parenthesized) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit an [`Array`] node.
+    fn visit_array(&mut self, node: &Array) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Dict`] node.
+    fn visit_dict(&mut self, node: &Dict) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit an [`Unary`] node.
+    fn visit_unary(&mut self, node: &Unary) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Binary`] node.
+    fn visit_binary(&mut self, node: &Binary) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`FieldAccess`] node.
+    fn visit_field_access(&mut self, node: &FieldAccess) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`FuncCall`] node.
+    fn visit_func_call(&mut self, node: &FuncCall) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Closure`] node.
+    fn visit_closure(&mut self, node: &Closure) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`LetBinding`] node.
+    fn visit_let_binding(&mut self, node: &LetBinding) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`DestructAssignment`] node.
+    fn visit_destruct_assignment(&mut self, node: &DestructAssignment) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`SetRule`] node.
+    fn visit_set_rule(&mut self, node: &SetRule) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`ShowRule`] node.
+    fn visit_show_rule(&mut self, node: &ShowRule) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`Conditional`] node.
+    fn visit_conditional(&mut self, node: &Conditional) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`WhileLoop`] node.
+    fn visit_while_loop(&mut self, node: &WhileLoop) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`ForLoop`] node.
+    fn visit_for_loop(&mut self, node: &ForLoop) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`ModuleImport`] node.
+    fn visit_module_import(&mut self, node: &ModuleImport) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`ModuleInclude`] node.
+    fn visit_module_include(&mut self, node: &ModuleInclude) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`LoopBreak`] node.
+    fn visit_loop_break(&mut self, node: &LoopBreak) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`LoopContinue`] node.
+    fn visit_loop_continue(&mut self, node: &LoopContinue) {
+        walk(self, node.as_untyped());
+    }
+
+    /// Visit a [`FuncReturn`] node.
+    fn visit_func_return(&mut self, node: &FuncReturn) {
+        walk(self, node.as_untyped());
+    }
+}
+
+/// Visit all children of a node.
+pub fn walk<V: Visitor + ?Sized>(visitor: &mut V, node: &SyntaxNode) {
+    for child in node.children() {
+        visitor.visit_node(child);
+    }
+}
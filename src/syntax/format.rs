@@ -0,0 +1,365 @@
+//! Source code formatting.
+
+use super::{LexMode, Source, SyntaxKind, SyntaxNode};
+
+/// Configures how source code is formatted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FormatConfig {
+    /// How many spaces each level of nested code is indented by.
+    pub indent: usize,
+    /// The line width beyond which argument lists, arrays, and dictionaries
+    /// are broken onto multiple lines.
+    pub max_width: usize,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self { indent: 2, max_width: 80 }
+    }
+}
+
+/// Format a source file.
+///
+/// In code, this normalizes the spacing around operators, commas, and
+/// colons, re-indents lines within delimiters and breaks overlong argument
+/// lists and collections onto one line per item. Whitespace is significant
+/// in markup and math, so there only trailing whitespace is removed.
+/// Comments are always preserved.
+///
+/// Formatting is idempotent: Formatting already formatted code yields the
+/// same code. Sources with syntax errors are returned unchanged.
+pub fn format(source: &Source, config: FormatConfig) -> String {
+    let root = source.root();
+    if root.erroneous() {
+        return source.text().into();
+    }
+
+    let mut formatter = Formatter { config, out: String::new(), stack: vec![] };
+    formatter.node(root, LexMode::Markup);
+    formatter.out
+}
+
+/// Formats syntax nodes into a string.
+struct Formatter {
+    /// The formatting configuration.
+    config: FormatConfig,
+    /// The formatted output.
+    out: String,
+    /// The indentation of the lines with the currently open delimiters.
+    stack: Vec<usize>,
+}
+
+impl Formatter {
+    /// Format a node in the given mode.
+    fn node(&mut self, node: &SyntaxNode, mode: LexMode) {
+        let children = node.children().as_slice();
+        match node.kind() {
+            SyntaxKind::ContentBlock => {
+                self.sequence(node.kind(), children, LexMode::Markup)
+            }
+            SyntaxKind::CodeBlock => self.sequence(node.kind(), children, LexMode::Code),
+            SyntaxKind::Equation => self.sequence(node.kind(), children, LexMode::Math),
+            SyntaxKind::Args | SyntaxKind::Array | SyntaxKind::Dict
+                if mode == LexMode::Code =>
+            {
+                self.collection(node.kind(), children)
+            }
+            SyntaxKind::LineComment => self.out.push_str(node.text().trim_end()),
+            _ if children.is_empty() => self.out.push_str(node.text()),
+            kind => self.sequence(kind, children, mode),
+        }
+    }
+
+    /// Format the children of a node of the given kind.
+    fn sequence(&mut self, parent: SyntaxKind, children: &[SyntaxNode], mode: LexMode) {
+        let mut embedded = false;
+        for (i, child) in children.iter().enumerate() {
+            let kind = child.kind();
+            let prev = i.checked_sub(1).map(|j| children[j].kind());
+            let next = children.get(i + 1).map(SyntaxNode::kind);
+
+            // Code embedded into markup or math starts after a hashtag.
+            let mode = if std::mem::take(&mut embedded) { LexMode::Code } else { mode };
+            if mode != LexMode::Code {
+                embedded = kind == SyntaxKind::Hashtag;
+                match kind {
+                    SyntaxKind::Space | SyntaxKind::Parbreak => self.trim(child.text()),
+                    _ => self.node(child, mode),
+                }
+                continue;
+            }
+
+            if kind == SyntaxKind::Space {
+                self.space(parent, child.text(), prev, next);
+                continue;
+            }
+
+            if prev.map_or(false, |prev| gap(parent, prev, kind)) {
+                self.out.push(' ');
+            }
+
+            if is_closing(kind) {
+                self.stack.pop();
+            }
+
+            if is_opening(kind) {
+                self.stack.push(self.line_indent());
+            }
+
+            self.node(child, mode);
+        }
+    }
+
+    /// Format a parenthesized argument list or collection, breaking it onto
+    /// multiple lines if it is too long.
+    fn collection(&mut self, kind: SyntaxKind, children: &[SyntaxNode]) {
+        let split = children
+            .iter()
+            .position(|child| child.kind() == SyntaxKind::RightParen)
+            .map_or(0, |i| i + 1);
+
+        let (parens, rest) = children.split_at(split);
+        let start = self.out.len();
+        self.sequence(kind, parens, LexMode::Code);
+
+        if !self.out[start..].contains('\n')
+            && self.column() > self.config.max_width
+            && breakable(parens)
+        {
+            self.out.truncate(start);
+            self.broken(parens);
+        }
+
+        self.sequence(kind, rest, LexMode::Code);
+    }
+
+    /// Format the parenthesized part of an argument list or collection with
+    /// one item per line.
+    fn broken(&mut self, parens: &[SyntaxNode]) {
+        let base = self.line_indent();
+        self.stack.push(base);
+        self.out.push('(');
+        for item in parens.iter().filter(|child| is_item(child.kind())) {
+            self.newline(base + self.config.indent);
+            self.node(item, LexMode::Code);
+            self.out.push(',');
+        }
+        self.newline(base);
+        self.out.push(')');
+        self.stack.pop();
+    }
+
+    /// Format whitespace in code.
+    fn space(
+        &mut self,
+        parent: SyntaxKind,
+        text: &str,
+        prev: Option<SyntaxKind>,
+        next: Option<SyntaxKind>,
+    ) {
+        let opening = prev.map_or(false, is_opening);
+        let closing = next.map_or(false, is_closing);
+        let newlines = text.chars().filter(|&c| c == '\n').count();
+        if newlines > 0 {
+            // Keep at most one empty line, but none directly after an
+            // opening or before a closing delimiter.
+            let max = if opening || closing { 1 } else { 2 };
+            for _ in 1..newlines.min(max) {
+                self.out.push('\n');
+            }
+
+            let base = self.stack.last().copied().unwrap_or(0);
+            let indent = if closing { base } else { base + self.config.indent };
+            self.newline(indent);
+        } else if !elide(parent, opening, closing, next) {
+            self.out.push(' ');
+        }
+    }
+
+    /// Push whitespace in markup or math, removing trailing whitespace from
+    /// all lines it ends.
+    fn trim(&mut self, text: &str) {
+        let mut lines = text.split('\n');
+        let last = lines.next_back().unwrap_or_default();
+        for line in lines {
+            self.out
+                .push_str(line.trim_end_matches(|c: char| c == ' ' || c == '\t'));
+            self.out.push('\n');
+        }
+        self.out.push_str(last);
+    }
+
+    /// Start a new line with the given indentation.
+    fn newline(&mut self, indent: usize) {
+        self.out.push('\n');
+        self.out.push_str(&" ".repeat(indent));
+    }
+
+    /// The text on the current line.
+    fn line(&self) -> &str {
+        let start = self.out.rfind('\n').map_or(0, |i| i + 1);
+        &self.out[start..]
+    }
+
+    /// The width of the current line.
+    fn column(&self) -> usize {
+        self.line().chars().count()
+    }
+
+    /// The indentation of the current line.
+    fn line_indent(&self) -> usize {
+        self.line().chars().take_while(|&c| c == ' ').count()
+    }
+}
+
+/// Whether a node of the given kind contains comma-separated items.
+fn is_list(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::Args
+            | SyntaxKind::Array
+            | SyntaxKind::Dict
+            | SyntaxKind::Params
+            | SyntaxKind::Parenthesized
+            | SyntaxKind::Destructuring
+            | SyntaxKind::ImportItems
+    )
+}
+
+/// Whether this is an opening delimiter in code.
+fn is_opening(kind: SyntaxKind) -> bool {
+    matches!(kind, SyntaxKind::LeftParen | SyntaxKind::LeftBrace)
+}
+
+/// Whether this is a closing delimiter in code.
+fn is_closing(kind: SyntaxKind) -> bool {
+    matches!(kind, SyntaxKind::RightParen | SyntaxKind::RightBrace)
+}
+
+/// Whether this is an item of an argument list or collection.
+fn is_item(kind: SyntaxKind) -> bool {
+    !kind.is_trivia()
+        && !matches!(
+            kind,
+            SyntaxKind::LeftParen | SyntaxKind::RightParen | SyntaxKind::Comma
+        )
+}
+
+/// Whether the parenthesized part of an argument list or collection can be
+/// broken onto multiple lines.
+///
+/// Comments are kept where they are since they may refer to a specific item.
+fn breakable(parens: &[SyntaxNode]) -> bool {
+    parens.first().map(SyntaxNode::kind) == Some(SyntaxKind::LeftParen)
+        && parens.iter().any(|child| is_item(child.kind()))
+        && !parens.iter().any(|child| {
+            matches!(child.kind(), SyntaxKind::LineComment | SyntaxKind::BlockComment)
+        })
+}
+
+/// Whether a space must be inserted between two adjacent children of a node.
+fn gap(parent: SyntaxKind, prev: SyntaxKind, next: SyntaxKind) -> bool {
+    if prev.is_trivia() {
+        return false;
+    }
+
+    match parent {
+        SyntaxKind::Binary => true,
+        SyntaxKind::LetBinding | SyntaxKind::Closure | SyntaxKind::DestructAssignment => {
+            let spaced = |kind| matches!(kind, SyntaxKind::Eq | SyntaxKind::Arrow);
+            spaced(prev) || spaced(next)
+        }
+        SyntaxKind::Named | SyntaxKind::Keyed => prev == SyntaxKind::Colon,
+        _ => is_list(parent) && prev == SyntaxKind::Comma && !is_closing(next),
+    }
+}
+
+/// Whether a space without a line break can be removed.
+fn elide(
+    parent: SyntaxKind,
+    opening: bool,
+    closing: bool,
+    next: Option<SyntaxKind>,
+) -> bool {
+    match parent {
+        SyntaxKind::Named | SyntaxKind::Keyed => next == Some(SyntaxKind::Colon),
+        _ => is_list(parent) && (opening || closing || next == Some(SyntaxKind::Comma)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn test(text: &str, expected: &str) {
+        test_with(text, expected, FormatConfig::default());
+    }
+
+    #[track_caller]
+    fn test_with(text: &str, expected: &str, config: FormatConfig) {
+        let found = format(&Source::detached(text), config);
+        assert_eq!(found, expected);
+        let again = format(&Source::detached(found.as_str()), config);
+        assert_eq!(again, found, "formatting should be idempotent");
+    }
+
+    #[test]
+    fn test_format_spacing() {
+        test("#let x=1+  2", "#let x = 1 + 2");
+        test("#f( a ,b,c:1 )", "#f(a, b, c: 1)");
+        test("#(a,)", "#(a,)");
+        test("#(: )", "#(:)");
+        test("#let f(x,y)=x not  in y", "#let f(x, y) = x not in y");
+        test("#let g = (x)=>x*2", "#let g = (x) => x * 2");
+        test("#import \"a.typ\": a,b", "#import \"a.typ\": a, b");
+    }
+
+    #[test]
+    fn test_format_markup() {
+        test("Hello  *world*  \nhow are  you? ", "Hello  *world*\nhow are  you? ");
+        test("$ a+b  $", "$ a+b  $");
+        test("#[ a , b ]", "#[ a , b ]");
+        test("// Comment   \nText", "// Comment\nText");
+    }
+
+    #[test]
+    fn test_format_indent() {
+        test("#{\nlet x = 1\n\n\n\n      x\n    }", "#{\n  let x = 1\n\n  x\n}");
+        test(
+            "#let dict = (\n  a: 1, // First\n b: (\nc: 2,\n       ),\n)",
+            "#let dict = (\n  a: 1, // First\n  b: (\n    c: 2,\n  ),\n)",
+        );
+        test_with(
+            "#if x {\n\ty\n}",
+            "#if x {\n    y\n}",
+            FormatConfig { indent: 4, ..Default::default() },
+        );
+    }
+
+    #[test]
+    fn test_format_break() {
+        let config = FormatConfig { max_width: 20, ..Default::default() };
+        test_with("#f(a, b)", "#f(a, b)", config);
+        test_with(
+            "#f(alpha, beta, gamma)[Body]",
+            "#f(\n  alpha,\n  beta,\n  gamma,\n)[Body]",
+            config,
+        );
+        test_with(
+            "#{\n  let x = (1, 2, 3, 4, 5)\n}",
+            "#{\n  let x = (\n    1,\n    2,\n    3,\n    4,\n    5,\n  )\n}",
+            config,
+        );
+        test_with(
+            "#f(alpha, /* c */ beta, gamma)",
+            "#f(alpha, /* c */ beta, gamma)",
+            config,
+        );
+    }
+
+    #[test]
+    fn test_format_erroneous() {
+        test("#f(a ,", "#f(a ,");
+    }
+}
@@ -1,4 +1,6 @@
-use crate::syntax::{ast, LinkedNode, SyntaxKind, SyntaxNode};
+use std::ops::Range;
+
+use super::{ast, LinkedNode, Source, SyntaxKind, SyntaxNode};
 
 /// A syntax highlighting tag.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -48,6 +50,64 @@ pub enum Tag {
 }
 
 impl Tag {
+    /// All highlighting tags, in a stable order.
+    ///
+    /// Useful as the legend of semantic tokens, which refer to token types by
+    /// index.
+    pub const LIST: &'static [Tag] = &[
+        Self::Comment,
+        Self::Punctuation,
+        Self::Escape,
+        Self::Strong,
+        Self::Emph,
+        Self::Link,
+        Self::Raw,
+        Self::Label,
+        Self::Ref,
+        Self::Heading,
+        Self::ListMarker,
+        Self::ListTerm,
+        Self::MathDelimiter,
+        Self::MathOperator,
+        Self::Keyword,
+        Self::Operator,
+        Self::Number,
+        Self::String,
+        Self::Function,
+        Self::Interpolated,
+        Self::Error,
+    ];
+
+    /// The recommended LSP semantic token type for the highlighting tag.
+    ///
+    /// This is one of the predefined token types where a fitting one exists
+    /// and a custom one otherwise.
+    pub fn semantic_token_type(self) -> &'static str {
+        match self {
+            Self::Comment => "comment",
+            Self::Punctuation => "punctuation",
+            Self::Escape => "escape",
+            Self::Strong => "strong",
+            Self::Emph => "emph",
+            Self::Link => "link",
+            Self::Raw => "raw",
+            Self::Label => "label",
+            Self::Ref => "ref",
+            Self::Heading => "heading",
+            Self::ListMarker => "marker",
+            Self::ListTerm => "term",
+            Self::MathDelimiter => "delimiter",
+            Self::MathOperator => "operator",
+            Self::Keyword => "keyword",
+            Self::Operator => "operator",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Function => "function",
+            Self::Interpolated => "variable",
+            Self::Error => "error",
+        }
+    }
+
     /// Return the recommended TextMate grammar scope for the given highlighting
     /// tag.
     pub fn tm_scope(&self) -> &'static str {
@@ -104,10 +164,50 @@ impl Tag {
     }
 }
 
+/// Highlight a whole source file.
+///
+/// Returns the tagged byte ranges in order, merging adjacent ranges with the
+/// same tag. Leaves without a tag of their own take the tag of their innermost
+/// tagged ancestor, for example the text of a heading. Embedded code and math
+/// start afresh and don't inherit the tag of the markup around them.
+pub fn highlight(source: &Source) -> Vec<(Range<usize>, Tag)> {
+    let mut tags = vec![];
+    highlight_leaves(&mut tags, &LinkedNode::new(source.root()), None);
+    tags
+}
+
+/// Highlight the leaves of a linked syntax node.
+fn highlight_leaves(
+    tags: &mut Vec<(Range<usize>, Tag)>,
+    node: &LinkedNode,
+    outer: Option<Tag>,
+) {
+    let embedded = matches!(node.kind(), SyntaxKind::CodeBlock | SyntaxKind::Equation)
+        || node.prev_sibling_kind() == Some(SyntaxKind::Hashtag);
+    let outer = if embedded { None } else { outer };
+    let tag = highlight_node(node).or(outer);
+
+    if node.text().is_empty() {
+        for child in node.children() {
+            highlight_leaves(tags, &child, tag);
+        }
+        return;
+    }
+
+    let Some(tag) = tag else { return };
+    let range = node.range();
+    match tags.last_mut() {
+        Some((last, prev)) if last.end == range.start && *prev == tag => {
+            last.end = range.end;
+        }
+        _ => tags.push((range, tag)),
+    }
+}
+
 /// Determine the highlight tag of a linked syntax node.
 ///
 /// Returns `None` if the node should not be highlighted.
-pub fn highlight(node: &LinkedNode) -> Option<Tag> {
+pub fn highlight_node(node: &LinkedNode) -> Option<Tag> {
     match node.kind() {
         SyntaxKind::Markup
             if node.parent_kind() == Some(SyntaxKind::TermItem)
@@ -318,7 +418,7 @@ fn highlight_hashtag(node: &LinkedNode) -> Option<Tag> {
     if !expr.hashtag() {
         return None;
     }
-    highlight(&next.leftmost_leaf()?)
+    highlight_node(&next.leftmost_leaf()?)
 }
 
 /// Whether the node is one of the two identifier nodes.
@@ -340,7 +440,7 @@ pub fn highlight_html(root: &SyntaxNode) -> String {
 /// Highlight one source node, emitting HTML.
 fn highlight_html_impl(html: &mut String, node: &LinkedNode) {
     let mut span = false;
-    if let Some(tag) = highlight(node) {
+    if let Some(tag) = highlight_node(node) {
         if tag != Tag::Error {
             span = true;
             html.push_str("<span class=\"");
@@ -374,10 +474,7 @@ fn highlight_html_impl(html: &mut String, node: &LinkedNode) {
 
 #[cfg(test)]
 mod tests {
-    use std::ops::Range;
-
     use super::*;
-    use crate::syntax::Source;
 
     #[test]
     fn test_highlighting() {
@@ -392,7 +489,7 @@ mod tests {
         }
 
         fn highlight_tree(tags: &mut Vec<(Range<usize>, Tag)>, node: &LinkedNode) {
-            if let Some(tag) = highlight(node) {
+            if let Some(tag) = highlight_node(node) {
                 tags.push((node.range(), tag));
             }
 
@@ -427,4 +524,37 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_highlighting_source() {
+        use Tag::*;
+
+        #[track_caller]
+        fn test(text: &str, goal: &[(Range<usize>, Tag)]) {
+            assert_eq!(highlight(&Source::detached(text)), goal);
+        }
+
+        test(
+            "= A #f(x)",
+            &[
+                (0..4, Heading),
+                (4..6, Function),
+                (6..7, Punctuation),
+                (8..9, Punctuation),
+            ],
+        );
+
+        test(
+            "*a* $pi #[_b_]$",
+            &[
+                (0..3, Strong),
+                (4..5, MathDelimiter),
+                (5..7, Interpolated),
+                (8..10, Punctuation),
+                (10..13, Emph),
+                (13..14, Punctuation),
+                (14..15, MathDelimiter),
+            ],
+        );
+    }
 }
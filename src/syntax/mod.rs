@@ -1,7 +1,9 @@
 //! Syntax definition, parsing, and highlighting.
 
 pub mod ast;
+pub mod format;
 
+mod highlight;
 mod kind;
 mod lexer;
 mod node;
@@ -10,6 +12,7 @@ mod reparser;
 mod source;
 mod span;
 
+pub use self::highlight::*;
 pub use self::kind::*;
 pub use self::lexer::*;
 pub use self::node::*;
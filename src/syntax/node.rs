@@ -7,7 +7,7 @@ use ecow::EcoString;
 
 use super::ast::AstNode;
 use super::{SourceId, Span, SyntaxKind};
-use crate::diag::SourceError;
+use crate::diag::SourceDiagnostic;
 
 /// A node in the untyped syntax tree.
 #[derive(Clone, Eq, PartialEq, Hash)]
@@ -109,6 +109,28 @@ impl SyntaxNode {
         }
     }
 
+    /// All leaf descendants of the node, in order. If the node itself is a
+    /// leaf, yields just the node.
+    pub fn leaves(&self) -> impl Iterator<Item = &SyntaxNode> + '_ {
+        let mut stack = vec![std::slice::from_ref(self).iter()];
+        std::iter::from_fn(move || loop {
+            match stack.last_mut()?.next() {
+                Some(node) if node.children().len() == 0 => return Some(node),
+                Some(node) => stack.push(node.children()),
+                None => {
+                    stack.pop();
+                }
+            }
+        })
+    }
+
+    /// Get the leaf at the specified byte offset.
+    ///
+    /// An offset exactly between two leaves resolves to the left one.
+    pub fn leaf_at(&self, cursor: usize) -> Option<&SyntaxNode> {
+        LinkedNode::new(self).leaf_at(cursor).map(|node| node.get())
+    }
+
     /// Whether the node can be cast to the given AST node.
     pub fn is<T: AstNode>(&self) -> bool {
         self.cast::<T>().is_some()
@@ -139,13 +161,14 @@ impl SyntaxNode {
     }
 
     /// The error messages for this node and its descendants.
-    pub fn errors(&self) -> Vec<SourceError> {
+    pub fn errors(&self) -> Vec<SourceDiagnostic> {
         if !self.erroneous() {
             return vec![];
         }
 
         if let Repr::Error(error) = &self.0 {
-            vec![SourceError::new(error.span, error.message.clone()).with_pos(error.pos)]
+            vec![SourceDiagnostic::error(error.span, error.message.clone())
+                .with_pos(error.pos)]
         } else {
             self.children()
                 .filter(|node| node.erroneous())
@@ -891,6 +914,16 @@ mod tests {
         assert_eq!(prev.text(), "set");
     }
 
+    #[test]
+    fn test_leaves() {
+        let source = Source::detached("#let x = 10");
+        let leaves: Vec<_> =
+            source.root().leaves().map(|node| node.text().as_str()).collect();
+        assert_eq!(leaves, ["#", "let", " ", "x", " ", "=", " ", "10"]);
+        assert_eq!(source.root().leaf_at(6).unwrap().text(), "x");
+        assert_eq!(source.root().leaf_at(12), None);
+    }
+
     #[test]
     fn test_linked_node_non_trivia_leaf() {
         let source = Source::detached("#set fun(12pt, red)");
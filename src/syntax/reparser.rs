@@ -250,7 +250,12 @@ mod tests {
     fn test(prev: &str, range: Range<usize>, with: &str, incremental: bool) {
         let mut source = Source::detached(prev);
         let prev = source.root().clone();
+        let start = range.start;
         let range = source.edit(range, with);
+        assert!(
+            range.start <= start && start + with.len() <= range.end,
+            "reparsed range should cover the edit"
+        );
         let mut found = source.root().clone();
         let mut expected = parse(source.text());
         found.synthesize(Span::detached());
@@ -273,6 +278,18 @@ mod tests {
         }
     }
 
+    #[track_caller]
+    fn test_range(prev: &str, range: Range<usize>, with: &str, reparsed: Range<usize>) {
+        let mut source = Source::detached(prev);
+        assert_eq!(source.edit(range, with), reparsed);
+    }
+
+    #[test]
+    fn test_reparse_range() {
+        test_range("a #{x} b", 4..5, "y", 3..6);
+        test_range("abc~def~gh~", 5..6, "+", 0..8);
+    }
+
     #[test]
     fn test_reparse_markup() {
         test("abc~def~gh~", 5..6, "+", true);
@@ -244,6 +244,28 @@ impl Source {
         }
         Some(range.start + (line.len() - chars.as_str().len()))
     }
+
+    /// Return the byte index of the given (line, column) pair, clamped to the
+    /// nearest valid position.
+    ///
+    /// A line past the end of the file is clamped to the last line. A column
+    /// past the end of a line is clamped to the end of the line's content,
+    /// that is, right before its line break. On an empty line, every column
+    /// thus maps to the start of the line.
+    pub fn line_column_to_byte_clamped(
+        &self,
+        line_idx: usize,
+        column_idx: usize,
+    ) -> usize {
+        let line_idx = line_idx.min(self.len_lines() - 1);
+        let range = self.line_to_range(line_idx).unwrap();
+        let content = self.text[range.clone()].trim_end_matches(is_newline);
+        let offset = content
+            .char_indices()
+            .nth(column_idx)
+            .map_or(content.len(), |(i, _)| i);
+        range.start + offset
+    }
 }
 
 impl Debug for Source {
@@ -412,6 +434,24 @@ mod tests {
         roundtrip(&source, 21);
     }
 
+    #[test]
+    fn test_source_file_clamped() {
+        let source = Source::detached(TEST);
+        assert_eq!(source.line_column_to_byte_clamped(0, 0), 0);
+        assert_eq!(source.line_column_to_byte_clamped(0, 1), 2);
+        assert_eq!(source.line_column_to_byte_clamped(0, 100), 6);
+        assert_eq!(source.line_column_to_byte_clamped(1, 2), 12);
+        assert_eq!(source.line_column_to_byte_clamped(2, 7), 17);
+        assert_eq!(source.line_column_to_byte_clamped(3, 5), 21);
+        assert_eq!(source.line_column_to_byte_clamped(10, 0), 18);
+        assert_eq!(source.line_column_to_byte_clamped(10, 10), 21);
+
+        let source = Source::detached("a\n\r\nb");
+        assert_eq!(source.line_column_to_byte_clamped(1, 0), 2);
+        assert_eq!(source.line_column_to_byte_clamped(1, 3), 2);
+        assert_eq!(source.line_column_to_byte_clamped(0, 3), 1);
+    }
+
     #[test]
     fn test_source_file_edit() {
         // This tests only the non-parser parts. The reparsing itself is
@@ -0,0 +1,2 @@
+// A package for testing package imports.
+#let add(x, y) = x + y
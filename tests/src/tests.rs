@@ -20,15 +20,15 @@ use tiny_skia as sk;
 use unscanny::Scanner;
 use walkdir::WalkDir;
 
-use typst::diag::{bail, FileError, FileResult};
+use typst::diag::{bail, FileError, FileResult, PackageError, Severity};
 use typst::doc::{Document, Frame, FrameItem, Meta};
-use typst::eval::{func, Datetime, Library, Value};
+use typst::eval::{func, Datetime, Library, PackageSpec, Tracer, Value};
 use typst::font::{Font, FontBook};
-use typst::geom::{Abs, Color, RgbaColor, Sides, Smart};
+use typst::geom::{Abs, Color, RgbaColor, Smart};
 use typst::syntax::{Source, SourceId, Span, SyntaxNode};
 use typst::util::{Buffer, PathExt};
 use typst::World;
-use typst_library::layout::PageElem;
+use typst_library::layout::{Margin, PageElem};
 use typst_library::text::{TextElem, TextSize};
 
 const TYP_DIR: &str = "typ";
@@ -37,6 +37,7 @@ const PNG_DIR: &str = "png";
 const PDF_DIR: &str = "pdf";
 const FONT_DIR: &str = "../assets/fonts";
 const FILE_DIR: &str = "../assets/files";
+const PACKAGE_DIR: &str = "packages";
 
 #[derive(Debug, Clone, Parser)]
 #[clap(name = "typst-test", author)]
@@ -179,7 +180,7 @@ fn library() -> Library {
     lib.styles
         .set(PageElem::set_width(Smart::Custom(Abs::pt(120.0).into())));
     lib.styles.set(PageElem::set_height(Smart::Auto));
-    lib.styles.set(PageElem::set_margin(Sides::splat(Some(Smart::Custom(
+    lib.styles.set(PageElem::set_margin(Margin::splat(Some(Smart::Custom(
         Abs::pt(10.0).into(),
     )))));
     lib.styles.set(TextElem::set_size(TextSize(Abs::pt(10.0).into())));
@@ -297,6 +298,17 @@ impl World for TestWorld {
             .clone()
     }
 
+    fn package(&self, spec: &PackageSpec) -> FileResult<PathBuf> {
+        let dir = Path::new(PACKAGE_DIR)
+            .join(spec.namespace.as_str())
+            .join(format!("{}-{}", spec.name, spec.version));
+        if dir.is_dir() {
+            Ok(dir)
+        } else {
+            Err(PackageError::NotFound(spec.clone()).into())
+        }
+    }
+
     fn today(&self, _: Option<i64>) -> Option<Datetime> {
         Some(Datetime::from_ymd(1970, 1, 1).unwrap())
     }
@@ -519,7 +531,7 @@ fn test_part(
         writeln!(output, "Syntax Tree:\n{:#?}\n", source.root()).unwrap();
     }
 
-    let (local_compare_ref, mut ref_errors) = parse_metadata(source);
+    let (local_compare_ref, mut ref_diagnostics) = parse_metadata(source);
     let compare_ref = local_compare_ref.unwrap_or(compare_ref);
 
     ok &= test_spans(output, source.root());
@@ -534,9 +546,14 @@ fn test_part(
         writeln!(output, "Model:\n{:#?}\n", module.content()).unwrap();
     }
 
-    let (mut frames, errors) = match typst::compile(world) {
-        Ok(document) => (document.pages, vec![]),
-        Err(errors) => (vec![], *errors),
+    let mut tracer = Tracer::default();
+    let (mut frames, diagnostics) = match typst::compile(world, &mut tracer) {
+        Ok(document) => (document.pages, tracer.warnings().to_vec()),
+        Err(errors) => {
+            let mut diagnostics = tracer.warnings().to_vec();
+            diagnostics.extend(*errors);
+            (vec![], diagnostics)
+        }
     };
 
     // Don't retain frames if we don't wanna compare with reference images.
@@ -544,33 +561,45 @@ fn test_part(
         frames.clear();
     }
 
-    // Map errors to range and message format, discard traces and errors from
-    // other files.
-    let mut errors: Vec<_> = errors
+    // Map diagnostics to severity, range and message format, discard traces
+    // and diagnostics from other files. Warnings without a span concern the
+    // whole document and are kept without a range.
+    let mut diagnostics: Vec<_> = diagnostics
         .into_iter()
-        .filter(|error| error.span.source() == id)
-        .map(|error| (error.range(world), error.message.replace('\\', "/")))
+        .filter(|diagnostic| {
+            diagnostic.span.source() == id
+                || (diagnostic.span.is_detached()
+                    && diagnostic.severity == Severity::Warning)
+        })
+        .map(|diagnostic| {
+            (
+                diagnostic.severity,
+                (!diagnostic.span.is_detached()).then(|| diagnostic.range(world)),
+                diagnostic.message.replace('\\', "/"),
+            )
+        })
         .collect();
 
-    errors.sort_by_key(|error| error.0.start);
-    ref_errors.sort_by_key(|error| error.0.start);
+    diagnostics.sort_by_key(|diagnostic| diagnostic.1.as_ref().map(|range| range.start));
+    ref_diagnostics
+        .sort_by_key(|diagnostic| diagnostic.1.as_ref().map(|range| range.start));
 
-    if errors != ref_errors {
-        writeln!(output, "  Subtest {i} does not match expected errors.").unwrap();
+    if diagnostics != ref_diagnostics {
+        writeln!(output, "  Subtest {i} does not match expected diagnostics.").unwrap();
         ok = false;
 
         let source = world.source(id);
-        for error in errors.iter() {
-            if !ref_errors.contains(error) {
+        for diagnostic in diagnostics.iter() {
+            if !ref_diagnostics.contains(diagnostic) {
                 write!(output, "    Not annotated | ").unwrap();
-                print_error(output, source, line, error);
+                print_diagnostic(output, source, line, diagnostic);
             }
         }
 
-        for error in ref_errors.iter() {
-            if !errors.contains(error) {
+        for diagnostic in ref_diagnostics.iter() {
+            if !diagnostics.contains(diagnostic) {
                 write!(output, "    Not emitted   | ").unwrap();
-                print_error(output, source, line, error);
+                print_diagnostic(output, source, line, diagnostic);
             }
         }
     }
@@ -578,9 +607,11 @@ fn test_part(
     (ok, compare_ref, frames)
 }
 
-fn parse_metadata(source: &Source) -> (Option<bool>, Vec<(Range<usize>, String)>) {
+fn parse_metadata(
+    source: &Source,
+) -> (Option<bool>, Vec<(Severity, Option<Range<usize>>, String)>) {
     let mut compare_ref = None;
-    let mut errors = vec![];
+    let mut diagnostics = vec![];
 
     let lines: Vec<_> = source.text().lines().map(str::trim).collect();
     for (i, line) in lines.iter().enumerate() {
@@ -607,29 +638,47 @@ fn parse_metadata(source: &Source) -> (Option<bool>, Vec<(Range<usize>, String)>
             source.line_column_to_byte(line, column).unwrap()
         };
 
-        let Some(rest) = line.strip_prefix("// Error: ") else { continue; };
+        let (severity, rest) = if let Some(rest) = line.strip_prefix("// Error: ") {
+            (Severity::Error, rest)
+        } else if let Some(rest) = line.strip_prefix("// Warning: ") {
+            (Severity::Warning, rest)
+        } else {
+            continue;
+        };
+
+        // Diagnostics without a span are annotated without a range.
         let mut s = Scanner::new(rest);
-        let start = pos(&mut s);
-        let end = if s.eat_if('-') { pos(&mut s) } else { start };
-        let range = start..end;
+        let range = s.at(char::is_numeric).then(|| {
+            let start = pos(&mut s);
+            let end = if s.eat_if('-') { pos(&mut s) } else { start };
+            start..end
+        });
 
-        errors.push((range, s.after().trim().to_string()));
+        diagnostics.push((severity, range, s.after().trim().to_string()));
     }
 
-    (compare_ref, errors)
+    (compare_ref, diagnostics)
 }
 
-fn print_error(
+fn print_diagnostic(
     output: &mut String,
     source: &Source,
     line: usize,
-    (range, message): &(Range<usize>, String),
+    (severity, range, message): &(Severity, Option<Range<usize>>, String),
 ) {
+    let kind = match severity {
+        Severity::Error => "Error",
+        Severity::Warning => "Warning",
+    };
+    let Some(range) = range else {
+        writeln!(output, "{kind}: {message}").unwrap();
+        return;
+    };
     let start_line = 1 + line + source.byte_to_line(range.start).unwrap();
     let start_col = 1 + source.byte_to_column(range.start).unwrap();
     let end_line = 1 + line + source.byte_to_line(range.end).unwrap();
     let end_col = 1 + source.byte_to_column(range.end).unwrap();
-    writeln!(output, "Error: {start_line}:{start_col}-{end_line}:{end_col}: {message}")
+    writeln!(output, "{kind}: {start_line}:{start_col}-{end_line}:{end_col}: {message}")
         .unwrap();
 }
 
@@ -741,23 +790,14 @@ fn test_reparse(
     }
 
     let source = Source::detached(text);
-    let leafs = leafs(source.root());
-    let start = source.range(leafs[pick(0..leafs.len())].span()).start;
+    let leaves: Vec<_> = source.root().leaves().collect();
+    let start = source.range(leaves[pick(0..leaves.len())].span()).start;
     let supplement = supplements[pick(0..supplements.len())];
     ok &= apply(start..start, supplement);
 
     ok
 }
 
-/// Returns all leaf descendants of a node (may include itself).
-fn leafs(node: &SyntaxNode) -> Vec<SyntaxNode> {
-    if node.children().len() == 0 {
-        vec![node.clone()]
-    } else {
-        node.children().flat_map(leafs).collect()
-    }
-}
-
 /// Ensure that all spans are properly ordered (and therefore unique).
 #[track_caller]
 fn test_spans(output: &mut String, root: &SyntaxNode) -> bool {
@@ -791,39 +831,25 @@ fn test_spans_impl(output: &mut String, node: &SyntaxNode, within: Range<u64>) -
 /// Draw all frames into one image with padding in between.
 fn render(frames: &[Frame]) -> sk::Pixmap {
     let pixel_per_pt = 2.0;
-    let pixmaps: Vec<_> = frames
-        .iter()
-        .map(|frame| {
-            let limit = Abs::cm(100.0);
-            if frame.width() > limit || frame.height() > limit {
-                panic!("overlarge frame: {:?}", frame.size());
-            }
-            typst::export::render(frame, pixel_per_pt, Color::WHITE)
-        })
-        .collect();
+    let padding = Abs::pt(5.0);
 
-    let pad = (5.0 * pixel_per_pt).round() as u32;
-    let pxw = 2 * pad + pixmaps.iter().map(sk::Pixmap::width).max().unwrap_or_default();
-    let pxh = pad + pixmaps.iter().map(|pixmap| pixmap.height() + pad).sum::<u32>();
-
-    let mut canvas = sk::Pixmap::new(pxw, pxh).unwrap();
-    canvas.fill(sk::Color::BLACK);
-
-    let [x, mut y] = [pad; 2];
-    for (frame, mut pixmap) in frames.iter().zip(pixmaps) {
-        let ts = sk::Transform::from_scale(pixel_per_pt, pixel_per_pt);
-        render_links(&mut pixmap, ts, frame);
-
-        canvas.draw_pixmap(
-            x as i32,
-            y as i32,
-            pixmap.as_ref(),
-            &sk::PixmapPaint::default(),
-            sk::Transform::identity(),
-            None,
-        );
+    for frame in frames {
+        let limit = Abs::cm(100.0);
+        if frame.width() > limit || frame.height() > limit {
+            panic!("overlarge frame: {:?}", frame.size());
+        }
+    }
+
+    let mut canvas =
+        typst::export::render_merged(frames, pixel_per_pt, Color::BLACK, padding);
 
-        y += pixmap.height() + pad;
+    let pad = (padding.to_pt() as f32 * pixel_per_pt).round();
+    let mut y = pad;
+    for frame in frames {
+        let ts =
+            sk::Transform::from_translate(pad, y).pre_scale(pixel_per_pt, pixel_per_pt);
+        render_links(&mut canvas, ts, frame);
+        y += (pixel_per_pt * frame.height().to_pt() as f32).round().max(1.0) + pad;
     }
 
     canvas
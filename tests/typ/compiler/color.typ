@@ -24,3 +24,18 @@
 #test(luma(20%).lighten(50%), luma(60%))
 #test(luma(80%).darken(20%), luma(63.9%))
 #test(luma(80%).negate(), luma(20%))
+
+---
+// Test color mixing and components.
+// Ref: false
+#test(black.mix(white, space: "srgb"), rgb(128, 128, 128))
+#test(black.mix(white), rgb(188, 188, 188))
+#test(red.mix((blue, 0%)), red.mix())
+#test(black.mix((white, 100%)), rgb(255, 255, 255))
+#test(rgb(1, 2, 3, 4).components(), (1, 2, 3, 4))
+#test(luma(20).components(), (20,))
+#test(cmyk(0%, 0%, 0%, 100%).components(), (0%, 0%, 0%, 100%))
+
+---
+// Error: 2-36 weights must not add up to more than 100%
+#red.mix((blue, 60%), (green, 60%))
@@ -62,3 +62,8 @@
 ---
 // Error: 9-13 cannot access fields on type boolean
 #{false.true}
+
+---
+// Test the fields method on content.
+#test(emph[A].fields(), (body: [A]))
+#test(heading(level: 2)[B].fields().keys().sorted(), ("body", "level"))
@@ -0,0 +1,15 @@
+// Test imports of packages.
+// Ref: false
+
+---
+#import "@test/adder:0.1.0": add
+#test(add(2, 3), 5)
+
+---
+// A package is bound under its name.
+#import "@test/adder:0.1.0"
+#test(adder.add(1, 2), 3)
+
+---
+// Error: 9-30 package not found (searched for @test/missing:0.1.0)
+#import "@test/missing:0.1.0"
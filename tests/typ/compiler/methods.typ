@@ -44,3 +44,20 @@
 ---
 // Error: 2-5 cannot mutate a constant: box
 #box.push(1)
+
+---
+// Test length conversions.
+#test((1in).pt(), 72.0)
+#test((72pt).inches(), 1.0)
+#test((2pt + 1em - 1em).pt(), 2.0)
+#test((3cm).mm() > 29.99, true)
+#test(4em / 2em, 2.0)
+
+---
+// Error: 2:2-2:8 cannot convert 2pt + 1em to an absolute length without a font size
+#let x = 1em + 2pt
+#x.pt()
+
+---
+// Error: 2-16 cannot convert 1em to an absolute length without a font size
+#(1em).inches()
@@ -57,3 +57,15 @@ World
 #show "GRAPH": image("/graph.png")
 
 The GRAPH has nodes.
+
+---
+// Test that a rule is not applied to its own output.
+// Ref: false
+#let hits = counter("hits")
+#show regex("a+"): it => [(#it)]
+#show "ha": it => hits.step() + [haha]
+aa ha
+#locate(loc => {
+  let n = hits.final(loc).first()
+  if n > 0 { test(n, 1) }
+})
@@ -128,6 +128,13 @@
 #test("123".replace(regex("\d$"), "_"), "12_")
 #test("123".replace(regex("\d{1,2}$"), "__"), "1__")
 
+---
+// Test group references in `replace` with regex patterns.
+#test("hello world".replace(regex("(\w+) (\w+)"), "$2 $1"), "world hello")
+#test("2023-06".replace(regex("(?<y>\d+)-(?<m>\d+)"), "${m}/${y}"), "06/2023")
+#test("a1".replace(regex("\d"), "$$"), "a$")
+#test("a$1".replace("$1", "b"), "ab")
+
 ---
 // Test the `replace` method with `Func` replacements.
 
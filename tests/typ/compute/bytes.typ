@@ -0,0 +1,47 @@
+// Test the bytes type.
+// Ref: false
+
+---
+#let data = bytes("Typst")
+#test(type(data), "bytes")
+#test(repr(data), "bytes(5)")
+#test(data.len(), 5)
+#test(data.at(0), 84)
+#test(data.at(-1), 116)
+#test(data.slice(1, 3), bytes("yp"))
+#test(str(data.slice(-2)), "st")
+#test(data + bytes("!"), bytes("Typst!"))
+#test(bytes((84, 121)), bytes("Ty"))
+
+---
+// Test encodings.
+#test(str(bytes("Typst"), encoding: "hex"), "5479707374")
+#test(str(bytes("Typst"), encoding: "base64"), "VHlwc3Q=")
+#test(bytes("5479707374", encoding: "hex"), bytes("Typst"))
+#test(bytes("VHlwc3Q=", encoding: "base64"), bytes("Typst"))
+
+---
+// Test iterating over bytes.
+#let sum = 0
+#for byte in bytes((1, 2, 3)) { sum += byte }
+#test(sum, 6)
+
+---
+// Error: 3-19 cannot add bytes and string
+#(bytes("a") + "b")
+
+---
+// Error: 8-14 array must only contain integers between 0 and 255
+#bytes((256,))
+
+---
+// Error: 8-12 string is not valid hexadecimal
+#bytes("zz", encoding: "hex")
+
+---
+// Error: 2-18 byte index out of bounds (index: 5, len: 1)
+#bytes("a").at(5)
+
+---
+// Error: 6-19 bytes are not valid utf-8
+#str(bytes((255,)))
@@ -14,6 +14,14 @@
 #test(rgb("#133337").negate(), rgb(236, 204, 200))
 #test(white.lighten(100%), white)
 
+// Test HSL colors and conversion to RGB.
+#test(hsl(0deg, 100%, 50%), rgb(255, 0, 0))
+#test(hsl(210deg, 50%, 60%), rgb(102, 153, 204))
+#test(hsl(-240deg, 100%, 25%), rgb(0, 128, 0))
+#test(rgb(luma(50%)), rgb(128, 128, 128))
+#test(rgb(cmyk(0%, 100%, 100%, 0%)), rgb(255, 0, 0))
+#test(rgb(rgb(cmyk(27%, 0%, 3%, 5%))), rgb(cmyk(27%, 0%, 3%, 5%)))
+
 ---
 // Test gray color conversion.
 // Ref: true
@@ -40,6 +48,10 @@
 // Error: 21-26 expected integer or ratio, found boolean
 #rgb(10%, 20%, 30%, false)
 
+---
+// Error: 17-21 ratio must be between 0% and 100%
+#hsl(0deg, 50%, 120%)
+
 ---
 // Ref: true
 #let envelope = symbol(
@@ -159,3 +171,108 @@
 ---
 // Error: 26-36 failed to format datetime in the requested format
 #datetime.today().display("[hour]")
+
+---
+// Test datetime arithmetic and durations.
+#let a = datetime(year: 2023, month: 6, day: 30)
+#let b = datetime(year: 2023, month: 6, day: 1)
+#test((a - b).days(), 29.0)
+#test((b - a).weeks(), -29.0 / 7)
+#test(b + duration(days: 29), a)
+#test(a - duration(weeks: 1), datetime(year: 2023, month: 6, day: 23))
+#test(duration(hours: 1, minutes: 30).minutes(), 90.0)
+#test(duration(minutes: 90), duration(hours: 1) + duration(seconds: 1800))
+#test(-duration(days: 1) < duration(), true)
+#test(repr(duration(days: 8, seconds: 5)), "duration(weeks: 1, days: 1, seconds: 5)")
+#test(datetime(hour: 23, minute: 0, second: 0) + duration(hours: 2), datetime(hour: 1, minute: 0, second: 0))
+
+---
+// Error: 3-61 cannot subtract datetimes of different kinds
+#(datetime.today() - datetime(hour: 1, minute: 0, second: 0))
+
+---
+// Error: 3-68 duration is too large
+#(duration(weeks: 10000000000000) + duration(weeks: 10000000000000))
+
+---
+// Test gradients.
+#test(gradient.linear(red), red)
+#test(gradient.linear(red, blue), gradient.linear((0%, red), (100%, blue)))
+#test(gradient.linear(red, blue, green), gradient.linear((0%, red), (50%, blue), (100%, green)))
+#test(gradient.linear(red, (50%, green), blue, yellow), gradient.linear((0%, red), (50%, green), (75%, blue), (100%, yellow)))
+#test(gradient(red, blue, angle: 45deg), gradient.linear(red, blue, angle: 45deg))
+#test(type(gradient.linear(red, blue)), "gradient")
+#test(repr(gradient.linear(luma(0), luma(255), angle: 90deg)), "gradient.linear((0%, luma(0)), (100%, luma(255)), angle: 90deg)")
+
+---
+// Error: 17-19 gradient must have at least one stop
+#gradient.linear()
+
+---
+// Error: 30-41 offsets must be in ascending order
+#gradient.linear((50%, red), (20%, blue))
+
+---
+// Error: 18-29 offset must be between 0% and 100%
+#gradient.linear((120%, red), blue)
+
+---
+// Error: 18-24 array must contain exactly an offset and a color
+#gradient.linear((red,))
+
+---
+// Test random generators.
+#{
+  let a = random(42)
+  let b = random(42)
+  let xs = ()
+  let ys = ()
+  for i in range(10) {
+    let x = a.next()
+    test(x >= 0.0 and x < 1.0, true)
+    xs.push(x)
+    ys.push(b.next())
+  }
+  test(xs, ys)
+  let c = random(1)
+  let d = random(2)
+  test(c.int(0, 1000) != d.int(0, 1000), true)
+  test(type(a), "random generator")
+}
+
+---
+#{
+  let rng = random(7)
+  for i in range(20) {
+    let n = rng.int(-2, 3)
+    test(n >= -2 and n < 3, true)
+  }
+}
+
+---
+// Error: 8-10 missing argument: seed
+#random()
+
+---
+#let rng = random(1)
+// Error: 2-15 minimum must be smaller than maximum
+#rng.int(3, 3)
+
+---
+// Error: 3-12 cannot mutate a temporary value
+#(random(1).next())
+
+---
+// Random generator methods on other values don't need mutable access.
+// Error: 7-10 module `calc` does not contain `int`
+#calc.int(1)
+
+---
+// Generators in collections advance in place.
+#{
+  let state = (rng: random(3), gens: (random(3),))
+  let x = state.rng.next()
+  test(state.rng.next() != x, true)
+  test(state.gens.at(0).next(), x)
+  test((state.rng).int(0, 1), 0)
+}
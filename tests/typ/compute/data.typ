@@ -11,9 +11,18 @@
 #let data = read("/missing.txt")
 
 ---
-// Error: 18-28 file is not valid utf-8
+// Test reading raw and encoded bytes.
+#test(read("/hello.txt", encoding: none), bytes("Hello, world!"))
+#test(read("/hello.txt", encoding: "hex").len(), 26)
+
+---
+// Error: 18-28 file is not valid utf-8 (at /bad.txt)
 #let data = read("/bad.txt")
 
+---
+// Error: 42-49 expected "utf8", "base64", "hex", or none
+#let data = read("/hello.txt", encoding: "utf16")
+
 ---
 // Test reading CSV data.
 // Ref: true
@@ -22,6 +31,13 @@
 #let cells = data.at(0).map(strong) + data.slice(1).flatten()
 #table(columns: data.at(0).len(), ..cells)
 
+---
+// Test reading CSV data with a header row.
+#let data = csv("/zoo.csv", header: true)
+#test(data.len(), 3)
+#test(data.at(0).Name, "Debby")
+#test(data.at(2), (Name: "Sleepy", Species: "Dolphin", Weight: "150kg", Length: "180cm"))
+
 ---
 // Error: 6-16 file not found (searched at typ/compute/nope.csv)
 #csv("nope.csv")
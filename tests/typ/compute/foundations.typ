@@ -120,3 +120,15 @@ _No relative giraffe!_
 ---
 // Error: 7-12 expected semicolon or line break
 #eval("1 2")
+
+---
+// Test hashing.
+#test(hash("a"), hash("a"))
+#test(hash((1, 2)) != hash((2, 1)), true)
+#test(hash(none).len(), 32)
+#test(sha256(bytes("")), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+#test(sha256(bytes("Typst")), "38c14037e93b9c2bad92513749a7e5c37340291749afe2c605b3d196ad2e0f2a")
+
+---
+// Error: 9-16 expected bytes, found string
+#sha256("Typst")
@@ -0,0 +1,39 @@
+// Test snapping lines to a baseline grid.
+// Ref: false
+
+---
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 2pt, orphans: 1, widows: 1)
+#set block(spacing: 0pt)
+#style(styles => {
+  test(measure([a \ b \ c], styles).height, 34pt)
+})
+
+#set block(baseline-grid: 12pt)
+#style(styles => {
+  // Lines that match the rhythm stay where they are.
+  test(measure([a \ b \ c], styles).height, 36pt)
+
+  // Lines with too much leading skip a grid line.
+  test(measure(par(leading: 3pt)[a \ b \ c], styles).height, 60pt)
+
+  // Blocks are not snapped, but the line after them is.
+  test(measure([a #block(height: 5pt) b], styles).height, 36pt)
+})
+
+---
+// The grid can also be enabled for a single block.
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#style(styles => {
+  test(measure(block[a], styles).height, 10pt)
+  test(measure(block(baseline-grid: 12pt)[a], styles).height, 12pt)
+})
+
+---
+// Lines that would no longer fit after snapping move to the next page.
+#set page(width: 100pt, height: 30pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 3pt, orphans: 1, widows: 1)
+#set block(baseline-grid: 12pt)
+a #locate(loc => test(loc.page(), 1)) \
+b #locate(loc => test(loc.page(), 2))
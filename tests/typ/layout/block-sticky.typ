@@ -0,0 +1,34 @@
+// Test that sticky blocks are kept with their successor.
+// Ref: false
+
+---
+// The sticky block moves to the next page with the block after it.
+#set page(width: 100pt, height: 60pt, margin: 0pt)
+#set block(spacing: 0pt)
+#block(height: 20pt)
+#block(sticky: true, height: 10pt)[#locate(loc => test(loc.page(), 2))]
+#block(height: 40pt)
+
+---
+// Headings are sticky.
+#set page(width: 100pt, height: 60pt, margin: 0pt)
+#set block(spacing: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#block(height: 20pt)
+= Heading #locate(loc => test(loc.page(), 2))
+#block(height: 30pt)
+
+---
+// Too large to move together, so the sticky block stays.
+#set page(width: 100pt, height: 60pt, margin: 0pt)
+#set block(spacing: 0pt)
+#block(height: 20pt)
+#block(sticky: true, height: 10pt)[#locate(loc => test(loc.page(), 1))]
+#block(height: 55pt)
+
+---
+// Moving the only block on the page would leave it empty.
+#set page(width: 100pt, height: 60pt, margin: 0pt)
+#set block(spacing: 0pt)
+#block(sticky: true, height: 10pt)[#locate(loc => test(loc.page(), 1))]
+#block(height: 55pt)
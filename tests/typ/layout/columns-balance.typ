@@ -0,0 +1,23 @@
+// Test balanced columns.
+// Ref: false
+
+---
+// Balanced columns are about half as tall as a single full column.
+#style(styles => {
+  let body = lorem(40)
+  let single = measure(body, styles, width: 100pt)
+  let balanced = measure(
+    columns(2, gutter: 0pt, balance: true, body),
+    styles,
+    width: 200pt,
+  )
+  test(balanced.height < single.height * 0.6, true)
+  test(balanced.height >= single.height / 2, true)
+})
+
+---
+// Unbreakable content that is taller than a column overflows.
+#box(height: 40pt, columns(2, balance: true)[
+  #block(breakable: false, height: 60pt, width: 100%)
+  #lorem(5)
+])
@@ -39,3 +39,36 @@
   align(center)[A bit more to the top],
   [],
 )
+
+---
+// Fractional tracks nested in an auto track claim the available space.
+// Ref: false
+#set page(width: 120pt, height: auto, margin: 0pt)
+#let row = block(width: 100%, height: 10pt, fill: aqua)
+#let test-x(label, expected) = locate(loc => {
+  let found = query(label, loc).map(elem => elem.location().position().x)
+  if found.len() > 0 { test(found, expected) }
+})
+
+#grid(
+  columns: (auto, 40pt),
+  grid(columns: (1fr, 1fr), [#row <a>], [#row <a>]),
+  [#row <a>],
+)
+#test-x(<a>, (0pt, 40pt, 80pt))
+
+// In a grid with fractional columns, the auto column takes up all the space.
+#grid(
+  columns: (auto, 1fr),
+  grid(columns: (1fr, 1fr), [#row <b>], [#row <b>]),
+  [#row <b>],
+)
+#test-x(<b>, (0pt, 60pt, 120pt))
+
+// Content with a fixed width is not affected.
+#grid(
+  columns: (auto, 1fr),
+  block(width: 30pt, grid(columns: (1fr, 1fr), [#row <c>], [#row <c>])),
+  [#row <c>],
+)
+#test-x(<c>, (0pt, 15pt, 30pt))
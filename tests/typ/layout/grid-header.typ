@@ -0,0 +1,76 @@
+// Test repeated grid and table headers.
+// Ref: false
+
+---
+// The header is repeated on every page the table breaks across.
+#set page(height: 120pt)
+#table(
+  columns: 2,
+  table.header[*Name*][*Score*],
+  ..range(12).map(n => ([Player #n], [#(n * 10)])).flatten(),
+)
+
+---
+#let row = block(width: 100%, height: 10pt, fill: aqua)
+#let test-positions(label, expected) = locate(loc => {
+  let found = query(label, loc).map(elem => {
+    let pos = elem.location().position()
+    (pos.page, pos.x, pos.y)
+  })
+
+  // Nothing is found in the first layout pass.
+  if found.len() > 0 { test(found, expected) }
+})
+
+#set page(height: 60pt)
+#set block(spacing: 0pt)
+
+// The body continues below the repeated header on each page.
+#grid(
+  rows: 10pt,
+  grid.header(row),
+  ..range(5).map(i => [#row <fixed>]),
+)
+#test-positions(<fixed>, (
+  (1, 10pt, 20pt), (1, 10pt, 30pt), (1, 10pt, 40pt),
+  (2, 10pt, 20pt), (2, 10pt, 30pt),
+))
+
+// An auto row is measured against the space below the repeated header.
+#pagebreak()
+#grid(
+  grid.header(row),
+  [#for i in range(8) [#row <auto>]],
+)
+#test-positions(<auto>, (
+  (3, 10pt, 20pt), (3, 10pt, 30pt), (3, 10pt, 40pt),
+  (4, 10pt, 20pt), (4, 10pt, 30pt), (4, 10pt, 40pt),
+  (5, 10pt, 20pt), (5, 10pt, 30pt),
+))
+
+// A fixed row that is taller than the space below the header stays on the
+// page instead of moving on forever.
+#pagebreak()
+#grid(
+  rows: (10pt, 35pt, 10pt),
+  grid.header(row),
+  [#row <tall>],
+  [#row <tall>],
+)
+#test-positions(<tall>, ((6, 10pt, 20pt), (7, 10pt, 20pt)))
+
+---
+// The following cells start in the row below a header.
+#style(styles => {
+  let size = measure(grid(
+    columns: (10pt, 10pt),
+    rows: 10pt,
+    grid.header[A],
+    [B], [C],
+  ), styles)
+  test(size.height, 20pt)
+})
+
+---
+// Error: 24-38 header must be the first cell of the grid
+#grid(columns: 2, [A], grid.header[B])
@@ -0,0 +1,98 @@
+// Test cells spanning multiple columns and rows.
+// Ref: false
+
+---
+// A cell spanning multiple auto columns makes them wide enough.
+#style(styles => {
+  let size = measure(grid(
+    columns: (auto, auto),
+    grid.cell(colspan: 2, box(width: 100pt)),
+    box(width: 10pt), box(width: 10pt),
+  ), styles)
+  test(size.width, 100pt)
+})
+
+---
+// A cell spanning multiple auto rows makes them tall enough.
+#style(styles => {
+  let size = measure(grid(
+    columns: 2,
+    grid.cell(rowspan: 2, box(height: 100pt)),
+    box(height: 10pt), box(height: 10pt),
+  ), styles)
+  test(size.height, 100pt)
+})
+
+---
+#let row = block(width: 100%, height: 10pt, fill: aqua)
+#let test-positions(label, expected) = locate(loc => {
+  let found = query(label, loc).map(elem => {
+    let pos = elem.location().position()
+    (pos.page, pos.x, pos.y)
+  })
+
+  // Nothing is found in the first layout pass.
+  if found.len() > 0 { test(found, expected) }
+})
+
+// A merged cell with gutter, in both directions.
+#set block(spacing: 0pt)
+#grid(
+  columns: (32pt, 32pt, 32pt),
+  rows: 10pt,
+  gutter: 2pt,
+  grid.cell(colspan: 2)[#row <a>], grid.cell(rowspan: 2)[#row <b>],
+  [#row <c>], [#row <d>],
+)
+
+#set text(dir: rtl)
+#grid(
+  columns: (32pt, 32pt, 32pt),
+  rows: 10pt,
+  gutter: 2pt,
+  grid.cell(colspan: 2)[#row <a>], grid.cell(rowspan: 2)[#row <b>],
+  [#row <c>], [#row <d>],
+)
+
+#test-positions(<a>, ((1, 10pt, 10pt), (1, 44pt, 32pt)))
+#test-positions(<b>, ((1, 78pt, 10pt), (1, 10pt, 32pt)))
+#test-positions(<c>, ((1, 10pt, 22pt), (1, 78pt, 44pt)))
+#test-positions(<d>, ((1, 44pt, 22pt), (1, 44pt, 44pt)))
+
+---
+#let row = block(width: 100%, height: 10pt, fill: aqua)
+#let test-positions(label, expected) = locate(loc => {
+  let found = query(label, loc).map(elem => {
+    let pos = elem.location().position()
+    (pos.page, pos.x, pos.y)
+  })
+
+  // Nothing is found in the first layout pass.
+  if found.len() > 0 { test(found, expected) }
+})
+
+// A cell spanning rows that break across pages is split between them.
+#set page(height: 60pt)
+#set block(spacing: 0pt)
+#grid(
+  columns: (1fr, 1fr),
+  grid.cell(rowspan: 3)[#for i in range(5) [#row <split>]],
+  [#row <other>], [#row <other>], [#row <other>],
+)
+#test-positions(<split>, (
+  (1, 10pt, 10pt), (1, 10pt, 20pt), (1, 10pt, 30pt), (1, 10pt, 40pt),
+  (2, 10pt, 10pt),
+))
+#test-positions(<other>, ((1, 60pt, 10pt), (1, 60pt, 20pt), (1, 60pt, 30pt)))
+
+---
+// Error: 19-43 cell spans more columns than the grid has
+#grid(columns: 2, grid.cell(colspan: 3)[A])
+
+---
+// Error: 24-48 cell spans past the last column
+#grid(columns: 2, [A], grid.cell(colspan: 2)[B])
+
+---
+// Error: 55-79 cell overlaps with another cell
+#grid(columns: 3, [A], grid.cell(rowspan: 2)[B], [C], grid.cell(colspan: 2)[D])
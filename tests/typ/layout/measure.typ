@@ -0,0 +1,11 @@
+// Test measuring content.
+// Ref: false
+
+---
+// Test measuring in a region of limited width.
+#style(styles => {
+  let wide = measure(lorem(20), styles)
+  let narrow = measure(lorem(20), styles, width: 50pt)
+  test(narrow.width <= 50pt, true)
+  test(narrow.height > wide.height, true)
+})
@@ -28,3 +28,28 @@ Hi #box(pad(left: 10pt)[A]) there
 ---
 // Test that padding adding up to 100% does not panic.
 #pad(50%)[]
+
+---
+// Test mixed relative and absolute padding.
+// Ref: false
+#let row = block(width: 100%, height: 10pt, fill: aqua)
+#pad(left: 25% + 5pt, right: 25%)[#row <mixed>]
+#locate(loc => {
+  let found = query(<mixed>, loc)
+  if found.len() > 0 { test(found.first().location().position().x, 40pt) }
+})
+#style(styles => {
+  // The relative padding is relative to the padded size.
+  let padded = pad(left: 25% + 5pt, right: 25%, block(width: 45pt, height: 10pt))
+  test(measure(padded, styles), (width: 100pt, height: 10pt))
+})
+
+---
+// Test negative padding for bleed.
+// Ref: false
+#let row = block(width: 100%, height: 10pt, fill: aqua)
+#pad(x: 20pt, pad(x: -10pt)[#row <bleed>])
+#locate(loc => {
+  let found = query(<bleed>, loc)
+  if found.len() > 0 { test(found.first().location().position().x, 20pt) }
+})
@@ -0,0 +1,33 @@
+// Test two-sided page margins and the page binding.
+// Ref: false
+
+---
+// Test that the left edge of the page's content is at `x`. Positions are
+// still unknown, and thus zero, in the first layout pass.
+#let left-edge(x) = place(left, locate(loc => {
+  let pos = loc.position()
+  if pos.x != 0pt { test(pos.x, x) }
+}))
+
+#set page(height: 50pt, margin: (inside: 30pt, outside: 10pt, y: 5pt))
+#left-edge(30pt)
+Right-hand page
+#pagebreak()
+#left-edge(10pt)
+Left-hand page
+
+#set page(height: 50pt, binding: right, margin: (inside: 30pt, rest: 10pt))
+#set text(dir: rtl)
+#left-edge(10pt)
+Right-bound
+#pagebreak()
+#left-edge(30pt)
+Right-bound
+
+---
+// Error: 19-63 `inside` and `outside` are mutually exclusive with `left` and `right`
+#set page(margin: (left: 1cm, outside: 2cm, y: 1cm, rest: 0pt))
+
+---
+// Error: 20-26 must be `left` or `right`
+#set page(binding: center)
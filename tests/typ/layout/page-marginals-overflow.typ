@@ -0,0 +1,22 @@
+// Test that headers and footers which don't fit into their margin are clipped.
+// Ref: false
+
+---
+// Warning: 48-66 page header does not fit into its margin and is clipped
+#set page(height: 100pt, margin: 20pt, header: rect(height: 40pt))
+Body
+
+---
+// Warning: 48-66 page footer does not fit into its margin and is clipped
+#set page(height: 100pt, margin: 20pt, footer: rect(height: 40pt))
+Body
+
+---
+// A header that fits doesn't produce a warning.
+#set page(height: 100pt, margin: 20pt, header: rect(height: 5pt))
+Body
+
+---
+// The footer with the page number is not measured and thus not clipped.
+#set page(height: 100pt, margin: (bottom: 5pt, rest: 20pt), numbering: "1")
+Body
@@ -43,3 +43,38 @@ First
 #page[Second]
 #pagebreak(weak: true)
 #page[Third]
+
+---
+// Test clearing to even or odd pages.
+// Ref: false
+#set page(width: 80pt, height: 30pt)
+First <p>
+#pagebreak(to: "odd")
+Third <p>
+#pagebreak(to: "even")
+Fourth <p>
+#pagebreak(to: "even")
+Sixth <p>
+#locate(loc => {
+  let pages = query(<p>, loc).map(it => it.location().position().page)
+  if pages.len() > 0 { test(pages, (1, 3, 4, 6)) }
+})
+
+---
+// A weak break at the start of the document does nothing, whichever page it
+// clears to.
+// Ref: false
+#set page(width: 80pt, height: 30pt)
+#pagebreak(weak: true, to: "odd")
+#pagebreak(weak: true, to: "even")
+First <p>
+#pagebreak(weak: true, to: "even")
+#pagebreak(weak: true, to: "even")
+Second <p>
+#locate(loc => {
+  let pages = query(<p>, loc).map(it => it.location().position().page)
+  if pages.len() > 0 {
+    test(pages, (1, 2))
+    test(counter(page).final(loc), (2,))
+  }
+})
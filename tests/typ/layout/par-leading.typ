@@ -0,0 +1,32 @@
+// Test leading and paragraph spacing.
+// Ref: false
+
+---
+#set text(10pt, top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 1em)
+#style(styles => {
+  // Relative leading is resolved against the line's font size.
+  test(measure([a \ b], styles).height, 30pt)
+  test(measure(text(20pt)[a \ b], styles).height, 40pt)
+
+  // Lines with mixed font sizes take the leading of their tallest text.
+  test(measure([a \ b #text(20pt)[c]], styles).height, 40pt)
+  test(measure([a #text(20pt)[b] \ c \ d], styles).height, 60pt)
+})
+
+---
+#set text(10pt, top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 5pt, spacing: 15pt)
+#style(styles => {
+  test(measure([a \ b], styles).height, 25pt)
+  test(measure([a #parbreak() b], styles).height, 35pt)
+})
+
+---
+// Lines with larger text get more leading than the lines around them.
+#set text(10pt, top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 0.5em)
+#style(styles => {
+  test(measure([a \ b \ c], styles).height, 40pt)
+  test(measure([a \ #text(20pt)[b] \ c], styles).height, 50pt)
+})
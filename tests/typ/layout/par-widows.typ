@@ -0,0 +1,63 @@
+// Test widow and orphan control.
+// Ref: false
+
+---
+// The third line moves to the next page so that there are three widows.
+#set page(width: 100pt, height: 60pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 0pt, widows: 3)
+#set block(spacing: 0pt)
+#v(30pt)
+a #locate(loc => test(loc.page(), 1)) \
+b #locate(loc => test(loc.page(), 1)) \
+c #locate(loc => test(loc.page(), 2)) \
+d \
+e
+
+---
+// The whole paragraph moves to the next page since three orphans don't fit.
+#set page(width: 100pt, height: 60pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 0pt, orphans: 3)
+#set block(spacing: 0pt)
+#v(40pt)
+a #locate(loc => test(loc.page(), 2)) \
+b \
+c
+
+---
+// A paragraph with fewer lines than required is broken as usual.
+#set page(width: 100pt, height: 60pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 0pt, orphans: 3, widows: 3)
+#set block(spacing: 0pt)
+#v(50pt)
+a #locate(loc => test(loc.page(), 1)) \
+b #locate(loc => test(loc.page(), 2))
+
+---
+// Without widow and orphan control, a single line may stay behind.
+#set page(width: 100pt, height: 60pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 0pt, orphans: 1, widows: 1)
+#set block(spacing: 0pt)
+#v(50pt)
+a #locate(loc => test(loc.page(), 1)) \
+b #locate(loc => test(loc.page(), 2)) \
+c
+
+---
+// If widow and orphan control conflict, the paragraph is kept together.
+#set page(width: 100pt, height: 60pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 0pt, orphans: 2, widows: 3)
+#set block(spacing: 0pt)
+#v(40pt)
+a #locate(loc => test(loc.page(), 2)) \
+b \
+c \
+d
+
+---
+// Error: 19-20 number must be positive
+#set par(orphans: 0)
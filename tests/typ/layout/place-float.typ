@@ -0,0 +1,31 @@
+// Test floating placement.
+// Ref: false
+
+---
+#let test-positions(label, expected) = locate(loc => {
+  let found = query(label, loc).map(elem => {
+    let pos = elem.location().position()
+    (pos.page, pos.x, pos.y)
+  })
+
+  // Nothing is found in the first layout pass.
+  if found.len() > 0 { test(found, expected) }
+})
+
+// Floats move to the top or bottom of the page and the other content makes
+// room for them and their clearance.
+#set page(height: 140pt, width: 120pt)
+#set place(clearance: 5pt)
+#set block(spacing: 0pt)
+#block(width: 100%, height: 10pt, fill: aqua) <body>
+#place(top, float: true)[#block(width: 100%, height: 20pt, fill: aqua) <top>]
+#place(bottom + right, float: true)[#block(width: 50%, height: 20pt, fill: red) <bottom>]
+#block(width: 100%, height: 10pt, fill: aqua) <body>
+
+#test-positions(<body>, ((1, 10pt, 35pt), (1, 10pt, 45pt)))
+#test-positions(<top>, ((1, 10pt, 10pt),))
+#test-positions(<bottom>, ((1, 60pt, 110pt),))
+
+---
+// Error: 2-43 floating placement must be `top` or `bottom`
+#place(horizon, float: true, rect[Floaty])
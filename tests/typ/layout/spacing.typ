@@ -34,3 +34,29 @@ A #h(1fr) B
 // Missing spacing.
 // Error: 11-13 missing argument: amount
 Totally #h() ignored
+
+---
+// Fractional vertical spacing is shared proportionally.
+// Ref: false
+#set page(height: 100pt)
+#set block(spacing: 0pt)
+#let row = block(width: 100%, height: 10pt, fill: aqua)
+#row <row>
+#v(1fr)
+#row <row>
+#v(3fr)
+#row <row>
+#locate(loc => {
+  let ys = query(<row>, loc).map(it => it.location().position().y)
+  if ys.len() > 0 { test(ys, (10pt, 32.5pt, 80pt)) }
+})
+
+---
+// Fractional vertical spacing collapses in unbounded regions.
+// Ref: false
+#set block(spacing: 0pt)
+#style(styles => {
+  let row = block(height: 10pt)
+  test(measure([#row #v(1fr) #row], styles).height, 20pt)
+  test(measure(block[#row #v(2fr) #row], styles).height, 20pt)
+})
@@ -50,3 +50,18 @@
   align(left, [B]),
   [C],
 )
+
+---
+// Test baseline alignment in horizontal stacks.
+// Ref: false
+#set text(top-edge: 1em, bottom-edge: -0.5em)
+#style(styles => {
+  let items = (text(20pt)[Big], [small], box(width: 6pt, height: 40pt))
+
+  // Aligned at the top, the tallest item determines the height.
+  test(measure(stack(dir: ltr, spacing: 4pt, ..items), styles).height, 40pt)
+
+  // On a shared baseline, the text extends below the box.
+  let aligned = stack(dir: ltr, spacing: 4pt, baseline: true, ..items)
+  test(measure(aligned, styles).height, 50pt)
+})
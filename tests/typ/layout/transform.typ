@@ -47,3 +47,27 @@ nor #xetex!
 #box(scale(r, x: 50%, y: 200%, origin: left + top))
 #box(scale(r, x: 50%, origin: center))
 #box(scale(r, x: 50%, y: 200%, origin: right + bottom))
+
+---
+// Test reflowing transforms.
+// Ref: false
+#let approx(a, b) = assert(a > b - 0.01pt and a < b + 0.01pt)
+#style(styles => {
+  // The frame takes the size of the transformed content's bounding box.
+  let plain = measure([World], styles)
+  let rotated = measure(rotate(90deg, reflow: true)[World], styles)
+  approx(rotated.width, plain.height)
+  approx(rotated.height, plain.width)
+
+  let r = rect(width: 30pt, height: 10pt)
+  let rotated = measure(rotate(-45deg, reflow: true, r), styles)
+  approx(rotated.width, 40pt * calc.cos(45deg))
+  approx(rotated.height, 40pt * calc.cos(45deg))
+
+  let scaled = measure(scale(200%, reflow: true, r), styles)
+  approx(scaled.width, 60pt)
+  approx(scaled.height, 20pt)
+
+  // Without reflow, the layout is not affected.
+  test(measure(scale(200%, r), styles), measure(r, styles))
+})
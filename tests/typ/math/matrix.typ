@@ -57,3 +57,44 @@ $ a + mat(delim: #none, 1, 2; 3, 4) + b $
 ---
 // Error: 13-14 expected array, found content
 $ mat(1, 2; 3, 4, delim: "[") $,
+
+---
+// Error: 13-14 all rows of a matrix must have the same number of cells (expected 2, found 1)
+$ mat(1, 2; 3) $
+
+---
+// A row of empty cells keeps the height of a parenthesis.
+// Ref: false
+#style(styles => {
+  let height(body) = measure(body, styles).height
+  test(height($mat(1; ""; 2)$), height($mat(1; "("; 2)$))
+  assert(height($mat(1; ""; 2)$) > height($mat(1; 2)$))
+})
+
+---
+// Cells are aligned per column with an array or by a function of the column
+// and row index.
+// Ref: false
+#let cell(width, l) = [#box(width: width, height: 4pt, fill: aqua)#l]
+#let approx(a, b) = assert(a > b - 0.01pt and a < b + 0.01pt)
+#let x-of(l, loc) = query(l, loc).first().location().position().x
+
+$ mat(
+  align: #(left, right),
+  #cell(20pt, <a-wide>), #cell(20pt, <b-wide>);
+  #cell(4pt, <a>), #cell(4pt, <b>)
+) $
+
+$ mat(
+  align: #((x, y) => if y == 0 { left } else { right }),
+  #cell(20pt, <c-wide>);
+  #cell(4pt, <c>)
+) $
+
+#locate(loc => {
+  if query(<a>, loc).len() > 0 {
+    approx(x-of(<a>, loc), x-of(<a-wide>, loc))
+    approx(x-of(<b>, loc), x-of(<b-wide>, loc) + 16pt)
+    approx(x-of(<c>, loc), x-of(<c-wide>, loc) + 16pt)
+  }
+})
@@ -36,3 +36,8 @@ And quark! @quark
 = Multiple Bibs
 Now we have multiple bibliographies containing #cite("glacier-melt", "keshav2007read")
 #bibliography(("/works.bib", "/works_too.bib"))
+
+---
+// Error: 2-30 bibliography does not contain the key `nonexistent`
+#cite("arrgh", "nonexistent")
+#bibliography("/works.bib")
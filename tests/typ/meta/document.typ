@@ -10,6 +10,16 @@ What's up?
 // Ref: false
 #set document(author: ("A", "B"))
 
+---
+// Keywords and date are fine as well.
+// Ref: false
+#set document(keywords: ("report", "draft"), date: datetime(year: 2023, month: 6, day: 1))
+#set document(keywords: "single")
+
+---
+// Error: 21-25 expected datetime or none, found string
+#set document(date: "now")
+
 ---
 // This, too.
 // Error: 23-29 expected string, found integer
@@ -0,0 +1,19 @@
+// Test the position of figure captions.
+// Ref: false
+
+---
+#set figure(caption-pos: top)
+#show figure: it => {
+  test(it.caption-pos, top)
+  it
+}
+
+#figure([Body], caption: [Caption])
+
+---
+// Error: 26-30 alignment must be vertical
+#figure([], caption-pos: left)
+
+---
+// Error: 26-33 caption position must be top or bottom
+#figure([], caption-pos: horizon)
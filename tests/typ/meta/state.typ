@@ -37,3 +37,10 @@ Was: #locate(location => {
 #trait[Adventure]
 #trait[Fear]
 #trait[Anger]
+
+---
+// Test that a state depending on its own final value warns.
+// Ref: false
+// Warning: layout did not converge within 5 attempts
+#let s = state("self", 0)
+#locate(loc => s.update(s.final(loc) + 1))
@@ -17,4 +17,12 @@ Aب😀🏞سمB
 01️⃣2
 
 // Tofus are rendered with the first font.
+// Warning: 1-6 no font has a glyph for 'ዲ' (U+12F2), tried "linux libertine" and the fallback fonts
+// Warning: 1-6 no font has a glyph for 'ሞ' (U+121E), tried "linux libertine" and the fallback fonts
 A🐈ዲሞB
+
+---
+// A character is only reported once, even if other fonts were tried for it.
+// Ref: false
+// Warning: 1-2 no font has a glyph for 'ዲ' (U+12F2), tried "linux libertine" and the fallback fonts
+ዲ #text(font: "IBM Plex Sans")[ዲ]
@@ -61,3 +61,11 @@ fi vs. #text(features: (liga: 0))[No fi]
 ---
 // Error: 21-35 expected string, found boolean
 #set text(features: ("tag", false))
+
+---
+// Error: 21-37 feature tag must be four ASCII characters, found "ss1"
+#set text(features: ("smcp", "ss1"))
+
+---
+// Error: 21-36 feature tag must be four ASCII characters, found "ligatures"
+#set text(features: (ligatures: 0))
@@ -32,6 +32,8 @@ Emoji: 🐪, 🌋, 🏞
 
 // Disable font fallback beyond the user-specified list.
 // Without disabling, New Computer Modern Math would come to the rescue.
+// Warning: 2:6-2:7 no font has a glyph for '𝛼' (U+1D6FC), tried "pt sans", "twitter color emoji"
+// Warning: 2:10-2:12 no font has a glyph for '𝛽' (U+1D6FD), tried "pt sans", "twitter color emoji"
 #set text(font: ("PT Sans", "Twitter Color Emoji"), fallback: false)
 2π = 𝛼 + 𝛽. ✅
 
@@ -0,0 +1,26 @@
+// Test text highlighting.
+// Ref: false
+
+---
+// The highlight doesn't affect the layout of the text.
+#style(styles => {
+  let plain = measure([Some text], styles)
+  let marked = measure(highlight(extent: 5pt, radius: 2pt)[Some text], styles)
+  test(marked.width, plain.width)
+  test(marked.height, plain.height)
+})
+
+---
+// Highlights that span line breaks and style changes don't affect the
+// layout either.
+#style(styles => {
+  let body = [A highlight that spans multiple lines with *strong* text.]
+  let plain = measure(block(width: 60pt, body), styles)
+  assert(plain.height > measure(body, styles).height)
+  test(measure(block(width: 60pt, highlight(fill: aqua, body)), styles), plain)
+  test(measure(block(width: 60pt, highlight(radius: 2pt, body)), styles), plain)
+})
+
+---
+// Error: 18-21 expected color or gradient, found string
+#highlight(fill: "a")[A]
@@ -49,3 +49,23 @@ It's a #emph[Tree]beard.
 #set page(width: 60pt)
 #set text(hyphenate: true)
 #h(6pt) networks, the rest.
+
+---
+// The last word of a paragraph is hyphenated like any other word. Keeping it
+// whole would change the layout of the single-word cells above, so this pins
+// the current behaviour. The minimum fragment lengths come from hypher's
+// patterns for each language.
+// Ref: false
+#set page(width: 80pt)
+#let marker = box(width: 0pt, height: 0pt, fill: red)
+
+#text(hyphenate: false)[Hi extraordinary#marker <whole>]
+
+#text(hyphenate: true)[Hi extraordinary#marker <split>]
+
+#locate(loc => {
+  let x(l) = query(l, loc).first().location().position().x
+  if query(<whole>, loc).len() > 0 {
+    assert(x(<split>) < x(<whole>))
+  }
+})
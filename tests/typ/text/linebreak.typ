@@ -43,3 +43,15 @@ Second part
 // Test comments at the end of a line with pre-spacing
 First part          //
 Second part
+
+---
+// Consecutive forced breaks produce empty lines with the height and leading
+// of a regular line.
+// Ref: false
+#set text(10pt, top-edge: 10pt, bottom-edge: 0pt)
+#set par(leading: 5pt)
+#style(styles => {
+  test(measure([a \ \ c], styles).height, 40pt)
+  test(measure([a \ \ \ d], styles).height, 55pt)
+  test(measure([a #linebreak(justify: true) #linebreak() c], styles).height, 40pt)
+})
@@ -0,0 +1,38 @@
+// Test measuring glyphs.
+// Ref: false
+
+---
+#set text(10pt)
+#style(styles => {
+  let glyph = text.measure-glyph("g", styles)
+  test(type(glyph), "dictionary")
+  test(glyph.keys(), ("advance", "ascent", "descent", "bbox"))
+  assert(glyph.advance > 0pt)
+  assert(glyph.ascent > 0pt)
+  assert(glyph.descent > 0pt)
+  assert(glyph.bbox.y-min < 0pt)
+  assert(glyph.bbox.x-max > glyph.bbox.x-min)
+})
+
+---
+// Metrics scale with the font size.
+#set text(10pt)
+#style(small => text(20pt, style(large => {
+  let small = text.measure-glyph("A", small)
+  let large = text.measure-glyph("A", large)
+  test(large.advance, 2 * small.advance)
+  test(large.ascent, 2 * small.ascent)
+})))
+
+---
+// Glyphs without an outline have no bounding box.
+#style(styles => test(text.measure-glyph(" ", styles).bbox, none))
+
+---
+// Characters that no font contains have no metrics.
+#set text(font: "Linux Libertine", fallback: false)
+#style(styles => test(text.measure-glyph("😀", styles), none))
+
+---
+// Error: 37-41 expected exactly one character
+#style(styles => text.measure-glyph("ab", styles))
@@ -0,0 +1,28 @@
+// Test line numbers of raw blocks.
+// Ref: false
+
+---
+// The gutter makes room for the numbers.
+#style(styles => {
+  let code = "a\nb\nc"
+  let plain = measure(raw(code, block: true), styles)
+  let numbered = measure(raw(code, block: true, line-numbers: true), styles)
+  assert(numbered.width > plain.width)
+  test(numbered.height, plain.height)
+})
+
+---
+// Line numbers are ignored for inline raw text.
+#style(styles => {
+  let plain = measure(raw("a"), styles)
+  test(measure(raw("a", line-numbers: true), styles), plain)
+})
+
+---
+// A wrapped line keeps a single number.
+#set page(width: 100pt)
+#set raw(line-numbers: true)
+```rust
+let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+let sum: i32 = numbers.iter().sum();
+```
@@ -0,0 +1,23 @@
+// Test custom syntax highlighting themes.
+// Ref: false
+
+---
+#set raw(theme: ("comment": gray, "keyword": red))
+#show raw: it => {
+  test(it.theme.keys(), ("comment", "keyword"))
+  test(it.theme.keyword, red)
+  it
+}
+
+```rust
+// A comment.
+let x = 1;
+```
+
+---
+// Error: 17-33 expected color, found string
+#set raw(theme: ("string": "red"))
+
+---
+// Error: 17-45 invalid scope selector: "a.b.c.d.e.f.g.h.i"
+#set raw(theme: ("a.b.c.d.e.f.g.h.i": red))
@@ -17,3 +17,14 @@ n#super[1], n#sub[2], ... n#super[N]
 #underline[The claim#super[\[4\]]] has been disputed. \
 The claim#super[#underline[\[4\]]] has been disputed. \
 It really has been#super(box(text(baseline: 0pt, underline[\[4\]]))) \
+
+---
+// Test that the codepoints are preferred over the font's `sups` feature and
+// that the feature is used for text without codepoints.
+// Ref: false
+#style(styles => {
+  let size(body) = measure(body, styles).width
+  test(size[x#super[12]], size[x¹²])
+  test(size[x#super[th]], size[x#text(features: ("sups",))[th]])
+  test(size[x#super[\[th\]]], size[x#super(typographic: false)[\[th\]]])
+})
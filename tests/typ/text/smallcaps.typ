@@ -0,0 +1,35 @@
+// Test small capitals.
+// Ref: false
+
+---
+// Test the font's own small capitals.
+#set text(font: "Linux Libertine")
+#style(styles => {
+  let caps = measure(smallcaps[Small Capitals], styles)
+  test(caps, measure(text(features: ("smcp",))[Small Capitals], styles))
+  assert(caps.width != measure([Small Capitals], styles).width)
+})
+
+---
+// Test synthesized small capitals for a font without them.
+#set text(font: "IBM Plex Sans")
+#style(styles => {
+  // Uppercase letters stay at full size.
+  test(measure(smallcaps[SMALL], styles), measure([SMALL], styles))
+
+  // Lowercase letters become smaller uppercase letters.
+  let small = measure(smallcaps[mall], styles)
+  let upper = measure([MALL], styles)
+  assert(small.width < upper.width)
+  assert(small.height < upper.height)
+  assert(small != measure([mall], styles))
+})
+
+---
+// Test that content other than plain text keeps using the feature.
+#set text(font: "IBM Plex Sans")
+#style(styles => {
+  let size = measure(smallcaps[Small *Capitals*], styles)
+  test(size, measure(text(features: ("smcp",))[Small *Capitals*], styles))
+  test(size, measure([Small *Capitals*], styles))
+})
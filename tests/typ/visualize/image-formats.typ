@@ -0,0 +1,10 @@
+// Test detection of image formats.
+// Ref: false
+
+---
+// WebP images are detected from their contents.
+#image("/pixel.webp", width: 10pt)
+
+---
+// Error: 8-19 AVIF images are not supported in this build
+#image("/stub.avif")
@@ -50,3 +50,26 @@
 ---
 // Error: 7-31 point array must contain exactly two entries
 #path(((0%, 0%), (0%, 0%, 0%)))
+
+---
+// Test fill rules and smooth curves.
+// Ref: false
+#let star = ((50pt, 0pt), (80pt, 90pt), (0pt, 35pt), (100pt, 35pt), (20pt, 90pt))
+#style(styles => {
+  // The fill rule doesn't affect the size.
+  let size = (width: 100pt, height: 90pt)
+  test(measure(path(fill: blue, closed: true, ..star), styles), size)
+  let even-odd = path(fill: blue, fill-rule: "even-odd", closed: true, ..star)
+  test(measure(even-odd, styles), size)
+
+  // A smooth curve passes through the vertices and overshoots between them.
+  let points = ((0pt, 0pt), (20pt, 20pt), (40pt, 20pt))
+  test(measure(path(..points), styles), (width: 40pt, height: 20pt))
+  let smooth = measure(path(smooth: true, ..points), styles)
+  test(smooth.width, 40pt)
+  assert(smooth.height > 20pt)
+})
+
+---
+// Error: 18-23 expected "non-zero" or "even-odd"
+#path(fill-rule: "odd")
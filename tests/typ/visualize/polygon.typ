@@ -30,3 +30,40 @@
 ---
 // Error: 10-17 point array must contain exactly two entries
 #polygon((50pt,))
+
+---
+// Test the even-odd fill rule.
+// Ref: false
+#style(styles => {
+  let star = ((50pt, 0pt), (80pt, 90pt), (0pt, 35pt), (100pt, 35pt), (20pt, 90pt))
+  let size = measure(polygon(fill: green, fill-rule: "even-odd", ..star), styles)
+  test(size, (width: 100pt, height: 90pt))
+})
+
+---
+// Test regular polygons.
+// Ref: false
+#let approx(a, b) = assert(a > b - 0.01pt and a < b + 0.01pt)
+#style(styles => {
+  // The bounding box is tightly fit around the corners.
+  let triangle = measure(polygon.regular(fill: blue), styles)
+  approx(triangle.width, 20pt * calc.sin(60deg))
+  approx(triangle.height, 15pt)
+
+  let turned = measure(polygon.regular(rotation: 180deg, fill: blue), styles)
+  approx(turned.width, triangle.width)
+  approx(turned.height, triangle.height)
+
+  let square = measure(polygon.regular(vertices: 4, radius: 15pt), styles)
+  approx(square.width, 30pt)
+  approx(square.height, 30pt)
+
+  let hexagon = polygon.regular(vertices: 6, radius: 15pt, stroke: 2pt + red)
+  let hexagon = measure(hexagon, styles)
+  approx(hexagon.width, 30pt * calc.sin(60deg))
+  approx(hexagon.height, 30pt)
+})
+
+---
+// Error: 17-30 regular polygon must have at least 3 vertices
+#polygon.regular(vertices: 2)
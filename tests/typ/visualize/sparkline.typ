@@ -0,0 +1,24 @@
+// Test sparklines.
+// Ref: false
+
+---
+// The chart is an inline box sized relative to the font size.
+#style(styles => {
+  let size = measure(sparkline((3, 4, 2, 5, 7, 6, 9)), styles)
+  test(size, (width: 30pt, height: 10pt))
+  let line = sparkline((1.5, -2, 0.5), width: 2em, stroke: 1.5pt + blue)
+  test(measure(line, styles), (width: 20pt, height: 10pt))
+})
+
+---
+// Constant data is a flat line and a single value a dot, both in a box of the
+// same size.
+#style(styles => {
+  let size = (width: 30pt, height: 10pt)
+  test(measure(sparkline((2, 2, 2)), styles), size)
+  test(measure(sparkline((4,), stroke: red), styles), size)
+})
+
+---
+// Error: 11-15 sparkline must have at least one data point
+#sparkline(())
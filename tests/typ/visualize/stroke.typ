@@ -98,3 +98,11 @@
   ((0%, 50%), (4%, 4%)),
   ((50%, 0%), (4%, 4%)),
 )
+
+---
+// Error: 29-59 dash array must contain at least one non-zero length
+#line(length: 60pt, stroke: (paint: red, dash: (0pt, 0pt)))
+
+---
+// Error: 29-76 dash array must contain at least one non-zero length
+#line(length: 60pt, stroke: (paint: red, dash: (array: (0pt,), phase: 1pt)))